@@ -5,6 +5,7 @@ use super::App;
 use crate::errors::{ErrorButtons, ErrorFerris, process_running};
 use crate::helper::{Helper, ProcessName, ProcessState};
 use crate::inits::init_text_styles;
+use crate::miscs::set_proxy;
 use crate::{NODE_MIDDLE, P2POOL_MIDDLE, SECOND, XMRIG_MIDDLE, XMRIG_PROXY_MIDDLE, XVB_MIDDLE};
 use derive_more::derive::{Deref, DerefMut};
 use log::debug;
@@ -34,6 +35,8 @@ impl eframe::App for App {
             init_text_styles(ctx, self.state.gupax.selected_scale);
             self.must_resize = false;
         }
+        // the proxy is used by the connections and the processes started from now on.
+        set_proxy(&self.state.gupax.proxy);
         // check for windows that a local instance of xmrig is not running outside of Gupaxx. Important because it could lead to crashes on this platform.
         // Warn only once per restart of Gupaxx.
         #[cfg(target_os = "windows")]
@@ -169,7 +169,8 @@ impl App {
                 Tab::Status => match self.state.status.submenu {
                     Submenu::Processes => self.state.status.submenu = Submenu::Benchmarks,
                     Submenu::P2pool => self.state.status.submenu = Submenu::Processes,
-                    Submenu::Benchmarks => self.state.status.submenu = Submenu::P2pool,
+                    Submenu::Xmrig => self.state.status.submenu = Submenu::P2pool,
+                    Submenu::Benchmarks => self.state.status.submenu = Submenu::Xmrig,
                 },
                 Tab::Gupax => flip!(self.state.gupax.simple),
                 Tab::Node => flip!(self.state.node.simple),
@@ -184,7 +185,8 @@ impl App {
             match self.tab {
                 Tab::Status => match self.state.status.submenu {
                     Submenu::Processes => self.state.status.submenu = Submenu::P2pool,
-                    Submenu::P2pool => self.state.status.submenu = Submenu::Benchmarks,
+                    Submenu::P2pool => self.state.status.submenu = Submenu::Xmrig,
+                    Submenu::Xmrig => self.state.status.submenu = Submenu::Benchmarks,
                     Submenu::Benchmarks => self.state.status.submenu = Submenu::Processes,
                 },
                 Tab::Gupax => flip!(self.state.gupax.simple),
@@ -12,10 +12,14 @@ use crate::GUPAX_VERSION;
 use crate::OS;
 use crate::cli::Cli;
 use crate::cli::parse_args;
+use crate::components::bench::Bench;
 use crate::components::gupax::FileWindow;
 use crate::components::node::Ping;
 use crate::components::node::REMOTE_NODES;
 use crate::components::node::RemoteNode;
+use crate::components::node_discovery::NodeDiscovery;
+use crate::components::node_storage::NodeStorage;
+use crate::components::payout_import::PayoutImport;
 use crate::components::update::Update;
 use crate::disk::consts::NODE_TOML;
 use crate::disk::consts::POOL_TOML;
@@ -103,6 +107,10 @@ pub struct App {
     pub update: Arc<Mutex<Update>>, // State for update data [update.rs]
     pub file_window: Arc<Mutex<FileWindow>>, // State for the path selector in [Gupax]
     pub ping: Arc<Mutex<Ping>>,     // Ping data found in [node.rs]
+    pub payout_import: Arc<Mutex<PayoutImport>>, // Payout import data found in [payout_import.rs]
+    pub node_storage: Arc<Mutex<NodeStorage>>, // Node DB move/prune in [node_storage.rs]
+    pub node_discovery: Arc<Mutex<NodeDiscovery>>, // Remote node discovery in [node_discovery.rs]
+    pub bench: Arc<Mutex<Bench>>,   // Local XMRig benchmarks in [bench.rs]
     pub og_node_vec: Vec<(String, PoolNode)>, // Manual Node database
     pub node_vec: Vec<(String, PoolNode)>, // Manual Node database
     pub og_pool_vec: Vec<(String, PoolNode)>, // Manual Pool database
@@ -285,6 +293,10 @@ impl App {
         let mut app = Self {
             tab: Tab::default(),
             ping: arc_mut!(Ping::new()),
+            payout_import: arc_mut!(PayoutImport::new()),
+            node_storage: arc_mut!(NodeStorage::new()),
+            node_discovery: arc_mut!(NodeDiscovery::new()),
+            bench: arc_mut!(Bench::new()),
             size: vec2(APP_DEFAULT_WIDTH, APP_DEFAULT_HEIGHT),
             must_resize: true,
             og: arc_mut!(State::new()),
@@ -414,6 +426,16 @@ impl App {
         app.node_path.push(NODE_TOML);
         app.pool_path.clone_from(&app.os_data_path);
         app.pool_path.push(POOL_TOML);
+        // Install the ban list of the node
+        crate::disk::state::Node::install_ban_list(&app.os_data_path);
+        // Remote nodes discovered and scored in the previous sessions
+        app.node_discovery.lock().unwrap().init(&app.os_data_path);
+        // Local benchmarks of the previous sessions
+        app.bench.lock().unwrap().init(&app.os_data_path);
+        app.ping
+            .lock()
+            .unwrap()
+            .init(&app.os_data_path, &app.node_api);
         // Set GupaxP2poolApi path
         app.gupax_p2pool_api_path = crate::disk::get_gupax_p2pool_path(&app.os_data_path);
         app.gupax_p2pool_api
@@ -553,6 +575,7 @@ impl App {
         if current > max {
             og.xmrig.current_threads = max;
         }
+        og.xmrig.affinity.retain(|cpu| *cpu < max);
         // Handle [node_vec] overflow
         info!("App Init | Handling [node_vec] overflow");
         if og.p2pool.selected_node.index > app.og_node_vec.len() {
@@ -755,13 +778,22 @@ impl App {
 
             // Locking during this entire loop should be fine,
             // only a few nodes to iter through.
-            for pinged_node in self.ping.lock().unwrap().nodes.iter() {
+            let ping = self.ping.lock().unwrap();
+            for pinged_node in ping.nodes.iter() {
                 // Continue if this node is not green/yellow.
                 if pinged_node.ms > crate::components::node::RED_NODE_PING {
                     continue;
                 }
+                // Continue if this node scored badly over the last pings.
+                if ping.is_bad(&pinged_node.ip) {
+                    info!(
+                        "Backup hosts ... skipping [{}], consistently bad score",
+                        pinged_node.ip
+                    );
+                    continue;
+                }
 
-                let (ip, rpc, zmq) = RemoteNode::get_ip_rpc_zmq(pinged_node.ip);
+                let (ip, rpc, zmq) = RemoteNode::get_ip_rpc_zmq(&pinged_node.ip);
 
                 let node = Node {
                     ip: ip.into(),
@@ -4,7 +4,7 @@ use crate::app::eframe_impl::{ProcessStateGui, ProcessStatesGui};
 use crate::app::{Restart, keys::KeyPressed};
 use crate::disk::node::Node;
 use crate::disk::pool::Pool;
-use crate::disk::state::{Gupax, State};
+use crate::disk::state::{Gupax, ProxyOptions, State};
 use crate::disk::status::Submenu;
 use crate::errors::process_running;
 use crate::helper::{Helper, ProcessName, ProcessSignal};
@@ -275,6 +275,7 @@ impl crate::app::App {
                                 &self.helper,
                                 &self.state.node,
                                 &self.state.gupax.absolute_node_path,
+                                self.ping.lock().unwrap().bootstrap_address(),
                             );
                         }
                         ProcessName::P2pool => {
@@ -288,7 +289,12 @@ impl crate::app::App {
                             );
                         }
                         ProcessName::Xmrig => {
-                            if cfg!(windows) || !Helper::password_needed() {
+                            if cfg!(windows)
+                                || !Helper::xmrig_password_needed(
+                                    &self.state.xmrig,
+                                    &self.state.gupax.absolute_xmrig_path,
+                                )
+                            {
                                 Helper::restart_xmrig(
                                     &self.helper,
                                     &self.state.xmrig,
@@ -351,6 +357,7 @@ impl crate::app::App {
                                 &self.helper,
                                 &self.state.node,
                                 &self.state.gupax.absolute_node_path,
+                                self.ping.lock().unwrap().bootstrap_address(),
                             ),
                             ProcessName::P2pool => Helper::start_p2pool(
                                 &self.helper,
@@ -362,7 +369,12 @@ impl crate::app::App {
                             ),
 
                             ProcessName::Xmrig => {
-                                if cfg!(windows) || !Helper::password_needed() {
+                                if cfg!(windows)
+                                    || !Helper::xmrig_password_needed(
+                                        &self.state.xmrig,
+                                        &self.state.gupax.absolute_xmrig_path,
+                                    )
+                                {
                                     Helper::start_xmrig(
                                         &self.helper,
                                         &self.state.xmrig,
@@ -458,7 +470,7 @@ impl crate::app::App {
         // ui.style_mut().wrap = Some(true);
         ui.group(|ui| {
             let spacing = spacing(ui);
-            let width = ((ui.available_width() / 1.5 / 3.0) - spacing).max(0.0);
+            let width = ((ui.available_width() / 1.5 / 4.0) - spacing).max(0.0);
             if ui
                 .add_sized(
                     [width, ui.available_height()],
@@ -484,6 +496,17 @@ impl crate::app::App {
                 self.state.status.submenu = Submenu::P2pool;
             }
             ui.separator();
+            if ui
+                .add_sized(
+                    [width, ui.available_height()],
+                    SelectableLabel::new(self.state.status.submenu == Submenu::Xmrig, "XMRig"),
+                )
+                .on_hover_text(STATUS_SUBMENU_XMRIG)
+                .clicked()
+            {
+                self.state.status.submenu = Submenu::Xmrig;
+            }
+            ui.separator();
             if ui
                 .add_sized(
                     [width, ui.available_height()],
@@ -599,6 +622,13 @@ impl crate::app::App {
     pub fn start_ready(&self, state: &ProcessStateGui) -> Result<(), String> {
         // custom check and var
         let name = state.name;
+        // the proxy is given to the processes.
+        if name != ProcessName::Xvb {
+            if let Err(e) = self.state.gupax.proxy.validate() {
+                return Err(format!("Error: {}", e));
+            }
+        }
+        let proxy = self.state.gupax.proxy.enabled;
         let path = match name {
             ProcessName::Node => {
                 // check path of DB valid, empty valid.
@@ -607,6 +637,23 @@ impl crate::app::App {
                 {
                     return Err(format!("Error: {}", NODE_DB_DIR));
                 }
+                // check path of the ban list valid, empty valid since the bundled one is used.
+                if self.state.node.ban_list
+                    && !self.state.node.ban_list_path.is_empty()
+                    && !Gupax::path_is_file(&self.state.node.ban_list_path)
+                {
+                    return Err(format!("Error: {}", NODE_BAN_LIST_FILE));
+                }
+                // the database can not be used while it is moved or pruned.
+                if self.node_storage.lock().unwrap().busy {
+                    return Err(format!("Error: {}", NODE_STORAGE_BUSY));
+                }
+                // check the RPC options used in advanced mode.
+                if !self.state.node.simple && self.state.node.arguments.is_empty() {
+                    if let Err(e) = self.state.node.validate_options() {
+                        return Err(format!("Error: {}", e));
+                    }
+                }
                 &self.state.gupax.node_path
             }
             ProcessName::P2pool => {
@@ -614,9 +661,38 @@ impl crate::app::App {
                 if !Regexes::addr_ok(&self.state.p2pool.address) {
                     return Err(format!("Error: {}", P2POOL_ADDRESS));
                 }
+                // check the options used in advanced mode.
+                if !self.state.p2pool.simple && self.state.p2pool.arguments.is_empty() {
+                    if let Err(e) = self.state.p2pool.options.validate() {
+                        return Err(format!("Error: {}", e));
+                    }
+                    // a .onion node needs a proxy, the one of the options or the global one.
+                    if ProxyOptions::is_onion(&self.state.p2pool.ip)
+                        && !proxy
+                        && self.state.p2pool.options.socks5.is_empty()
+                    {
+                        return Err(format!("Error: {}", GUPAX_PROXY_ONION));
+                    }
+                }
+                // check merge mining, not used in custom mode.
+                if self.state.p2pool.simple || self.state.p2pool.arguments.is_empty() {
+                    if let Err(e) = self.state.p2pool.validate_merge_mining() {
+                        return Err(format!("Error: {}", e));
+                    }
+                }
                 &self.state.gupax.p2pool_path
             }
-            ProcessName::Xmrig => &self.state.gupax.xmrig_path,
+            ProcessName::Xmrig => {
+                // a .onion pool needs the proxy.
+                if !self.state.xmrig.simple
+                    && self.state.xmrig.arguments.is_empty()
+                    && ProxyOptions::is_onion(&self.state.xmrig.ip)
+                    && !proxy
+                {
+                    return Err(format!("Error: {}", GUPAX_PROXY_ONION));
+                }
+                &self.state.gupax.xmrig_path
+            }
             ProcessName::XmrigProxy => &self.state.gupax.xmrig_proxy_path,
             ProcessName::Xvb => {
                 if !Regexes::addr_ok(&self.state.p2pool.address) {
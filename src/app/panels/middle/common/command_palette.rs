@@ -0,0 +1,176 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use egui::{Grid, RichText, ScrollArea, TextEdit, TextStyle, Ui};
+use strum::IntoEnumIterator;
+
+use crate::helper::Process;
+use crate::helper::commands::{PaletteCommand, ResponseTable};
+use crate::{LIGHT_GRAY, RED, SPACE};
+
+// Output printed after this time is not part of the response.
+const RESPONSE_WINDOW: Duration = Duration::from_secs(3);
+
+#[derive(Clone)]
+struct Palette<C> {
+    selected: C,
+    args: Vec<String>,
+    error: Option<&'static str>,
+    sent: Option<Sent<C>>,
+}
+
+// Last command sent, its response is the console output between [offset] and [end].
+#[derive(Clone)]
+struct Sent<C> {
+    command: C,
+    lines: Vec<String>,
+    offset: usize,
+    end: Option<usize>,
+    time: Instant,
+}
+
+// Buttons for the common commands of a process, with the fields of their arguments.
+// The response of the last command is shown as a table under the console.
+pub fn command_palette<C: PaletteCommand + IntoEnumIterator>(
+    ui: &mut Ui,
+    process: &Arc<Mutex<Process>>,
+    output: &str,
+    id_salt: &str,
+) {
+    let id = ui.make_persistent_id(id_salt);
+    let Some(first) = C::iter().next() else {
+        return;
+    };
+    let mut palette = ui
+        .data_mut(|d| d.get_temp::<Palette<C>>(id))
+        .unwrap_or_else(|| Palette {
+            selected: first,
+            args: vec![],
+            error: None,
+            sent: None,
+        });
+    let alive = process.lock().unwrap().is_alive();
+    ui.horizontal_wrapped(|ui| {
+        for command in C::iter() {
+            if ui
+                .selectable_label(palette.selected == command, command.to_string())
+                .on_hover_text(command.hover())
+                .clicked()
+            {
+                palette.selected = command;
+                palette.args.clear();
+                palette.error = None;
+            }
+        }
+    });
+    let command = palette.selected;
+    palette.args.resize(command.args().len(), String::new());
+    ui.horizontal_wrapped(|ui| {
+        for ((name, hint, optional), arg) in command.args().iter().zip(palette.args.iter_mut()) {
+            if *optional {
+                ui.label(format!("{name} (optional):"));
+            } else {
+                ui.label(format!("{name}:"));
+            }
+            ui.add(
+                TextEdit::singleline(arg)
+                    .hint_text(*hint)
+                    .desired_width(ui.text_style_height(&TextStyle::Body) * 10.0),
+            );
+        }
+        ui.add_enabled_ui(alive, |ui| {
+            if ui
+                .button(format!("Send {command}"))
+                .on_hover_text(command.hover())
+                .on_disabled_hover_text("The process must be running")
+                .clicked()
+            {
+                match command.lines(&palette.args) {
+                    Ok(lines) => {
+                        process.lock().unwrap().input.extend(lines.iter().cloned());
+                        palette.error = None;
+                        palette.sent = Some(Sent {
+                            command,
+                            lines,
+                            offset: output.len(),
+                            end: None,
+                            time: Instant::now(),
+                        });
+                    }
+                    Err(e) => palette.error = Some(e),
+                }
+            }
+        });
+    });
+    if let Some(error) = palette.error {
+        ui.label(RichText::new(error).color(RED));
+    }
+    if let Some(sent) = &mut palette.sent {
+        if sent.end.is_none() && sent.time.elapsed() > RESPONSE_WINDOW {
+            sent.end = Some(output.len());
+        }
+        ui.add_space(SPACE);
+        // the console can be reset by the watchdog in the meantime.
+        match output.get(sent.offset..sent.end.unwrap_or(output.len())) {
+            Some(text) => {
+                let table = ResponseTable::parse(sent.command, &sent.lines, text);
+                if table.notes.is_empty() && table.rows.is_empty() {
+                    if sent.end.is_none() {
+                        ui.spinner();
+                    } else {
+                        ui.label(RichText::new("No response").color(LIGHT_GRAY));
+                    }
+                } else {
+                    response_table(ui, &table, id);
+                }
+            }
+            None => {
+                ui.label(RichText::new("The console was reset, send the command again").color(RED));
+            }
+        }
+    }
+    ui.data_mut(|d| d.insert_temp(id, palette));
+}
+
+fn response_table(ui: &mut Ui, table: &ResponseTable, id: egui::Id) {
+    for note in &table.notes {
+        ui.label(note);
+    }
+    if table.rows.is_empty() {
+        return;
+    }
+    ScrollArea::both()
+        .id_salt(id.with("response"))
+        .max_height(ui.text_style_height(&TextStyle::Body) * 15.0)
+        .show(ui, |ui| {
+            Grid::new(id.with("grid")).striped(true).show(ui, |ui| {
+                for header in &table.headers {
+                    ui.label(RichText::new(header).underline().color(LIGHT_GRAY));
+                }
+                ui.end_row();
+                for row in &table.rows {
+                    for cell in row {
+                        ui.label(cell);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+}
@@ -26,7 +26,8 @@ use crate::disk::state::Gupax;
 use crate::miscs::height_txt_before_button;
 use crate::regex::Regexes;
 use crate::{
-    GREEN, GUPAX_SELECT, LIGHT_GRAY, NODE_DB_DIR, NODE_DB_PATH_EMPTY, NODE_PATH_OK, RED, SPACE,
+    GREEN, GUPAX_SELECT, LIGHT_GRAY, NODE_BAN_LIST_FILE, NODE_BAN_LIST_PATH_EMPTY, NODE_DB_DIR,
+    NODE_DB_PATH_EMPTY, NODE_PATH_OK, RED, SPACE,
 };
 
 pub fn slider_state_field(
@@ -198,6 +199,39 @@ pub fn path_db_field(ui: &mut Ui, path: &mut String, file_window: &Arc<Mutex<Fil
         });
     });
 }
+pub fn path_ban_list_field(ui: &mut Ui, path: &mut String, file_window: &Arc<Mutex<FileWindow>>) {
+    ui.horizontal(|ui| {
+        let symbol;
+        let color;
+        let hover;
+        if path.is_empty() {
+            symbol = "➖";
+            color = LIGHT_GRAY;
+            hover = NODE_BAN_LIST_PATH_EMPTY;
+        } else if !Gupax::path_is_file(path) {
+            symbol = "❌";
+            color = RED;
+            hover = NODE_BAN_LIST_FILE;
+        } else {
+            symbol = "✔";
+            color = GREEN;
+            hover = NODE_PATH_OK;
+        }
+        let text = ["Node Ban List ", symbol].concat();
+        ui.add_sized(
+            [0.0, height_txt_before_button(ui, &TextStyle::Body)],
+            Label::new(RichText::new(text).color(color)),
+        );
+        let window_busy = file_window.lock().unwrap().thread;
+        ui.add_enabled_ui(!window_busy, |ui| {
+            if ui.button("Open").on_hover_text(GUPAX_SELECT).clicked() {
+                Gupax::spawn_file_window_thread(file_window, FileType::BanList);
+            }
+            ui.spacing_mut().text_edit_width = ui.available_width();
+            ui.text_edit_singleline(path).on_hover_text(hover);
+        });
+    });
+}
 pub fn monero_address_field(address: &mut String, ui: &mut Ui, hover: &str) {
     ui.group(|ui| {
         let text;
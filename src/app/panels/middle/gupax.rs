@@ -6,6 +6,7 @@ use crate::components::update::Update;
 use crate::components::update::check_binary_path;
 use crate::disk::state::*;
 use crate::miscs::height_txt_before_button;
+use common::state_edit_field::StateTextEdit;
 use common::state_edit_field::slider_state_field;
 use log::debug;
 use std::path::Path;
@@ -117,6 +118,16 @@ impl Gupax {
                 ui.separator();
                 self.horizontal_flex_auto_start(ui, AutoStart::ALL);
             });
+            debug!("Gupaxx Tab | Rendering proxy settings");
+            ui.group(|ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add(Label::new(
+                        RichText::new("Proxy").underline().color(LIGHT_GRAY),
+                    ))
+                });
+                ui.separator();
+                self.proxy(ui);
+            });
             if self.simple {
                 return;
             }
@@ -375,6 +386,25 @@ impl Gupax {
             });
         });
     }
+    // SOCKS5 proxy used by Gupaxx and the processes, like a local Tor.
+    fn proxy(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.proxy.enabled, "Use a SOCKS5 proxy")
+                .on_hover_text(GUPAX_PROXY_ENABLED);
+            ui.add_enabled_ui(self.proxy.enabled, |ui| {
+                ui.checkbox(&mut self.proxy.tor, "Tor")
+                    .on_hover_text(GUPAX_PROXY_TOR);
+            });
+        });
+        ui.add_enabled_ui(self.proxy.enabled, |ui| {
+            StateTextEdit::new(ui)
+                .description(" PROXY ")
+                .max_ch(21)
+                .help_msg(GUPAX_PROXY_ADDRESS)
+                .validations(&[ProxyOptions::address_ok])
+                .build(ui, &mut self.proxy.address);
+        });
+    }
     /// widget: AutoStart variant and selectable label (true) or checkbox (false)
     pub fn horizontal_flex_show_processes(&mut self, ui: &mut Ui, processes: Vec<ProcessName>) {
         let text_style = TextStyle::Button;
@@ -68,7 +68,14 @@ impl crate::app::App {
                         states,
                         self.max_threads,
                         &self.gupax_p2pool_api,
+                        &self.payout_import,
+                        &mut self.state.p2pool,
+                        self.state.node.rpc_address(),
+                        self.state.node.rpc_login().map(str::to_string),
                         &self.benchmarks,
+                        &self.bench,
+                        &self.state.xmrig,
+                        &self.state.gupax.absolute_xmrig_path,
                         ctx,
                         ui,
                     );
@@ -91,12 +98,21 @@ impl crate::app::App {
                 }
                 Tab::Node => {
                     debug!("App | Entering [Node] Tab");
+                    // the recovery buttons start the node, with the checks of the Start button.
+                    let start_ready = self.start_ready(states.find(ProcessName::Node));
                     crate::disk::state::Node::show(
                         &mut self.state.node,
                         &self.node,
                         &self.node_api,
                         &mut self.node_stdin,
                         &self.file_window,
+                        &self.node_storage,
+                        &self.state.gupax.absolute_node_path,
+                        &self.ip_local,
+                        &self.ip_public,
+                        &self.helper,
+                        &self.ping,
+                        start_ready,
                         ui,
                     );
                 }
@@ -108,6 +124,7 @@ impl crate::app::App {
                         &mut self.node_vec,
                         &self.og,
                         &self.ping,
+                        &self.node_discovery,
                         &self.p2pool,
                         &self.p2pool_api,
                         &mut self.p2pool_stdin,
@@ -117,6 +134,7 @@ impl crate::app::App {
                         &self.state.gupax.absolute_p2pool_path,
                         zmq_port,
                         rpc_port,
+                        self.state.node.rpc_login().map(str::to_string),
                     );
                 }
                 Tab::Xmrig => {
@@ -130,6 +148,10 @@ impl crate::app::App {
                         ctx,
                         ui,
                         self.state.p2pool.stratum_port(),
+                        &self.sudo,
+                        &mut self.error_state,
+                        &self.state.gupax.absolute_xmrig_path,
+                        &self.pub_sys,
                     );
                 }
                 Tab::XmrigProxy => {
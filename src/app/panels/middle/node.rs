@@ -15,33 +15,63 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::app::panels::middle::common::command_palette::command_palette;
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
 use crate::app::panels::middle::common::header_tab::header_tab;
-use crate::app::panels::middle::common::state_edit_field::{path_db_field, slider_state_field};
+use crate::app::panels::middle::common::state_edit_field::{
+    StateTextEdit, path_ban_list_field, path_db_field, slider_state_field,
+};
 use crate::app::panels::middle::{rpc_bind_field, rpc_port_field, zmq_bind_field, zmq_port_field};
+use crate::components::gupax::FileType;
+use crate::components::node::Ping;
+use crate::components::node_storage::{NODE_STORAGE_REFRESH_SECS, NodeStorage, StorageInfo};
+use crate::disk::state::{Gupax, ProxyOptions};
+use crate::human::HumanNumber;
+use crate::regex::REGEXES;
+use crate::utils::constants::IP_NOT_FOUND;
 use crate::{
-    NODE_DNS_BLOCKLIST, NODE_DNS_CHECKPOINT, NODE_FULL_MEM, NODE_INPUT, NODE_PRUNNING, NODE_URL,
-    START_OPTIONS_HOVER,
+    NODE_BAN_LIST_COUNT, NODE_BAN_LIST_ENABLE, NODE_BAN_LIST_REFRESH, NODE_BOOTSTRAP_ADDRESS,
+    NODE_BOOTSTRAP_LOGIN, NODE_BOOTSTRAP_PRIVACY, NODE_BOOTSTRAP_PROXY, NODE_BOOTSTRAP_SIMPLE,
+    NODE_CONFIRM_EXTERNAL_BIND, NODE_DNS_BLOCKLIST, NODE_DNS_CHECKPOINT, NODE_FULL_MEM, NODE_INPUT,
+    NODE_LIMIT_RATE_DOWN, NODE_LIMIT_RATE_UP, NODE_PRUNNING, NODE_PUBLIC_NODE, NODE_PUBLIC_RPC,
+    NODE_PUBLIC_RPC_LOCAL, NODE_RECOVERY, NODE_RECOVERY_RESYNC, NODE_RECOVERY_SALVAGE,
+    NODE_RECOVERY_VERIFY, NODE_RESTRICTED_PORT, NODE_RESTRICTED_RPC, NODE_RPC_LOGIN,
+    NODE_STORAGE_DATA_DIR, NODE_STORAGE_MOVE, NODE_STORAGE_OLD_DB, NODE_STORAGE_PRUNE,
+    NODE_STORAGE_REFRESH, NODE_STORAGE_SPACE, NODE_URL, RED, START_OPTIONS_HOVER,
+    STATUS_NODE_BOOTSTRAP, YELLOW,
 };
-use egui::TextStyle;
+use egui::{ProgressBar, RichText, TextStyle, Ui};
+use readable::byte::Byte;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use log::debug;
+use log::{debug, warn};
 
 use crate::components::gupax::FileWindow;
 use crate::disk::state::{Node, StartOptionsMode};
+use crate::helper::commands::NodeCommand;
 use crate::helper::node::PubNodeApi;
-use crate::helper::{Process, ProcessName};
+use crate::helper::{Helper, Process, ProcessName};
 use crate::{P2POOL_IN, P2POOL_LOG, P2POOL_OUT, SPACE};
 
 impl Node {
     #[inline(always)] // called once
+    #[allow(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubNodeApi>>,
         buffer: &mut String,
         file_window: &Arc<Mutex<FileWindow>>,
+        storage: &Arc<Mutex<NodeStorage>>,
+        node_path: &Path,
+        local_ip: &Arc<Mutex<Option<IpAddr>>>,
+        public_ip: &Arc<Mutex<Option<Ipv4Addr>>>,
+        helper: &Arc<Mutex<Helper>>,
+        ping: &Arc<Mutex<Ping>>,
+        start_ready: Result<(), String>,
         ui: &mut egui::Ui,
     ) {
         ui.style_mut().override_text_style = Some(TextStyle::Body);
@@ -55,7 +85,11 @@ impl Node {
         // console output for log
         debug!("Node Tab | Rendering [Console]");
         egui::ScrollArea::vertical().show(ui, |ui| {
-            let text = &api.lock().unwrap().output;
+            let mut api = api.lock().unwrap();
+            // results of the storage jobs are written in the console.
+            let results = std::mem::take(&mut storage.lock().unwrap().console);
+            api.output.push_str(&results);
+            let text = &api.output;
             ui.group(|ui| {
                 console(ui, text, &mut self.console_height, ProcessName::Node);
                 if !self.simple {
@@ -67,8 +101,27 @@ impl Node {
                         r#"Commands: help, status, set_log <level>, diff"#,
                         NODE_INPUT,
                     );
+                    ui.separator();
+                    command_palette::<NodeCommand>(ui, process, text, "node_command_palette");
                 }
             });
+            //---------------------------------------------------------------------------------------------------- Recovery
+            if let Some(db_error) = &api.db_error {
+                debug!("Node Tab | Rendering [Recovery]");
+                ui.add_space(SPACE);
+                ui.group(|ui| {
+                    self.recovery(
+                        ui,
+                        db_error,
+                        process,
+                        storage,
+                        node_path,
+                        helper,
+                        ping,
+                        &start_ready,
+                    );
+                });
+            }
             //---------------------------------------------------------------------------------------------------- [Advanced] Console
             if !self.simple {
                 //---------------------------------------------------------------------------------------------------- Arguments
@@ -166,8 +219,411 @@ impl Node {
                         guard.picked_nodedb = false;
                     }
                 });
+                //---------------------------------------------------------------------------------------------------- RPC sharing
                 ui.add_space(SPACE);
+                debug!("Node Tab | Rendering [RPC sharing]");
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.options.restricted_rpc, "Restricted RPC")
+                            .on_hover_text(NODE_RESTRICTED_RPC);
+                        ui.separator();
+                        ui.checkbox(&mut self.options.public_node, "Public node")
+                            .on_hover_text(NODE_PUBLIC_NODE);
+                        ui.separator();
+                        ui.checkbox(
+                            &mut self.options.confirm_external_bind,
+                            "Confirm external bind",
+                        )
+                        .on_hover_text(NODE_CONFIRM_EXTERNAL_BIND);
+                    });
+                    ui.add_space(SPACE);
+                    self.restricted_port_field(ui);
+                    self.rpc_login_field(ui);
+                    self.limit_rate_up_field(ui);
+                    self.limit_rate_down_field(ui);
+                    if let Err(e) = self.validate_options() {
+                        ui.label(RichText::new(e).color(RED));
+                    }
+                    ui.add_space(SPACE);
+                    // Do not fetch public ip automatically, same as the XMRig-Proxy tab.
+                    ui.horizontal(|ui| {
+                        ui.label("Public RPC:").on_hover_text(NODE_PUBLIC_RPC);
+                        let Some(port) = self.public_rpc_port() else {
+                            ui.label(format!(
+                                "not shared, the RPC only listens on {}",
+                                self.api_ip
+                            ))
+                            .on_hover_text(NODE_PUBLIC_RPC_LOCAL);
+                            return;
+                        };
+                        match *local_ip.lock().unwrap() {
+                            Some(ip) => ui.label(format!("local network {ip}:{port}")),
+                            None => ui.label(IP_NOT_FOUND),
+                        };
+                        ui.separator();
+                        match *public_ip.lock().unwrap() {
+                            Some(ip) => ui.label(format!("internet {ip}:{port}")),
+                            None => ui.label(IP_NOT_FOUND),
+                        };
+                        if ui.button("refresh ip").clicked() {
+                            Helper::spawn_ip_fetch(helper);
+                        }
+                    });
+                });
+                ui.add_space(SPACE);
+            }
+            //---------------------------------------------------------------------------------------------------- Bootstrap daemon
+            debug!("Node Tab | Rendering [Bootstrap daemon]");
+            ui.group(|ui| {
+                if self.simple {
+                    ui.checkbox(
+                        &mut self.bootstrap,
+                        "Use the fastest remote node while syncing",
+                    )
+                    .on_hover_text(NODE_BOOTSTRAP_SIMPLE);
+                    if self.bootstrap {
+                        ui.label(RichText::new(NODE_BOOTSTRAP_PRIVACY).color(YELLOW));
+                    }
+                } else {
+                    self.bootstrap_address_field(ui);
+                    self.bootstrap_login_field(ui);
+                    self.bootstrap_proxy_field(ui);
+                }
+                ui.label(format!("Bootstrap daemon: {}", api.bootstrap_status()))
+                    .on_hover_text(STATUS_NODE_BOOTSTRAP);
+            });
+            ui.add_space(SPACE);
+            //---------------------------------------------------------------------------------------------------- Ban list
+            debug!("Node Tab | Rendering [Ban list]");
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.ban_list, "Ban spy nodes")
+                        .on_hover_text(NODE_BAN_LIST_ENABLE);
+                    ui.separator();
+                    // the count is kept for the file in use, and read again on refresh.
+                    let id = ui.make_persistent_id("node_ban_list_entries");
+                    let file = self.ban_list_file();
+                    let mut entries = ui.data_mut(|d| d.get_temp::<(Option<PathBuf>, String)>(id));
+                    if entries.as_ref().is_none_or(|(path, _)| *path != file) {
+                        let count = match &file {
+                            Some(path) => match Self::ban_list_entries(path) {
+                                Ok(count) => format!("{count} banned IPs/subnets"),
+                                Err(e) => format!("Could not read the ban list: {e}"),
+                            },
+                            None => "Ban list disabled".to_string(),
+                        };
+                        ui.data_mut(|d| d.insert_temp(id, (file, count.clone())));
+                        entries = Some((None, count));
+                    }
+                    if let Some((_, count)) = entries {
+                        ui.label(count).on_hover_text(NODE_BAN_LIST_COUNT);
+                    }
+                    if ui
+                        .button("Refresh")
+                        .on_hover_text(NODE_BAN_LIST_REFRESH)
+                        .clicked()
+                    {
+                        ui.data_mut(|d| d.remove::<(Option<PathBuf>, String)>(id));
+                    }
+                });
+                if !self.simple {
+                    ui.add_enabled_ui(self.ban_list, |ui| {
+                        path_ban_list_field(ui, &mut self.ban_list_path, file_window);
+                    });
+                    let mut guard = file_window.lock().unwrap();
+                    if guard.picked_ban_list {
+                        self.ban_list_path.clone_from(&guard.ban_list_path);
+                        guard.picked_ban_list = false;
+                    }
+                }
+            });
+            //---------------------------------------------------------------------------------------------------- Storage
+            ui.add_space(SPACE);
+            debug!("Node Tab | Rendering [Storage]");
+            ui.group(|ui| {
+                self.storage(ui, process, storage, file_window, node_path);
+            });
+        });
+    }
+
+    fn restricted_port_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description("RESTRICTED PORT")
+            .max_ch(5)
+            .help_msg(NODE_RESTRICTED_PORT)
+            .validations(&[|x| REGEXES.port.is_match(x)])
+            .build(ui, &mut self.options.restricted_port)
+    }
+    fn rpc_login_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description("  RPC LOGIN    ")
+            .max_ch(100)
+            .help_msg(NODE_RPC_LOGIN)
+            .validations(&[Node::rpc_login_ok])
+            .build(ui, &mut self.options.rpc_login)
+    }
+    fn bootstrap_address_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description("BOOTSTRAP ADDR ")
+            .max_ch(100)
+            .help_msg(NODE_BOOTSTRAP_ADDRESS)
+            .validations(&[Node::bootstrap_address_ok])
+            .build(ui, &mut self.options.bootstrap_address)
+    }
+    fn bootstrap_login_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description("BOOTSTRAP LOGIN")
+            .max_ch(100)
+            .help_msg(NODE_BOOTSTRAP_LOGIN)
+            .validations(&[Node::rpc_login_ok])
+            .build(ui, &mut self.options.bootstrap_login)
+    }
+    fn bootstrap_proxy_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description("BOOTSTRAP PROXY")
+            .max_ch(21)
+            .help_msg(NODE_BOOTSTRAP_PROXY)
+            .validations(&[ProxyOptions::address_ok])
+            .build(ui, &mut self.options.bootstrap_proxy)
+    }
+    fn limit_rate_up_field(&mut self, ui: &mut Ui) -> bool {
+        let mut limit = self.options.limit_rate_up.to_string();
+        let valid = StateTextEdit::new(ui)
+            .description("LIMIT UP kB/s  ")
+            .max_ch(9)
+            .help_msg(NODE_LIMIT_RATE_UP)
+            .validations(&[|x| x.parse::<u32>().is_ok()])
+            .build(ui, &mut limit);
+        if let Ok(limit) = limit.parse() {
+            self.options.limit_rate_up = limit;
+        }
+        valid
+    }
+    fn limit_rate_down_field(&mut self, ui: &mut Ui) -> bool {
+        let mut limit = self.options.limit_rate_down.to_string();
+        let valid = StateTextEdit::new(ui)
+            .description("LIMIT DOWN kB/s")
+            .max_ch(9)
+            .help_msg(NODE_LIMIT_RATE_DOWN)
+            .validations(&[|x| x.parse::<u32>().is_ok()])
+            .build(ui, &mut limit);
+        if let Ok(limit) = limit.parse() {
+            self.options.limit_rate_down = limit;
+        }
+        valid
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn recovery(
+        &mut self,
+        ui: &mut Ui,
+        db_error: &str,
+        process: &Arc<Mutex<Process>>,
+        storage: &Arc<Mutex<NodeStorage>>,
+        node_path: &Path,
+        helper: &Arc<Mutex<Helper>>,
+        ping: &Arc<Mutex<Ping>>,
+        start_ready: &Result<(), String>,
+    ) {
+        ui.label(RichText::new(format!("Database error: {db_error}")).color(RED))
+            .on_hover_text(NODE_RECOVERY);
+        let Some(data_dir) = self.data_dir(node_path) else {
+            return;
+        };
+        // the node is started again once the database is moved aside, with the checks of the Start button.
+        if std::mem::take(&mut storage.lock().unwrap().resync) {
+            match start_ready {
+                Ok(()) => Helper::start_node(
+                    helper,
+                    self,
+                    node_path,
+                    ping.lock().unwrap().bootstrap_address(),
+                ),
+                Err(e) => warn!("Node | Not started after the move aside ... {e}"),
+            }
+        }
+        let stopped = !process.lock().unwrap().is_alive();
+        let busy = storage.lock().unwrap().busy;
+        ui.add_enabled_ui(stopped && !busy, |ui| {
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(start_ready.is_ok(), |ui| {
+                    if ui
+                        .button("Retry with --db-salvage")
+                        .on_hover_text(NODE_RECOVERY_SALVAGE)
+                        .on_disabled_hover_text(start_ready.clone().err().unwrap_or_default())
+                        .clicked()
+                    {
+                        Helper::start_node_salvage(
+                            helper,
+                            self,
+                            node_path,
+                            ping.lock().unwrap().bootstrap_address(),
+                        );
+                    }
+                });
+                ui.separator();
+                if ui
+                    .button("Verify database")
+                    .on_hover_text(NODE_RECOVERY_VERIFY)
+                    .clicked()
+                {
+                    NodeStorage::spawn_verify(storage, node_path, data_dir.clone());
+                }
+                ui.separator();
+                if ui
+                    .button("Move aside and resync")
+                    .on_hover_text(NODE_RECOVERY_RESYNC)
+                    .clicked()
+                {
+                    NodeStorage::spawn_move_aside(storage, data_dir.clone());
+                }
+            });
+        });
+    }
+    fn storage(
+        &mut self,
+        ui: &mut Ui,
+        process: &Arc<Mutex<Process>>,
+        storage: &Arc<Mutex<NodeStorage>>,
+        file_window: &Arc<Mutex<FileWindow>>,
+        node_path: &Path,
+    ) {
+        let Some(data_dir) = self.data_dir(node_path) else {
+            ui.label(RichText::new("Could not find the data directory of the node").color(RED));
+            return;
+        };
+        // the new directory of a finished move becomes the one of the node.
+        if let Some(dir) = storage.lock().unwrap().moved_to.take() {
+            self.path_db = dir.display().to_string();
+        }
+        // sizes are read again when the directory changes, on refresh, every second during a job and after it.
+        let id = ui.make_persistent_id("node_storage_info");
+        let (busy, done) = {
+            let mut lock = storage.lock().unwrap();
+            (lock.busy, std::mem::take(&mut lock.done))
+        };
+        let mut info = ui.data_mut(|d| d.get_temp::<StorageInfo>(id));
+        let outdated = info.as_ref().is_none_or(|info| {
+            info.data_dir != data_dir
+                || (busy
+                    && info.read_at.elapsed() >= Duration::from_secs(NODE_STORAGE_REFRESH_SECS))
+        });
+        if done || outdated {
+            let read = StorageInfo::read(data_dir.clone());
+            ui.data_mut(|d| d.insert_temp(id, read.clone()));
+            info = Some(read);
+        }
+        let Some(info) = info else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label("Data directory:")
+                .on_hover_text(NODE_STORAGE_DATA_DIR);
+            ui.label(info.data_dir.display().to_string());
+        });
+        ui.horizontal(|ui| {
+            let size = info
+                .db_size
+                .map_or("No database".to_string(), |s| Byte::from(s).to_string());
+            ui.label(format!("Database size: {size}"));
+            ui.separator();
+            let free = info
+                .free_space
+                .map_or(HumanNumber::unknown().to_string(), |s| {
+                    Byte::from(s).to_string()
+                });
+            ui.label(format!("Free space: {free}"))
+                .on_hover_text(NODE_STORAGE_SPACE);
+        });
+        let pruned = self.pruned_db();
+        if let Some(missing) = info.space_missing(pruned) {
+            let node = if pruned { "pruned" } else { "full" };
+            ui.label(
+                RichText::new(format!(
+                    "Not enough free space for a {node} node, {} missing",
+                    Byte::from(missing)
+                ))
+                .color(RED),
+            )
+            .on_hover_text(NODE_STORAGE_SPACE);
+        }
+        if pruned && info.prunable() {
+            ui.label(
+                RichText::new("The existing database is not pruned, prune it to save space")
+                    .color(YELLOW),
+            )
+            .on_hover_text(NODE_STORAGE_PRUNE);
+        }
+        let stopped = !process.lock().unwrap().is_alive();
+        if let Some(size) = info.old_db_size {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "The database before pruning is still on disk ({})",
+                        Byte::from(size)
+                    ))
+                    .color(YELLOW),
+                )
+                .on_hover_text(NODE_STORAGE_OLD_DB);
+                ui.add_enabled_ui(stopped && !busy, |ui| {
+                    if ui
+                        .button("Delete old database")
+                        .on_hover_text(NODE_STORAGE_OLD_DB)
+                        .on_disabled_hover_text(NODE_STORAGE_OLD_DB)
+                        .clicked()
+                    {
+                        NodeStorage::spawn_delete_old(storage, data_dir.clone());
+                    }
+                });
+            });
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .button("Refresh")
+                .on_hover_text(NODE_STORAGE_REFRESH)
+                .clicked()
+            {
+                ui.data_mut(|d| d.remove::<StorageInfo>(id));
             }
+            let window_busy = file_window.lock().unwrap().thread;
+            let movable = !self.simple && self.arguments.is_empty() && info.db_size.is_some();
+            ui.add_enabled_ui(stopped && !busy && !window_busy && movable, |ui| {
+                if ui
+                    .button("Move database")
+                    .on_hover_text(NODE_STORAGE_MOVE)
+                    .on_disabled_hover_text(NODE_STORAGE_MOVE)
+                    .clicked()
+                {
+                    Gupax::spawn_file_window_thread(file_window, FileType::NodeDBMove);
+                }
+            });
+            ui.add_enabled_ui(stopped && !busy && info.prunable(), |ui| {
+                if ui
+                    .button("Prune database")
+                    .on_hover_text(NODE_STORAGE_PRUNE)
+                    .on_disabled_hover_text(NODE_STORAGE_PRUNE)
+                    .clicked()
+                {
+                    NodeStorage::spawn_prune(storage, node_path, data_dir.clone());
+                }
+            });
         });
+        let mut guard = file_window.lock().unwrap();
+        if guard.picked_nodedb_move {
+            guard.picked_nodedb_move = false;
+            // the node could have been started while the window was open.
+            if stopped && !busy {
+                let to = PathBuf::from(&guard.nodedb_move_path);
+                NodeStorage::spawn_move(storage, data_dir, to);
+            }
+        }
+        drop(guard);
+        let lock = storage.lock().unwrap();
+        if !lock.msg.is_empty() {
+            ui.add_space(SPACE);
+            ui.label(&lock.msg);
+            if lock.busy {
+                ui.add(ProgressBar::new(lock.prog / 100.0));
+            }
+        }
     }
 }
@@ -17,12 +17,15 @@
 
 use crate::app::panels::middle::common::list_poolnode::{PoolNode, list_poolnode};
 use crate::app::panels::middle::common::state_edit_field::{StateTextEdit, slider_state_field};
-use crate::disk::state::P2poolChain;
+use crate::disk::state::{MergeMine, P2poolChain};
 use crate::miscs::height_txt_before_button;
-use crate::{disk::state::P2pool, utils::regex::REGEXES};
+use crate::{
+    disk::state::P2pool,
+    utils::regex::{REGEXES, Regexes},
+};
 
 use crate::constants::*;
-use egui::{Checkbox, SelectableLabel, Ui};
+use egui::{Button, Checkbox, SelectableLabel, Ui};
 use log::*;
 
 impl P2pool {
@@ -122,6 +125,14 @@ impl P2pool {
                                     Checkbox::new(&mut self.backup_host, "Backup host"),
                                 )
                                 .on_hover_text(P2POOL_BACKUP_HOST_ADVANCED);
+                                // [Seed nano peers]
+                                ui.add_enabled_ui(self.chain == P2poolChain::Nano, |ui| {
+                                    ui.add_sized(
+                                        [(ui.available_width() / 2.0) - (SPACE * 2.0), height],
+                                        Checkbox::new(&mut self.seed_nano_peers, "Seed Nano peers"),
+                                    )
+                                    .on_hover_text(P2POOL_SEED_NANO_PEERS);
+                                });
                             });
                         });
                     });
@@ -155,7 +166,93 @@ impl P2pool {
                         })
                     });
                 });
+
+                debug!("P2Pool Tab | Rendering [Options] elements");
+                // [Light mode/RandomX/UPnP] + [Tor/Merge mining]
+                ui.horizontal(|ui| {
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut self.options.light_mode, "Light mode")
+                                .on_hover_text(P2POOL_LIGHT_MODE);
+                            ui.checkbox(&mut self.options.no_randomx, "No RandomX")
+                                .on_hover_text(P2POOL_NO_RANDOMX);
+                            ui.checkbox(&mut self.options.no_upnp, "No UPnP")
+                                .on_hover_text(P2POOL_NO_UPNP);
+                        });
+                    });
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            self.onion_address_field(ui);
+                            self.socks5_field(ui);
+                        });
+                    });
+                });
+                debug!("P2Pool Tab | Rendering [Merge mining] elements");
+                ui.group(|ui| {
+                    self.merge_mining(ui);
+                });
+            });
+    }
+    // The Tari preset first, then the other chains, each given once to P2Pool.
+    fn merge_mining(&mut self, ui: &mut Ui) {
+        self.tari();
+        ui.horizontal(|ui| {
+            ui.label("Merge mining")
+                .on_hover_text(P2POOL_MERGE_MINING_CHAINS);
+            if ui
+                .button("Add a chain")
+                .on_hover_text(P2POOL_MERGE_MINING_ADD)
+                .clicked()
+            {
+                self.merge_mine.push(MergeMine {
+                    enabled: true,
+                    host: String::new(),
+                    address: String::new(),
+                });
+            }
+        });
+        let mut remove = None;
+        for (i, chain) in self.merge_mine.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let name = if i == 0 {
+                    "Tari".to_string()
+                } else {
+                    format!("Chain {}", i + 1)
+                };
+                ui.checkbox(&mut chain.enabled, name)
+                    .on_hover_text(P2POOL_MERGE_MINING);
+                ui.add_enabled_ui(chain.enabled, |ui| {
+                    chain.host_field(ui);
+                    chain.address_field(ui);
+                });
+                if ui
+                    .add_enabled(i != 0, Button::new("✖"))
+                    .on_hover_text(P2POOL_MERGE_MINING_REMOVE)
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
             });
+        }
+        if let Some(i) = remove {
+            self.merge_mine.remove(i);
+        }
+    }
+    fn onion_address_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description(" ONION ADDRESS")
+            .max_ch(62)
+            .help_msg(P2POOL_ONION_ADDRESS)
+            .validations(&[|x| REGEXES.onion.is_match(x)])
+            .build(ui, &mut self.options.onion_address)
+    }
+    fn socks5_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description(" SOCKS5       ")
+            .max_ch(255)
+            .help_msg(P2POOL_SOCKS5)
+            .validations(&[Regexes::host_port_ok])
+            .build(ui, &mut self.options.socks5)
     }
     fn name_field(&mut self, ui: &mut Ui) -> bool {
         StateTextEdit::new(ui)
@@ -205,3 +302,27 @@ impl P2pool {
         valid
     }
 }
+
+impl MergeMine {
+    fn host_field(&mut self, ui: &mut Ui) -> bool {
+        StateTextEdit::new(ui)
+            .description(" NODE   ")
+            .max_ch(255)
+            .help_msg(P2POOL_MERGE_MINING_HOST)
+            .validations(&[MergeMine::host_ok])
+            .build(ui, &mut self.host)
+    }
+    pub(super) fn address_field(&mut self, ui: &mut Ui) -> bool {
+        let validations: &[fn(&str) -> bool] = if self.is_tari() {
+            &[Regexes::tari_addr_ok]
+        } else {
+            &[|x| !x.trim().is_empty()]
+        };
+        StateTextEdit::new(ui)
+            .description(" WALLET ")
+            .max_ch(255)
+            .help_msg(P2POOL_MERGE_MINING_ADDRESS)
+            .validations(validations)
+            .build(ui, &mut self.address)
+    }
+}
@@ -1,5 +1,8 @@
+use crate::app::panels::middle::common::command_palette::command_palette;
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
+use crate::components::node_discovery::NodeDiscovery;
 use crate::disk::state::{P2pool, StartOptionsMode, State};
+use crate::helper::commands::P2poolCommand;
 use crate::helper::p2pool::PubP2poolApi;
 // Gupaxx - Fork of Gupax
 //
@@ -37,6 +40,7 @@ impl P2pool {
         node_vec: &mut Vec<(String, PoolNode)>,
         _og: &Arc<Mutex<State>>,
         ping: &Arc<Mutex<Ping>>,
+        discovery: &Arc<Mutex<NodeDiscovery>>,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubP2poolApi>>,
         buffer: &mut String,
@@ -46,6 +50,7 @@ impl P2pool {
         path: &Path,
         local_node_zmq_port: u16,
         local_node_rpc_port: u16,
+        local_node_login: Option<String>,
     ) {
         //---------------------------------------------------------------------------------------------------- [Simple] Console
         // debug!("P2Pool Tab | Rendering [Console]");
@@ -71,6 +76,8 @@ impl P2pool {
                         r#"Type a command (e.g "help" or "status") and press Enter"#,
                         P2POOL_INPUT,
                     );
+                    ui.separator();
+                    command_palette::<P2poolCommand>(ui, process, text, "p2pool_command_palette");
                 }
             });
             if !self.simple {
@@ -105,7 +112,14 @@ impl P2pool {
             );
 
             if self.simple {
-                self.simple(ui, ping, &mut api_lock);
+                self.simple(
+                    ui,
+                    ping,
+                    discovery,
+                    local_node_rpc_port,
+                    local_node_login,
+                    &mut api_lock,
+                );
             } else {
                 if !self.arguments.is_empty() {
                     ui.disable();
@@ -19,11 +19,16 @@ use std::sync::Arc;
 use std::sync::Mutex;
 
 use crate::app::panels::middle::ProgressBar;
+use crate::app::panels::middle::common::state_edit_field::StateTextEdit;
 use crate::components::node::Ping;
 use crate::components::node::RemoteNode;
 use crate::components::node::format_ip_location;
 use crate::components::node::format_ms;
+use crate::components::node_discovery::NodeDiscovery;
+use crate::components::node_discovery::parse_seeds;
+use crate::components::node_discovery::seeds_ok;
 use crate::disk::state::P2pool;
+use crate::human::HumanTime;
 use crate::miscs::height_txt_before_button;
 use egui::Button;
 use egui::Checkbox;
@@ -39,11 +44,31 @@ use log::*;
 use super::p2pool::PubP2poolApi;
 
 impl P2pool {
-    pub(super) fn simple(&mut self, ui: &mut Ui, ping: &Arc<Mutex<Ping>>, api: &mut PubP2poolApi) {
+    pub(super) fn simple(
+        &mut self,
+        ui: &mut Ui,
+        ping: &Arc<Mutex<Ping>>,
+        discovery: &Arc<Mutex<NodeDiscovery>>,
+        local_node_rpc_port: u16,
+        local_node_login: Option<String>,
+        api: &mut PubP2poolApi,
+    ) {
         ui.vertical_centered(|ui|{
             ui.add_space(SPACE);
             ui.checkbox(&mut self.local_node, "Start with a local node").on_hover_text("If checked (recommended), p2pool will start trying to use the local node.\nCheck the Node tab to start a local node.\nIf unchecked, p2pool will attempt to use a remote node.");
         });
+        debug!("P2Pool Tab | Rendering [Merge mining]");
+        ui.add_space(SPACE);
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let tari = self.tari();
+                ui.checkbox(&mut tari.enabled, "Merge mine Tari")
+                    .on_hover_text(P2POOL_MERGE_MINING);
+                ui.add_enabled_ui(tari.enabled, |ui| {
+                    tari.address_field(ui);
+                });
+            });
+        });
         ui.add_space(SPACE * 2.0);
         // if checked, use only local node
         // if unchecked, show remote nodes.
@@ -84,12 +109,22 @@ impl P2pool {
                         .selected_text(text)
                         .width(ui.available_width())
                         .show_ui(ui, |ui| {
-                            for data in ping.lock().unwrap().nodes.iter() {
+                            let ping = ping.lock().unwrap();
+                            for data in ping.nodes.iter() {
                                 let ms = format_ms(data.ms);
-                                let ip_location = format_ip_location(data.ip, true);
-                                let text = RichText::new(format!(" ⏺ {} | {}", ms, ip_location))
-                                    .color(data.color);
-                                ui.selectable_value(&mut self.node, data.ip.to_string(), text);
+                                let ip_location = format_ip_location(&data.ip, true);
+                                let score = if ping.pinged {
+                                    format!("{:>3}", data.score)
+                                } else {
+                                    "???".to_string()
+                                };
+                                let text = RichText::new(format!(
+                                    " ⏺ {} | {}/100 | {}",
+                                    ms, score, ip_location
+                                ))
+                                .color(data.color);
+                                ui.selectable_value(&mut self.node, data.ip.to_string(), text)
+                                    .on_hover_text(P2POOL_NODE_SCORE);
                             }
                         });
                 });
@@ -234,8 +269,23 @@ impl P2pool {
                                     api.prefer_local_node = self.prefer_local_node;
                                     // api.lock().unwrap().prefer_local_node = self.prefer_local_node;
                                 }
-                            })
+                            });
+                            if self.prefer_local_node {
+                                if let Some(eta) = api.local_node_eta {
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "Switching to the local node once caught up, in about {}",
+                                            HumanTime::into_human(eta).display(false)
+                                        ))
+                                        .color(YELLOW),
+                                    )
+                                    .on_hover_text(P2POOL_AUTOSWITCH_LOCAL_NODE_ETA);
+                                }
+                            }
                         });
+                        debug!("P2Pool Tab | Rendering [Node discovery]");
+                        ui.add_space(SPACE);
+                        ui.group(|ui| self.discovery(ui, ping, discovery, local_node_rpc_port, local_node_login));
                     });
             });
             debug!("P2Pool Tab | Rendering warning text");
@@ -249,4 +299,50 @@ impl P2pool {
             });
         });
     }
+    // Seeds and button of the node discovery, with its progress.
+    fn discovery(
+        &mut self,
+        ui: &mut Ui,
+        ping: &Arc<Mutex<Ping>>,
+        discovery: &Arc<Mutex<NodeDiscovery>>,
+        local_node_rpc_port: u16,
+        local_node_login: Option<String>,
+    ) {
+        StateTextEdit::new(ui)
+            .description(" SEEDS ")
+            .max_ch(255)
+            .help_msg(P2POOL_DISCOVERY_SEEDS)
+            .validations(&[seeds_ok])
+            .build(ui, &mut self.discovery_seeds);
+        ui.horizontal(|ui| {
+            let busy = discovery.lock().unwrap().busy || ping.lock().unwrap().pinging;
+            ui.add_enabled_ui(!busy, |ui| {
+                if ui
+                    .button("Discover nodes")
+                    .on_hover_text(P2POOL_DISCOVERY)
+                    .clicked()
+                {
+                    // invalid seeds are shown by the field and ignored.
+                    let seeds = parse_seeds(&self.discovery_seeds).unwrap_or_default();
+                    NodeDiscovery::spawn_thread(
+                        discovery,
+                        ping,
+                        local_node_rpc_port,
+                        local_node_login,
+                        seeds,
+                    );
+                }
+            });
+            let discovery = discovery.lock().unwrap();
+            if discovery.busy {
+                ui.spinner();
+                ui.label(format!("{} ... {}%", discovery.msg, discovery.prog.round()));
+            } else {
+                ui.label(format!(
+                    "{} | {} discovered node(s)",
+                    discovery.msg, discovery.found
+                ));
+            }
+        });
+    }
 }
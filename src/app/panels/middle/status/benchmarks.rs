@@ -15,16 +15,27 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use crate::{app::Benchmark, disk::state::Status, helper::xrig::xmrig::PubXmrigApi};
-use egui::{ProgressBar, ScrollArea, TextWrapMode};
+use crate::{
+    app::Benchmark,
+    components::bench::Bench,
+    disk::{
+        bench_runs::BenchRun,
+        state::{Status, Xmrig},
+        status::{BenchMode, BenchSize},
+    },
+    helper::xrig::xmrig::PubXmrigApi,
+};
+use egui::{Grid, ProgressBar, ScrollArea, SelectableLabel, Slider, TextWrapMode};
 use egui_extras::{Column, TableBuilder};
 use readable::num::{Float, Percent, Unsigned};
 
 use crate::constants::*;
 use egui::{Label, RichText};
 use log::*;
+use strum::IntoEnumIterator;
 impl Status {
     pub(super) fn benchmarks(
         &mut self,
@@ -32,6 +43,10 @@ impl Status {
         benchmarks: &[Benchmark],
         xmrig_alive: bool,
         xmrig_api: &Arc<Mutex<PubXmrigApi>>,
+        bench: &Arc<Mutex<Bench>>,
+        state_xmrig: &Xmrig,
+        xmrig_path: &Path,
+        max_threads: u16,
     ) {
         debug!("Status Tab | Rendering [Benchmarks]");
         let text = ui.text_style_height(&egui::TextStyle::Body);
@@ -115,6 +130,13 @@ impl Status {
                 });
             }
             ui.add_space(SPACE);
+        });
+        // Local runs of [xmrig --bench]
+        let runs = self.local_bench(ui, bench, xmrig_alive, state_xmrig, xmrig_path, max_threads);
+        // the fastest recorded CPU, the reference of the relative hashrate.
+        let top = benchmarks.iter().map(|b| b.high).fold(0.0, f32::max);
+        ui.vertical_centered(|ui| {
+            ui.add_space(SPACE);
             // Comparison
             ui.group(|ui| {
                 ui.hyperlink_to("Other CPUs", "https://xmrig.com/benchmark")
@@ -168,8 +190,20 @@ impl Status {
                     });
                 })
                 .body(|body| {
-                    body.rows(text, benchmarks.len(), |mut row| {
-                        let benchmark = &benchmarks[row.index()];
+                    // local runs first, newest on top.
+                    body.rows(text, runs.len() + benchmarks.len(), |mut row| {
+                        if let Some(run) = runs.iter().rev().nth(row.index()) {
+                            row.set_selected(true);
+                            Self::local_row(
+                                row,
+                                run,
+                                top,
+                                benchmarks,
+                                [cpu, bar, high, rank, text],
+                            );
+                            return;
+                        }
+                        let benchmark = &benchmarks[row.index() - runs.len()];
                         row.col(|ui| {
                             ui.add_sized([cpu, text], Label::new(benchmark.cpu.as_str()));
                         });
@@ -219,4 +253,208 @@ impl Status {
                 });
         });
     }
+    // Controls of the local benchmark and its runs side by side, returns the runs.
+    fn local_bench(
+        &mut self,
+        ui: &mut egui::Ui,
+        bench: &Arc<Mutex<Bench>>,
+        xmrig_alive: bool,
+        state_xmrig: &Xmrig,
+        xmrig_path: &Path,
+        max_threads: u16,
+    ) -> Vec<BenchRun> {
+        if self.bench_threads == 0 || self.bench_threads > max_threads {
+            self.bench_threads = max_threads;
+        }
+        let lock = bench.lock().unwrap();
+        let (busy, runs) = (lock.busy, lock.runs.runs.clone());
+        let (msg, elapsed) = (lock.msg.clone(), lock.start.map(|s| s.elapsed().as_secs()));
+        drop(lock);
+        let mut remove = None;
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(RichText::new("Local benchmark").underline().color(BONE))
+                    .on_hover_text(STATUS_SUBMENU_BENCH);
+            });
+            ui.add_enabled_ui(!busy, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Hashes:");
+                    for size in BenchSize::iter() {
+                        if ui
+                            .add(SelectableLabel::new(
+                                self.bench_size == size,
+                                size.to_string(),
+                            ))
+                            .on_hover_text(STATUS_SUBMENU_BENCH_SIZE)
+                            .clicked()
+                        {
+                            self.bench_size = size;
+                        }
+                    }
+                    ui.separator();
+                    ui.label("RandomX mode:");
+                    for mode in BenchMode::iter() {
+                        if ui
+                            .add(SelectableLabel::new(
+                                self.bench_mode == mode,
+                                mode.to_string(),
+                            ))
+                            .on_hover_text(STATUS_SUBMENU_BENCH_MODE)
+                            .clicked()
+                        {
+                            self.bench_mode = mode;
+                        }
+                    }
+                    ui.separator();
+                    ui.label("Threads:");
+                    ui.add(Slider::new(&mut self.bench_threads, 1..=max_threads))
+                        .on_hover_text(STATUS_SUBMENU_BENCH_THREADS);
+                });
+            });
+            ui.horizontal(|ui| {
+                let runnable = !busy && !xmrig_alive && xmrig_path.is_file();
+                let hover = if xmrig_alive {
+                    STATUS_SUBMENU_BENCH_XMRIG_ALIVE
+                } else if !xmrig_path.is_file() {
+                    STATUS_SUBMENU_BENCH_XMRIG_PATH
+                } else {
+                    STATUS_SUBMENU_BENCH_RUN
+                };
+                if ui
+                    .add_enabled(runnable, egui::Button::new("Run"))
+                    .on_hover_text(hover)
+                    .on_disabled_hover_text(hover)
+                    .clicked()
+                {
+                    Bench::spawn(
+                        bench,
+                        xmrig_path,
+                        self.bench_size,
+                        self.bench_mode,
+                        self.bench_threads,
+                        state_xmrig,
+                    );
+                }
+                if ui
+                    .add_enabled(busy, egui::Button::new("Stop"))
+                    .on_hover_text(STATUS_SUBMENU_BENCH_STOP)
+                    .clicked()
+                {
+                    bench.lock().unwrap().stop = true;
+                }
+                if let Some(elapsed) = elapsed {
+                    ui.spinner();
+                    ui.label(format!("{elapsed} seconds"));
+                }
+                ui.label(msg);
+            });
+            if runs.is_empty() {
+                return;
+            }
+            let best = runs.iter().map(|r| r.hashrate).fold(0.0, f32::max);
+            ui.separator();
+            ScrollArea::horizontal()
+                .id_salt("local_bench")
+                .show(ui, |ui| {
+                    Grid::new("local_bench_runs").striped(true).show(ui, |ui| {
+                        // one column per run, the newest first.
+                        let newest = || runs.iter().enumerate().rev();
+                        let fields: [(&str, &dyn Fn(&BenchRun) -> String); 6] = [
+                            ("Date", &|r| format_date(r.date)),
+                            ("Hashes", &|r| format!("{} {}", r.size, r.mode)),
+                            ("Threads", &|r| r.threads.to_string()),
+                            ("Time", &|r| format!("{:.1} s", r.seconds)),
+                            ("Hashrate", &|r| {
+                                format!("{} H/s", Float::from_0(r.hashrate.into()))
+                            }),
+                            ("Of the best run", &|r| {
+                                Percent::from(r.hashrate / best * 100.0).to_string()
+                            }),
+                        ];
+                        for (name, field) in fields {
+                            ui.label(RichText::new(name).color(BONE));
+                            for (_, run) in newest() {
+                                ui.label(field(run));
+                            }
+                            ui.end_row();
+                        }
+                        ui.label("");
+                        for (i, _) in newest() {
+                            if ui
+                                .add_enabled(!busy, egui::Button::new("Remove"))
+                                .on_hover_text(STATUS_SUBMENU_BENCH_REMOVE)
+                                .clicked()
+                            {
+                                remove = Some(i);
+                            }
+                        }
+                        ui.end_row();
+                    });
+                });
+        });
+        if let Some(i) = remove {
+            bench.lock().unwrap().remove(i);
+        }
+        runs
+    }
+
+    // A local run in the table of the other CPUs, ranked among them.
+    fn local_row(
+        mut row: egui_extras::TableRow,
+        run: &BenchRun,
+        top: f32,
+        benchmarks: &[Benchmark],
+        [cpu, bar, hashrate, rank_width, text]: [f32; 5],
+    ) {
+        let percent = if top > 0.0 {
+            run.hashrate / top * 100.0
+        } else {
+            0.0
+        };
+        let human = [Float::from_0(run.hashrate.into()).as_str(), " H/s"].concat();
+        row.col(|ui| {
+            ui.add_sized(
+                [cpu, text],
+                Label::new(
+                    RichText::new(format!(
+                        "Local {} {}, {} threads",
+                        run.size, run.mode, run.threads
+                    ))
+                    .color(GREEN),
+                ),
+            )
+            .on_hover_text(STATUS_SUBMENU_BENCH_LOCAL);
+        });
+        row.col(|ui| {
+            ui.add_sized([bar, text], ProgressBar::new(percent / 100.0))
+                .on_hover_text(Percent::from(percent).as_str());
+        });
+        // a single run, the same high, average and low.
+        for _ in 0..3 {
+            row.col(|ui| {
+                ui.add_sized([hashrate, text], Label::new(human.as_str()));
+            });
+        }
+        row.col(|ui| {
+            let rank = benchmarks.iter().filter(|b| b.high > run.hashrate).count() + 1;
+            ui.add_sized(
+                [rank_width, text],
+                Label::new(Unsigned::from(rank).as_str()),
+            );
+        });
+        row.col(|ui| {
+            ui.add_sized([hashrate, text], Label::new("local"));
+        });
+    }
+}
+
+// Local date and time of a run.
+fn format_date(date: u64) -> String {
+    match chrono::DateTime::from_timestamp(date as i64, 0) {
+        Some(date) => date
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        None => "?".to_string(),
+    }
 }
@@ -17,7 +17,12 @@
 
 use crate::{
     app::{Benchmark, eframe_impl::ProcessStatesGui},
-    disk::{gupax_p2pool_api::GupaxP2poolApi, state::Status, status::*},
+    components::{bench::Bench, payout_import::PayoutImport},
+    disk::{
+        gupax_p2pool_api::GupaxP2poolApi,
+        state::{P2pool, Status, Xmrig},
+        status::*,
+    },
     helper::{
         ProcessName, ProcessState, Sys,
         node::PubNodeApi,
@@ -29,11 +34,14 @@ use crate::{
         xvb::PubXvbApi,
     },
 };
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 mod benchmarks;
 mod p2pool;
+mod peers;
 mod processes;
+mod xmrig;
 
 impl Status {
     #[inline(always)] // called once
@@ -52,7 +60,14 @@ impl Status {
         states: &ProcessStatesGui,
         max_threads: u16,
         gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+        payout_import: &Arc<Mutex<PayoutImport>>,
+        state_p2pool: &mut P2pool,
+        node_rpc: String,
+        node_login: Option<String>,
         benchmarks: &[Benchmark],
+        bench: &Arc<Mutex<Bench>>,
+        state_xmrig: &Xmrig,
+        xmrig_path: &Path,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
     ) {
@@ -79,6 +94,21 @@ impl Status {
                 gupax_p2pool_api,
                 states.find(ProcessName::P2pool).state == ProcessState::Alive,
                 p2pool_api,
+                payout_import,
+                state_p2pool,
+                node_rpc,
+                node_login,
+                states.is_alive(ProcessName::Node),
+            );
+        //---------------------------------------------------------------------------------------------------- [XMRig]
+        } else if self.submenu == Submenu::Xmrig {
+            self.xmrig_detail(
+                ui,
+                sys,
+                states.is_alive(ProcessName::Xmrig),
+                xmrig_api,
+                states.is_alive(ProcessName::XmrigProxy),
+                xmrig_proxy_api,
             );
         //---------------------------------------------------------------------------------------------------- [Benchmarks]
         } else if self.submenu == Submenu::Benchmarks {
@@ -87,6 +117,10 @@ impl Status {
                 benchmarks,
                 states.is_alive(ProcessName::Xmrig),
                 xmrig_api,
+                bench,
+                state_xmrig,
+                xmrig_path,
+                max_threads,
             )
         }
     }
@@ -22,9 +22,10 @@ use readable::num::Unsigned;
 use strum::{EnumCount, IntoEnumIterator};
 
 use crate::{
+    components::payout_import::PayoutImport,
     disk::{
         gupax_p2pool_api::GupaxP2poolApi,
-        state::Status,
+        state::{P2pool, Status},
         status::{Hash, PayoutView},
     },
     helper::p2pool::PubP2poolApi,
@@ -38,6 +39,11 @@ impl Status {
         gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
         p2pool_alive: bool,
         p2pool_api: &Arc<Mutex<PubP2poolApi>>,
+        payout_import: &Arc<Mutex<PayoutImport>>,
+        state_p2pool: &mut P2pool,
+        node_rpc: String,
+        node_login: Option<String>,
+        node_alive: bool,
     ) {
         let api = gupax_p2pool_api.lock().unwrap();
         // let height = size.y;
@@ -123,6 +129,87 @@ impl Status {
             });
             // });
             drop(api);
+            // Import payouts not seen by Gupaxx by scanning the blocks of the local node
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                let mut import = payout_import.lock().unwrap();
+                if import.address.is_empty() {
+                    import.address = state_p2pool.address.clone();
+                }
+                let scanning = import.scanning;
+                ui.add_enabled_ui(!scanning, |ui| {
+                    ui.horizontal(|ui| {
+                        let width = (ui.available_width() / 2.0) - SPACE;
+                        ui.label("Address");
+                        ui.add_sized(
+                            [width, height],
+                            egui::TextEdit::singleline(&mut import.address),
+                        )
+                        .on_hover_text(STATUS_SUBMENU_IMPORT_ADDRESS);
+                        ui.label("Private view key");
+                        ui.add_sized(
+                            [ui.available_width(), height],
+                            egui::TextEdit::singleline(&mut import.view_key).password(true),
+                        )
+                        .on_hover_text(STATUS_SUBMENU_IMPORT_VIEW_KEY);
+                    });
+                });
+                ui.horizontal(|ui| {
+                    let width = (ui.available_width() / 8.0).max(size_text * 6.0);
+                    ui.add_enabled_ui(!scanning, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Start height");
+                            ui.add_sized(
+                                [width, height],
+                                egui::TextEdit::singleline(&mut import.height_start),
+                            )
+                            .on_hover_text(STATUS_SUBMENU_IMPORT_START);
+                            ui.label("End height");
+                            ui.add_sized(
+                                [width, height],
+                                egui::TextEdit::singleline(&mut import.height_end),
+                            )
+                            .on_hover_text(STATUS_SUBMENU_IMPORT_END);
+                        });
+                    });
+                    ui.separator();
+                    if scanning {
+                        // the scan stays until its thread exits, a new one can not start before.
+                        let stopping = import.stop;
+                        if ui
+                            .add_enabled_ui(!stopping, |ui| {
+                                ui.add_sized([width, height], egui::Button::new("Stop"))
+                                    .on_hover_text(STATUS_SUBMENU_IMPORT_STOP)
+                            })
+                            .inner
+                            .clicked()
+                        {
+                            import.stop = true;
+                        }
+                    } else {
+                        let button = ui
+                            .add_enabled_ui(node_alive, |ui| {
+                                ui.add_sized([width, height], egui::Button::new("Scan"))
+                                    .on_hover_text(STATUS_SUBMENU_IMPORT_SCAN)
+                                    .on_disabled_hover_text(STATUS_SUBMENU_IMPORT_NODE_OFFLINE)
+                            })
+                            .inner;
+                        if button.clicked() {
+                            drop(import);
+                            PayoutImport::spawn_thread(
+                                payout_import,
+                                gupax_p2pool_api,
+                                node_rpc,
+                                node_login,
+                            );
+                            return;
+                        }
+                    }
+                    ui.add(egui::ProgressBar::new(import.prog / 100.0));
+                });
+                let import = payout_import.lock().unwrap();
+                ui.label(RichText::new(&import.msg).color(LIGHT_GRAY));
+            });
             // Payout/Share Calculator
             // let button = (width / 20.0) - (SPACE * 1.666);
             ui.group(|ui| {
@@ -368,6 +455,9 @@ impl Status {
                 });
                 drop(api);
             });
+            // Peers of the sidechain
+            ui.add_space(height / 2.0);
+            self.p2pool_peers(ui, state_p2pool, p2pool_api, p2pool_alive);
         });
     }
 }
@@ -0,0 +1,224 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use egui::{Label, RichText, ScrollArea, SelectableLabel, TextStyle, TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::{
+    disk::state::{P2pool, Status},
+    helper::p2pool::PubP2poolApi,
+    utils::{constants::*, regex::Regexes},
+};
+
+impl Status {
+    // Connected peers of the P2Pool sidechain, with the pinned and not added peers kept in the state.
+    pub(super) fn p2pool_peers(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut P2pool,
+        p2pool_api: &Arc<Mutex<PubP2poolApi>>,
+        p2pool_alive: bool,
+    ) {
+        let text = ui.text_style_height(&TextStyle::Body);
+        let width = ui.available_width() / 20.0;
+        let (direction, address, uptime, ping, software, height, action) = (
+            width * 2.0,
+            width * 4.0,
+            width * 3.0,
+            width * 2.0,
+            width * 3.0,
+            width * 2.0,
+            width * 2.0,
+        );
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+            let api = p2pool_api.lock().unwrap();
+            ui.label(
+                RichText::new(format!("Peers: {}", api.peers.len()))
+                    .underline()
+                    .color(LIGHT_GRAY),
+            )
+            .on_hover_text(STATUS_SUBMENU_PEERS);
+            ui.add_enabled_ui(p2pool_alive, |ui| {
+                ScrollArea::horizontal().show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .columns(Column::auto(), 7)
+                        .max_scroll_height(text * 12.0)
+                        .header(text * 2.0, |mut header| {
+                            header.col(|ui| {
+                                ui.add_sized([direction, text], Label::new("Direction"));
+                            });
+                            header.col(|ui| {
+                                ui.add_sized([address, text], Label::new("Address"));
+                            });
+                            header.col(|ui| {
+                                ui.add_sized([uptime, text], Label::new("Uptime"));
+                            });
+                            header.col(|ui| {
+                                ui.add_sized([ping, text], Label::new("Ping"));
+                            });
+                            header.col(|ui| {
+                                ui.add_sized([software, text], Label::new("Software"));
+                            });
+                            header.col(|ui| {
+                                ui.add_sized([height, text], Label::new("Height"));
+                            });
+                            header.col(|ui| {
+                                ui.add_sized([action, text], Label::new("Pin/Don't add"));
+                            });
+                        })
+                        .body(|body| {
+                            body.rows(text, api.peers.len(), |mut row| {
+                                let peer = &api.peers[row.index()];
+                                let pinned = state.pinned_peers.contains(&peer.address);
+                                let banned = state.banned_peers.contains(&peer.address);
+                                let color = if pinned {
+                                    GREEN
+                                } else if banned {
+                                    RED
+                                } else {
+                                    LIGHT_GRAY
+                                };
+                                row.col(|ui| {
+                                    ui.add_sized(
+                                        [direction, text],
+                                        Label::new(if peer.incoming { "In" } else { "Out" }),
+                                    );
+                                });
+                                row.col(|ui| {
+                                    ui.add_sized(
+                                        [address, text],
+                                        Label::new(RichText::new(&peer.address).color(color)),
+                                    );
+                                });
+                                row.col(|ui| {
+                                    ui.add_sized(
+                                        [uptime, text],
+                                        Label::new(peer.uptime.display(false)),
+                                    );
+                                });
+                                row.col(|ui| {
+                                    ui.add_sized(
+                                        [ping, text],
+                                        Label::new(format!("{} ms", peer.ping)),
+                                    );
+                                });
+                                row.col(|ui| {
+                                    ui.add_sized([software, text], Label::new(&peer.software));
+                                });
+                                row.col(|ui| {
+                                    ui.add_sized(
+                                        [height, text],
+                                        Label::new(peer.height.to_string()),
+                                    );
+                                });
+                                row.col(|ui| {
+                                    ui.horizontal(|ui| {
+                                        // the address of an incoming peer has the port of its connection, not of its P2Pool.
+                                        if ui
+                                            .add_enabled(
+                                                !peer.incoming || pinned,
+                                                SelectableLabel::new(pinned, "Pin"),
+                                            )
+                                            .on_hover_text(STATUS_SUBMENU_PEER_PIN)
+                                            .on_disabled_hover_text(
+                                                STATUS_SUBMENU_PEER_PIN_INCOMING,
+                                            )
+                                            .clicked()
+                                        {
+                                            state.toggle_pinned_peer(&peer.address);
+                                        }
+                                        if ui
+                                            .add(SelectableLabel::new(banned, "Don't add"))
+                                            .on_hover_text(STATUS_SUBMENU_PEER_DONT_ADD)
+                                            .clicked()
+                                        {
+                                            state.toggle_banned_peer(&peer.address);
+                                        }
+                                    });
+                                });
+                            });
+                        });
+                });
+            });
+            drop(api);
+            ui.separator();
+            // Add a peer that is not connected.
+            let id = ui.make_persistent_id("p2pool_peer_input");
+            let mut input = ui.data_mut(|d| d.get_temp::<String>(id).unwrap_or_default());
+            ui.horizontal(|ui| {
+                ui.label("IP:PORT");
+                ui.add_sized([address, text], egui::TextEdit::singleline(&mut input))
+                    .on_hover_text(STATUS_SUBMENU_PEER_INPUT);
+                let valid = Regexes::host_port_ok(input.trim());
+                ui.add_enabled_ui(valid, |ui| {
+                    if ui
+                        .button("Pin")
+                        .on_hover_text(STATUS_SUBMENU_PEER_PIN)
+                        .clicked()
+                    {
+                        if !state.pinned_peers.iter().any(|p| p == input.trim()) {
+                            state.toggle_pinned_peer(input.trim());
+                        }
+                        input.clear();
+                    }
+                    if ui
+                        .button("Don't add")
+                        .on_hover_text(STATUS_SUBMENU_PEER_DONT_ADD)
+                        .clicked()
+                    {
+                        if !state.banned_peers.iter().any(|p| p == input.trim()) {
+                            state.toggle_banned_peer(input.trim());
+                        }
+                        input.clear();
+                    }
+                });
+            });
+            ui.data_mut(|d| d.insert_temp(id, input));
+            // Click on a pinned/not added peer to remove it.
+            ui.horizontal_wrapped(|ui| {
+                ui.label(RichText::new("Pinned:").color(GREEN))
+                    .on_hover_text(STATUS_SUBMENU_PEERS_PINNED);
+                for peer in state.pinned_peers.clone() {
+                    if ui
+                        .small_button(&peer)
+                        .on_hover_text(STATUS_SUBMENU_PEER_REMOVE)
+                        .clicked()
+                    {
+                        state.toggle_pinned_peer(&peer);
+                    }
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.label(RichText::new("Not added:").color(RED))
+                    .on_hover_text(STATUS_SUBMENU_PEERS_NOT_ADDED);
+                for peer in state.banned_peers.clone() {
+                    if ui
+                        .small_button(&peer)
+                        .on_hover_text(STATUS_SUBMENU_PEER_REMOVE)
+                        .clicked()
+                    {
+                        state.toggle_banned_peer(&peer);
+                    }
+                }
+            });
+        });
+    }
+}
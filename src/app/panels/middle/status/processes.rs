@@ -4,12 +4,14 @@ use std::sync::{Arc, Mutex};
 use crate::app::eframe_impl::ProcessStatesGui;
 use crate::disk::state::Status;
 use crate::helper::node::PubNodeApi;
-use crate::helper::p2pool::{ImgP2pool, PubP2poolApi};
+use crate::helper::p2pool::{ImgP2pool, MergeMiningStatus, PubP2poolApi};
+use crate::helper::xrig::idle::IdleAction;
 use crate::helper::xrig::xmrig::{ImgXmrig, PubXmrigApi};
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
 use crate::helper::xvb::nodes::Pool;
 use crate::helper::xvb::{PubXvbApi, rounds::XvbRound};
 use crate::helper::{ProcessName, Sys};
+use crate::human::HumanTime;
 
 use crate::constants::*;
 use egui::{RichText, TextStyle};
@@ -241,6 +243,15 @@ fn p2pool(
             .on_hover_text(STATUS_P2POOL_ADDRESS);
         ui.label(&img.address);
         drop(img);
+        ui.label(RichText::new("Merge Mining").underline().color(BONE))
+            .on_hover_text(STATUS_P2POOL_MERGE_MINING);
+        let color = match api.merge_mining {
+            MergeMiningStatus::Disabled => LIGHT_GRAY,
+            MergeMiningStatus::Connecting => YELLOW,
+            MergeMiningStatus::Connected => GREEN,
+            MergeMiningStatus::Error => RED,
+        };
+        ui.label(RichText::new(api.merge_mining.to_string()).color(color));
         drop(api);
     });
 }
@@ -339,6 +350,12 @@ fn xmrig(
         ui.label(RichText::new("Pool").underline().color(BONE))
             .on_hover_text(STATUS_XMRIG_POOL);
         ui.label(api.pool.as_ref().unwrap_or(&Pool::Unknown).to_string());
+        // place in the failover chain
+        if let Some(i) = api.pool_entry {
+            if let Some(pool) = api.pools.get(i) {
+                ui.label(format!("[{}: {}/{}]", pool.name, i + 1, api.pools.len()));
+            }
+        }
         ui.label(RichText::new("Threads").underline().color(BONE))
             .on_hover_text(STATUS_XMRIG_THREADS);
         ui.label(format!(
@@ -346,6 +363,14 @@ fn xmrig(
             &xmrig_img.lock().unwrap().threads,
             max_threads
         ));
+        ui.label(RichText::new("Idle policy").underline().color(BONE))
+            .on_hover_text(STATUS_XMRIG_IDLE);
+        let color = if api.idle.action == IdleAction::Mine {
+            LIGHT_GRAY
+        } else {
+            YELLOW
+        };
+        ui.add(Label::new(RichText::new(api.idle.to_string()).color(color)).wrap());
         drop(api);
     });
 }
@@ -458,7 +483,10 @@ fn node(ui: &mut Ui, node_alive: bool, node_api: &Arc<Mutex<PubNodeApi>>) {
         ui.label(api.blockheight.to_string());
         ui.label(RichText::new("Network Difficulty").underline().color(BONE))
             .on_hover_text(STATUS_NODE_DIFFICULTY);
-        ui.label(api.difficulty.to_string());
+        ui.label(api.difficulty.to_string()).on_hover_text(format!(
+            "Cumulative difficulty: {}",
+            api.cumulative_difficulty
+        ));
         ui.label(RichText::new("Database size").underline().color(BONE))
             .on_hover_text(STATUS_NODE_DB_SIZE);
         ui.label(api.database_size.to_owned());
@@ -470,16 +498,60 @@ fn node(ui: &mut Ui, node_alive: bool, node_api: &Arc<Mutex<PubNodeApi>>) {
         ui.label(api.nettype.to_string());
         ui.label(RichText::new("Outgoing peers").underline().color(BONE))
             .on_hover_text(STATUS_NODE_OUT);
-        ui.label(api.outgoing_connections.to_string());
+        ui.label(api.outgoing_connections.to_string())
+            .on_hover_text(node_connections(&api, false));
         ui.label(RichText::new("Incoming peers").underline().color(BONE))
             .on_hover_text(STATUS_NODE_IN);
-        ui.label(api.incoming_connections.to_string());
+        ui.label(api.incoming_connections.to_string())
+            .on_hover_text(node_connections(&api, true));
+        ui.label(RichText::new("Transaction pool").underline().color(BONE))
+            .on_hover_text(STATUS_NODE_TX_POOL);
+        ui.label(api.tx_pool_size.to_string());
         ui.label(RichText::new("Synchronized").underline().color(BONE))
             .on_hover_text(STATUS_NODE_SYNC);
         ui.label(api.synchronized.to_string());
+        ui.label(RichText::new("Sync progress").underline().color(BONE))
+            .on_hover_text(STATUS_NODE_SYNC_PROGRESS);
+        ui.label(format!(
+            "{:.2}% ({}/{})",
+            api.sync_percent(),
+            api.height,
+            api.target_height()
+        ));
+        ui.label(RichText::new("Sync speed").underline().color(BONE))
+            .on_hover_text(STATUS_NODE_SYNC_SPEED);
+        ui.label(format!("{:.0} blocks/min", api.blocks_per_minute()));
+        ui.label(RichText::new("Time left").underline().color(BONE))
+            .on_hover_text(STATUS_NODE_SYNC_ETA);
+        ui.label(api.sync_eta().map_or("???".to_string(), |eta| {
+            HumanTime::into_human(eta).display(false)
+        }));
         ui.label(RichText::new("Status").underline().color(BONE))
             .on_hover_text(STATUS_NODE_STATUS);
         ui.label(api.status.to_string());
+        ui.label(RichText::new("Bootstrap daemon").underline().color(BONE))
+            .on_hover_text(STATUS_NODE_BOOTSTRAP);
+        ui.label(api.bootstrap_status());
         drop(api);
     });
 }
+
+// details of the peers in one direction, from get_connections.
+fn node_connections(api: &PubNodeApi, incoming: bool) -> String {
+    let mut text = String::new();
+    for c in api.connections.iter().filter(|c| c.incoming == incoming) {
+        text.push_str(&format!(
+            "{} | height {} | {} | {} | {}/{} kB/s\n",
+            c.address,
+            c.height,
+            c.state,
+            HumanTime::from_u64(c.live_time).display(false),
+            c.current_download,
+            c.current_upload
+        ));
+    }
+    if text.is_empty() {
+        text.push_str("No details on the peers");
+    }
+    text
+}
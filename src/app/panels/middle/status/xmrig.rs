@@ -0,0 +1,479 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use egui::{
+    Grid, Label, RichText, ScrollArea, Sense, Stroke, TextStyle, TextWrapMode, Ui, pos2, vec2,
+};
+use egui_extras::{Column, TableBuilder};
+use log::debug;
+use readable::num::Float;
+use strum::IntoEnumIterator;
+
+use crate::{
+    disk::state::Status,
+    helper::{
+        Sys,
+        xrig::{
+            idle::IdleAction,
+            pool_errors::{POOL_ERRORS_BROKEN, PoolErrorKind, PoolErrors},
+            proxy_workers::ProxyWorkers,
+            thermal::{THERMAL_HISTORY, ThermalGuard},
+            xmrig::PubXmrigApi,
+            xmrig_proxy::PubXmrigProxyApi,
+        },
+    },
+    utils::{constants::*, human::HumanTime},
+};
+
+impl Status {
+    // Per-thread hashrate of XMRig and the state of the hardware, with warnings about what lowers the hashrate.
+    pub(super) fn xmrig_detail(
+        &self,
+        ui: &mut Ui,
+        sys: &Arc<Mutex<Sys>>,
+        xmrig_alive: bool,
+        xmrig_api: &Arc<Mutex<PubXmrigApi>>,
+        xmrig_proxy_alive: bool,
+        xmrig_proxy_api: &Arc<Mutex<PubXmrigProxyApi>>,
+    ) {
+        debug!("Status Tab | Rendering [XMRig]");
+        let detail = xmrig_api.lock().unwrap().detail.clone();
+        let text = ui.text_style_height(&TextStyle::Body);
+        ui.style_mut().wrap_mode = Some(TextWrapMode::Wrap);
+        if !xmrig_alive {
+            ui.vertical_centered(|ui| {
+                ui.label("XMRig is offline");
+            });
+        }
+        ui.add_enabled_ui(xmrig_alive, |ui| {
+            for warning in detail.warnings() {
+                ui.group(|ui| {
+                    ui.set_width(ui.available_width());
+                    ui.label(RichText::new(warning).color(RED));
+                });
+            }
+            ui.horizontal(|ui| {
+                let width = ui.available_width() / 2.0 - ui.spacing().item_spacing.x * 2.0;
+                ui.group(|ui| {
+                    ui.set_width(width);
+                    ui.vertical(|ui| {
+                        ui.label(RichText::new("Hardware").underline().color(BONE));
+                        Grid::new("xmrig_hardware").num_columns(2).show(ui, |ui| {
+                            ui.label("CPU").on_hover_text(STATUS_XMRIG_DETAIL_CPU);
+                            ui.label(format!("{} ({})", detail.cpu_brand, detail.assembly));
+                            ui.end_row();
+                            ui.label("Instructions")
+                                .on_hover_text(STATUS_XMRIG_DETAIL_CPU);
+                            ui.label(detail.cpu_flags.join(" "));
+                            ui.end_row();
+                            ui.label("Hugepages")
+                                .on_hover_text(STATUS_XMRIG_DETAIL_HUGEPAGES);
+                            let (hugepages, color) = match detail.hugepages {
+                                Some(h) if h.available() => (h.to_string(), GREEN),
+                                Some(h) => (h.to_string(), RED),
+                                None => (UNKNOWN_DATA.to_string(), LIGHT_GRAY),
+                            };
+                            ui.label(RichText::new(hugepages).color(color));
+                            ui.end_row();
+                            ui.label("MSR mod").on_hover_text(STATUS_XMRIG_DETAIL_MSR);
+                            let (msr, color) = match detail.msr {
+                                Some(true) => (format!("Applied ({})", detail.msr_preset), GREEN),
+                                Some(false) if detail.msr_preset == "none" => {
+                                    ("No preset for this CPU".to_string(), LIGHT_GRAY)
+                                }
+                                Some(false) => ("Not applied".to_string(), RED),
+                                None => (UNKNOWN_DATA.to_string(), LIGHT_GRAY),
+                            };
+                            ui.label(RichText::new(msr).color(color));
+                            ui.end_row();
+                            ui.label("Memory").on_hover_text(STATUS_XMRIG_DETAIL_MEMORY);
+                            ui.label(format!(
+                                "[XMRig: {}]\n[Threads: {}]\n[Free: {} / {}]",
+                                megabytes(detail.memory_rss),
+                                megabytes(detail.memory_backend),
+                                megabytes(detail.memory_free),
+                                megabytes(detail.memory_total),
+                            ));
+                            ui.end_row();
+                            ui.label("Features")
+                                .on_hover_text(STATUS_XMRIG_DETAIL_FEATURES);
+                            ui.label(format!(
+                                "XMRig {} [{}]",
+                                detail.version,
+                                detail.features.join(", ")
+                            ));
+                            ui.end_row();
+                        });
+                    });
+                });
+                ui.group(|ui| {
+                    ui.set_width(width);
+                    ui.vertical(|ui| {
+                        ui.label(RichText::new("Pool").underline().color(BONE));
+                        Grid::new("xmrig_pool").num_columns(2).show(ui, |ui| {
+                            ui.label("Pool").on_hover_text(STATUS_XMRIG_POOL);
+                            ui.label(format!("{} ({})", detail.pool, detail.algo));
+                            ui.end_row();
+                            ui.label("TLS");
+                            ui.label(detail.tls.as_deref().unwrap_or("Disabled"));
+                            ui.end_row();
+                            ui.label("Latency")
+                                .on_hover_text(STATUS_XMRIG_DETAIL_LATENCY);
+                            ui.label(format!("{} ms", detail.ping_ms));
+                            ui.end_row();
+                            ui.label("Connected");
+                            ui.label(
+                                HumanTime::into_human(Duration::from_secs(detail.pool_uptime))
+                                    .display(false),
+                            );
+                            ui.end_row();
+                            ui.label("Failures");
+                            ui.label(detail.failures.to_string());
+                            ui.end_row();
+                            ui.label("Share time")
+                                .on_hover_text(STATUS_XMRIG_DETAIL_SHARES);
+                            ui.label(format!(
+                                "{:.1} seconds",
+                                detail.share_time_ms as f32 / 1000.0
+                            ));
+                            ui.end_row();
+                            ui.label("Shares").on_hover_text(STATUS_XMRIG_DETAIL_SHARES);
+                            ui.label(format!("{}/{}", detail.shares_good, detail.shares_total));
+                            ui.end_row();
+                            ui.label("Best difficulty")
+                                .on_hover_text(STATUS_XMRIG_DETAIL_BEST);
+                            ui.label(detail.best_diff.to_string());
+                            ui.end_row();
+                        });
+                    });
+                });
+            });
+            // Per-thread hashrate
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                ui.label(
+                    RichText::new(format!("Threads: {}", detail.threads.len()))
+                        .underline()
+                        .color(LIGHT_GRAY),
+                )
+                .on_hover_text(STATUS_XMRIG_DETAIL_THREADS);
+                let width = ui.available_width() / 5.0;
+                ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                ScrollArea::horizontal().show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .columns(Column::auto(), 5)
+                        .max_scroll_height(text * 12.0)
+                        .header(text * 2.0, |mut header| {
+                            for name in ["Thread", "CPU", "10s", "1m", "15m"] {
+                                header.col(|ui| {
+                                    ui.add_sized([width, text], Label::new(name));
+                                });
+                            }
+                        })
+                        .body(|body| {
+                            body.rows(text, detail.threads.len(), |mut row| {
+                                let thread = &detail.threads[row.index()];
+                                let index = row.index();
+                                row.col(|ui| {
+                                    ui.add_sized([width, text], Label::new(index.to_string()));
+                                });
+                                row.col(|ui| {
+                                    let cpu = thread.cpu.map_or("-".to_string(), |c| c.to_string());
+                                    ui.add_sized([width, text], Label::new(cpu));
+                                });
+                                for hashrate in thread.hashrate {
+                                    row.col(|ui| {
+                                        let hashrate = hashrate
+                                            .map_or(UNKNOWN_DATA.to_string(), |h| {
+                                                [Float::from_0(h.into()).as_str(), " H/s"].concat()
+                                            });
+                                        ui.add_sized([width, text], Label::new(hashrate));
+                                    });
+                                }
+                            });
+                        });
+                });
+            });
+        });
+        // the sensors are read even when XMRig is offline.
+        let (temperature, history) = {
+            let sys = sys.lock().unwrap();
+            (sys.cpu_temperature, sys.temperature_history.clone())
+        };
+        temperature_graph(
+            ui,
+            temperature,
+            &history,
+            &xmrig_api.lock().unwrap().thermal,
+        );
+        // the errors are kept after the process stops, to see why it stopped.
+        pool_diagnostics(ui, "XMRig", &xmrig_api.lock().unwrap().pool_errors);
+        let (proxy_errors, proxy_workers) = {
+            let api = xmrig_proxy_api.lock().unwrap();
+            (api.pool_errors.clone(), api.workers.clone())
+        };
+        if xmrig_proxy_alive || !proxy_errors.last.is_empty() {
+            pool_diagnostics(ui, "XMRig-Proxy", &proxy_errors);
+        }
+        if xmrig_proxy_alive || !proxy_workers.workers.is_empty() {
+            proxy_workers_table(ui, &proxy_workers);
+        }
+    }
+}
+
+// Counters of errors per pool and the last errors with a suggested fix.
+fn pool_diagnostics(ui: &mut Ui, name: &str, errors: &PoolErrors) {
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        ui.label(
+            RichText::new(format!("Pool errors of {name}"))
+                .underline()
+                .color(LIGHT_GRAY),
+        )
+        .on_hover_text(STATUS_POOL_ERRORS);
+        if errors.pools.is_empty() {
+            ui.label("No errors");
+            return;
+        }
+        Grid::new(format!("pool_errors_{name}"))
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new("Pool").color(BONE));
+                for kind in PoolErrorKind::iter() {
+                    ui.label(RichText::new(kind.to_string()).color(BONE))
+                        .on_hover_text(kind.suggestion());
+                }
+                ui.end_row();
+                for (pool, counters) in &errors.pools {
+                    let broken = counters.blocking >= POOL_ERRORS_BROKEN;
+                    ui.label(RichText::new(pool).color(if broken { RED } else { LIGHT_GRAY }));
+                    for kind in PoolErrorKind::iter() {
+                        ui.label(counters.count(kind).to_string());
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.separator();
+        ui.label(RichText::new("Last errors").color(BONE))
+            .on_hover_text(STATUS_POOL_ERRORS_LAST);
+        let text = ui.text_style_height(&TextStyle::Body);
+        ScrollArea::vertical()
+            .id_salt(format!("pool_errors_last_{name}"))
+            .max_height(text * 12.0)
+            .show(ui, |ui| {
+                for error in errors.last.iter().rev() {
+                    ui.label(
+                        RichText::new(format!("[{}] {} | {}", error.time, error.pool, error.kind))
+                            .color(if error.kind.is_blocking() {
+                                RED
+                            } else {
+                                YELLOW
+                            }),
+                    )
+                    .on_hover_text(&error.line);
+                    ui.label(RichText::new(error.kind.suggestion()).color(LIGHT_GRAY));
+                }
+            });
+    });
+}
+
+// Temperature of the CPU over time with the ceiling of the thermal guard, and its events.
+fn temperature_graph(
+    ui: &mut Ui,
+    temperature: Option<f32>,
+    history: &VecDeque<f32>,
+    thermal: &ThermalGuard,
+) {
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        let title = match temperature {
+            Some(t) => format!("CPU temperature: {t:.0}°C"),
+            None => "CPU temperature: no sensor found".to_string(),
+        };
+        ui.label(RichText::new(title).underline().color(LIGHT_GRAY))
+            .on_hover_text(STATUS_TEMPERATURE);
+        if history.is_empty() {
+            return;
+        }
+        let ceiling = thermal.enabled.then_some(thermal.ceiling as f32);
+        // scale from 20°C under the coldest value to 5°C over the hottest or the ceiling.
+        let max = history
+            .iter()
+            .copied()
+            .chain(ceiling)
+            .fold(f32::MIN, f32::max)
+            + 5.0;
+        let min = (history.iter().copied().fold(f32::MAX, f32::min) - 20.0).max(0.0);
+        let height = ui.text_style_height(&TextStyle::Body) * 8.0;
+        let (response, painter) =
+            ui.allocate_painter(vec2(ui.available_width(), height), Sense::hover());
+        let rect = response.rect;
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, GRAY), egui::StrokeKind::Inside);
+        let y = |t: f32| rect.bottom() - (t - min) / (max - min) * rect.height();
+        let step = rect.width() / (THERMAL_HISTORY - 1) as f32;
+        // newest on the right
+        let offset = THERMAL_HISTORY - history.len();
+        let points = history
+            .iter()
+            .enumerate()
+            .map(|(i, t)| pos2(rect.left() + (offset + i) as f32 * step, y(*t)))
+            .collect::<Vec<_>>();
+        painter.add(egui::Shape::line(points, Stroke::new(1.5, GREEN)));
+        if let Some(ceiling) = ceiling {
+            painter.hline(rect.x_range(), y(ceiling), Stroke::new(1.0, RED));
+        }
+        response.on_hover_text(format!(
+            "Last 10 minutes, between {min:.0}°C and {max:.0}°C"
+        ));
+        if thermal.events.is_empty() {
+            return;
+        }
+        ui.separator();
+        ui.label(RichText::new("Thermal guard").color(BONE))
+            .on_hover_text(STATUS_TEMPERATURE_EVENTS);
+        let text = ui.text_style_height(&TextStyle::Body);
+        ScrollArea::vertical()
+            .id_salt("thermal_events")
+            .max_height(text * 8.0)
+            .show(ui, |ui| {
+                for event in thermal.events.iter().rev() {
+                    let color = match event.action {
+                        IdleAction::Mine => GREEN,
+                        IdleAction::Throttle(_) => YELLOW,
+                        IdleAction::Pause => RED,
+                    };
+                    ui.label(RichText::new(event.to_string()).color(color));
+                }
+            });
+    });
+}
+
+// Workers of XMRig-Proxy, the silent ones in red.
+fn proxy_workers_table(ui: &mut Ui, workers: &ProxyWorkers) {
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        ui.label(
+            RichText::new(format!(
+                "Workers of XMRig-Proxy: {} (by {})",
+                workers.workers.len(),
+                workers.mode
+            ))
+            .underline()
+            .color(LIGHT_GRAY),
+        )
+        .on_hover_text(STATUS_PROXY_WORKERS);
+        let silent = workers.silent();
+        if silent != 0 {
+            ui.label(
+                RichText::new(format!("{silent} worker(s) did not send a share recently"))
+                    .color(RED),
+            );
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let text = ui.text_style_height(&TextStyle::Body);
+        let width = ui.available_width() / 10.0;
+        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+        ScrollArea::horizontal()
+            .id_salt("proxy_workers")
+            .show(ui, |ui| {
+                TableBuilder::new(ui)
+                    .columns(Column::auto(), 10)
+                    .max_scroll_height(text * 12.0)
+                    .header(text * 2.0, |mut header| {
+                        for name in [
+                            "Worker",
+                            "IP",
+                            "1m",
+                            "10m",
+                            "1h",
+                            "12h",
+                            "24h",
+                            "Accepted",
+                            "Rejected",
+                            "Last share",
+                        ] {
+                            header.col(|ui| {
+                                ui.add_sized([width, text], Label::new(name));
+                            });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(text, workers.workers.len(), |mut row| {
+                            let worker = &workers.workers[row.index()];
+                            let color = if worker.silent {
+                                RED
+                            } else if worker.connections == 0 {
+                                LIGHT_GRAY
+                            } else {
+                                GREEN
+                            };
+                            row.col(|ui| {
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(RichText::new(&worker.name).color(color)),
+                                )
+                                .on_hover_text(format!(
+                                    "Connections: {}\nAgent: {}\nDifficulty: {}\nInvalid shares: {}",
+                                    worker.connections, worker.agent, worker.diff, worker.invalid
+                                ));
+                            });
+                            row.col(|ui| {
+                                ui.add_sized([width, text], Label::new(&worker.ip));
+                            });
+                            for hashrate in worker.hashrate {
+                                row.col(|ui| {
+                                    let hashrate =
+                                        [Float::from_0(hashrate.into()).as_str(), " H/s"].concat();
+                                    ui.add_sized([width, text], Label::new(hashrate));
+                                });
+                            }
+                            row.col(|ui| {
+                                ui.add_sized([width, text], Label::new(worker.accepted.to_string()));
+                            });
+                            row.col(|ui| {
+                                ui.add_sized([width, text], Label::new(worker.rejected.to_string()));
+                            });
+                            row.col(|ui| {
+                                let last_share = if worker.last_share == 0 {
+                                    "Never".to_string()
+                                } else {
+                                    let ago = Duration::from_secs(
+                                        now.saturating_sub(worker.last_share),
+                                    );
+                                    [&HumanTime::into_human(ago).display(false), " ago"].concat()
+                                };
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(RichText::new(last_share).color(color)),
+                                );
+                            });
+                        });
+                    });
+            });
+    });
+}
+
+fn megabytes(bytes: u64) -> String {
+    format!("{} megabytes", bytes / 1_000_000)
+}
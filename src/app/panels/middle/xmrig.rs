@@ -22,14 +22,24 @@ use crate::app::panels::middle::common::state_edit_field::{
     monero_address_field, slider_state_field,
 };
 use crate::constants::*;
-use crate::disk::state::{StartOptionsMode, Xmrig};
+#[cfg(target_family = "unix")]
+use crate::disk::state::XmrigPrivilege;
+use crate::disk::state::{FailoverPool, StartOptionsMode, Xmrig};
+use crate::helper::xrig::config::XmrigTuning;
 use crate::helper::xrig::xmrig::PubXmrigApi;
-use crate::helper::{Process, ProcessName};
+use crate::helper::{Process, ProcessName, Sys};
 use crate::miscs::height_txt_before_button;
 use crate::regex::REGEXES;
-use egui::{Checkbox, Image, Ui, vec2};
+use crate::utils::errors::ErrorState;
+#[cfg(target_os = "linux")]
+use crate::utils::sudo::PrivilegeSetup;
+use crate::utils::sudo::SudoState;
+#[cfg(target_family = "unix")]
+use crate::utils::sudo::cpu_has_1gb_pages;
+use egui::{Button, Checkbox, ComboBox, Grid, Image, SelectableLabel, TextEdit, Ui, vec2};
 use log::*;
 
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use super::common::list_poolnode::PoolNode;
@@ -38,6 +48,7 @@ use super::common::state_edit_field::StateTextEdit;
 impl Xmrig {
     #[inline(always)] // called once
     #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(target_os = "windows", allow(unused_variables))]
     pub fn show(
         &mut self,
         pool_vec: &mut Vec<(String, PoolNode)>,
@@ -47,6 +58,10 @@ impl Xmrig {
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
         p2pool_stratum_port: u16,
+        sudo: &Arc<Mutex<SudoState>>,
+        error_state: &mut ErrorState,
+        xmrig_path: &Path,
+        sys: &Arc<Mutex<Sys>>,
     ) {
         let logo = Some(Image::from_bytes("bytes:/xmrig.png", BYTES_XMRIG));
         header_tab(
@@ -97,13 +112,16 @@ impl Xmrig {
             debug!("XMRig Tab | Rendering [Threads]");
             ui.vertical_centered(|ui| {
                 ui.set_max_width(ui.available_width() * 0.75);
-                slider_state_field(
-                    ui,
-                    &format!("Threads [1-{}]:", self.max_threads),
-                    XMRIG_THREADS,
-                    &mut self.current_threads,
-                    1..=self.max_threads,
-                );
+                // the threads are given by the pinned CPUs.
+                ui.add_enabled_ui(self.pinned().is_empty(), |ui| {
+                    slider_state_field(
+                        ui,
+                        &format!("Threads [1-{}]:", self.max_threads),
+                        XMRIG_THREADS,
+                        &mut self.current_threads,
+                        1..=self.max_threads,
+                    );
+                });
                 #[cfg(not(target_os = "linux"))] // Pause on active isn't supported on Linux
                 slider_state_field(
                     ui,
@@ -112,7 +130,44 @@ impl Xmrig {
                     &mut self.pause,
                     0..=255,
                 );
+                if !self.simple {
+                    slider_state_field(
+                        ui,
+                        "CPU priority [0-5]:",
+                        XMRIG_PRIORITY,
+                        &mut self.priority,
+                        0..=5,
+                    );
+                    ui.checkbox(&mut self.huge_pages, "Hugepages")
+                        .on_hover_text(XMRIG_HUGE_PAGES);
+                }
+                self.tuning(ui, process, api);
+            });
+            debug!("XMRig Tab | Rendering [Idle policy]");
+            ui.add_space(SPACE);
+            ui.group(|ui| {
+                self.idle_policy(ui);
+            });
+            debug!("XMRig Tab | Rendering [Thermal guard]");
+            ui.add_space(SPACE);
+            ui.group(|ui| {
+                self.thermal_guard(ui);
             });
+            if !self.simple {
+                debug!("XMRig Tab | Rendering [CPU topology]");
+                ui.add_space(SPACE);
+                ui.group(|ui| {
+                    self.topology(ui, sys);
+                });
+            }
+            #[cfg(target_family = "unix")]
+            {
+                debug!("XMRig Tab | Rendering [Privileges]");
+                ui.add_space(SPACE);
+                ui.group(|ui| {
+                    self.privileges(ui, sudo, error_state, xmrig_path);
+                });
+            }
             if !self.simple {
                 if !self.arguments.is_empty() {
                     ui.disable();
@@ -169,7 +224,7 @@ impl Xmrig {
                                 ui.vertical(|ui| {
                                     // TLS/Keepalive
                                     ui.horizontal(|ui| {
-                                        let width = (ui.available_width() / 2.0) - 11.0;
+                                        let width = (ui.available_width() / 3.0) - 14.0;
                                         let height =
                                             height_txt_before_button(ui, &egui::TextStyle::Button)
                                                 * 2.0;
@@ -185,14 +240,336 @@ impl Xmrig {
                                             Checkbox::new(&mut self.keepalive, "Keepalive"),
                                         )
                                         .on_hover_text(XMRIG_KEEPALIVE);
+                                        ui.separator();
+                                        ui.add_sized(
+                                            size,
+                                            Checkbox::new(&mut self.nicehash, "Nicehash"),
+                                        )
+                                        .on_hover_text(XMRIG_NICEHASH);
                                     });
                                 });
                             });
                         });
+                        ui.add_space(5.0);
+                        debug!("XMRig Tab | Rendering [Failover]");
+                        ui.group(|ui| {
+                            self.failover(ui, pool_vec);
+                        });
                     });
             }
         });
     }
+    // Pools of the list XMRig falls back to, in order, each with its own connection flags.
+    fn failover(&mut self, ui: &mut Ui, pool_vec: &[(String, PoolNode)]) {
+        self.sync_failover(pool_vec);
+        ui.horizontal(|ui| {
+            ui.label(format!("Failover pools: {}", self.failover.len()))
+                .on_hover_text(XMRIG_FAILOVER);
+            ui.separator();
+            ComboBox::from_id_salt("xmrig_failover_add")
+                .selected_text("Add a pool")
+                .show_ui(ui, |ui| {
+                    for (name, pool) in pool_vec {
+                        if ui
+                            .add_enabled(
+                                self.failover_accepts(name, pool),
+                                SelectableLabel::new(false, name),
+                            )
+                            .on_disabled_hover_text(XMRIG_FAILOVER_TAKEN)
+                            .clicked()
+                        {
+                            self.failover.push(FailoverPool {
+                                name: name.clone(),
+                                ip: pool.ip().to_string(),
+                                port: pool.port().to_string(),
+                                rig: pool.custom().to_string(),
+                                tls: false,
+                                keepalive: false,
+                                nicehash: false,
+                            });
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(XMRIG_FAILOVER_ADD);
+        });
+        let mut up = None;
+        let mut remove = None;
+        Grid::new("xmrig_failover").num_columns(6).show(ui, |ui| {
+            ui.label(format!("1. {} ({}:{})", self.name, self.ip, self.port));
+            ui.label("Main pool, TLS, Keepalive and Nicehash above");
+            ui.end_row();
+            for (i, pool) in self.failover.iter_mut().enumerate() {
+                ui.label(format!(
+                    "{}. {} ({}:{})",
+                    i + 2,
+                    pool.name,
+                    pool.ip,
+                    pool.port
+                ));
+                ui.checkbox(&mut pool.tls, "TLS").on_hover_text(XMRIG_TLS);
+                ui.checkbox(&mut pool.keepalive, "Keepalive")
+                    .on_hover_text(XMRIG_KEEPALIVE);
+                ui.checkbox(&mut pool.nicehash, "Nicehash")
+                    .on_hover_text(XMRIG_NICEHASH);
+                if ui
+                    .add_enabled(i != 0, Button::new("⬆"))
+                    .on_hover_text(XMRIG_FAILOVER_UP)
+                    .clicked()
+                {
+                    up = Some(i);
+                }
+                if ui
+                    .button("✖")
+                    .on_hover_text(XMRIG_FAILOVER_REMOVE)
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = up {
+            self.failover.swap(i - 1, i);
+        }
+        if let Some(i) = remove {
+            self.failover.remove(i);
+        }
+    }
+    // Apply the settings above to the running XMRig, only if they changed.
+    fn tuning(&self, ui: &mut Ui, process: &Arc<Mutex<Process>>, api: &Arc<Mutex<PubXmrigApi>>) {
+        let alive = process.lock().unwrap().is_alive();
+        let mut api = api.lock().unwrap();
+        let tuning = XmrigTuning::from_state(self);
+        let changed = api.tuned.as_ref().is_some_and(|tuned| *tuned != tuning);
+        let pending = api.tuning.is_some();
+        ui.add_enabled_ui(alive && changed && !pending, |ui| {
+            if ui
+                .button("Apply without restart")
+                .on_hover_text(XMRIG_TUNING)
+                .on_disabled_hover_text(XMRIG_TUNING_DISABLED)
+                .clicked()
+            {
+                api.tuning = Some(tuning);
+            }
+        });
+    }
+    // Pause or throttle XMRig while the computer is in use.
+    fn idle_policy(&mut self, ui: &mut Ui) {
+        ui.checkbox(
+            &mut self.idle.enabled,
+            "Mine only when the computer is idle",
+        )
+        .on_hover_text(XMRIG_IDLE);
+        ui.add_enabled_ui(self.idle.enabled, |ui| {
+            slider_state_field(
+                ui,
+                "Max CPU load of other programs [0-100%]:",
+                XMRIG_IDLE_LOAD,
+                &mut self.idle.max_load,
+                0..=100,
+            );
+            ui.horizontal(|ui| {
+                ui.label("Programs:");
+                ui.add(
+                    TextEdit::singleline(&mut self.idle.processes).hint_text("steam, blender, obs"),
+                )
+                .on_hover_text(XMRIG_IDLE_PROCESSES);
+            });
+            ui.checkbox(&mut self.idle.pause_on_battery, "Pause on battery")
+                .on_hover_text(XMRIG_IDLE_BATTERY);
+            slider_state_field(
+                ui,
+                &format!("Threads while in use [0-{}]:", self.max_threads),
+                XMRIG_IDLE_THREADS,
+                &mut self.idle.busy_threads,
+                0..=self.max_threads,
+            );
+            slider_state_field(
+                ui,
+                "Resume after [0-600 seconds]:",
+                XMRIG_IDLE_RESUME,
+                &mut self.idle.resume_after,
+                0..=600,
+            );
+        });
+    }
+    fn thermal_guard(&mut self, ui: &mut Ui) {
+        ui.checkbox(
+            &mut self.thermal.enabled,
+            "Throttle when the CPU is too hot",
+        )
+        .on_hover_text(XMRIG_THERMAL);
+        ui.add_enabled_ui(self.thermal.enabled, |ui| {
+            slider_state_field(
+                ui,
+                "Temperature ceiling [50-105°C]:",
+                XMRIG_THERMAL_CEILING,
+                &mut self.thermal.ceiling,
+                50..=105,
+            );
+            slider_state_field(
+                ui,
+                "Hysteresis [1-30°C]:",
+                XMRIG_THERMAL_HYSTERESIS,
+                &mut self.thermal.hysteresis,
+                1..=30,
+            );
+            slider_state_field(
+                ui,
+                &format!("Threads while too hot [0-{}]:", self.max_threads),
+                XMRIG_THERMAL_THREADS,
+                &mut self.thermal.hot_threads,
+                0..=self.max_threads,
+            );
+        });
+    }
+    // NUMA nodes, physical cores and their logical CPUs, selected to pin the threads.
+    fn topology(&mut self, ui: &mut Ui, sys: &Arc<Mutex<Sys>>) {
+        let sys = sys.lock().unwrap();
+        let topology = &sys.topology;
+        ui.horizontal(|ui| {
+            let affinity = if self.affinity.is_empty() {
+                "chosen by XMRig".to_string()
+            } else {
+                format!("{} threads pinned", self.affinity.len())
+            };
+            ui.label(format!("CPU affinity: {affinity}"))
+                .on_hover_text(XMRIG_AFFINITY);
+            ui.separator();
+            if ui
+                .button("One thread per core")
+                .on_hover_text(XMRIG_AFFINITY_CORES)
+                .clicked()
+            {
+                self.affinity = topology.one_per_core();
+            }
+            if ui
+                .button("Spread across NUMA nodes")
+                .on_hover_text(XMRIG_AFFINITY_SPREAD)
+                .clicked()
+            {
+                self.affinity = topology.spread(self.current_threads);
+            }
+            if ui
+                .button("Let XMRig choose")
+                .on_hover_text(XMRIG_AFFINITY_RESET)
+                .clicked()
+            {
+                self.affinity.clear();
+            }
+        });
+        for node in &topology.nodes {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(format!("NUMA node {}:", node.id));
+                for core in &node.cores {
+                    // logical CPUs of the same physical core are grouped.
+                    ui.group(|ui| {
+                        for cpu in &core.cpus {
+                            let usage = sys.cpu_usage.get(*cpu as usize).copied();
+                            let selected = self.affinity.contains(cpu);
+                            if ui
+                                .selectable_label(selected, cpu.to_string())
+                                .on_hover_text(format!(
+                                    "CPU {cpu}, usage {:.0}%",
+                                    usage.unwrap_or_default()
+                                ))
+                                .clicked()
+                            {
+                                if selected {
+                                    self.affinity.retain(|c| c != cpu);
+                                } else {
+                                    self.affinity.push(*cpu);
+                                    topology.sort(&mut self.affinity);
+                                }
+                            }
+                        }
+                    });
+                }
+            });
+        }
+    }
+    // Mode used to get the privileges of XMRig, and the actions to change it.
+    #[cfg(target_family = "unix")]
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    fn privileges(
+        &mut self,
+        ui: &mut Ui,
+        sudo: &Arc<Mutex<SudoState>>,
+        error_state: &mut ErrorState,
+        xmrig_path: &Path,
+    ) {
+        // the result of a setup or detection is kept in the state.
+        if let Some(privilege) = sudo.lock().unwrap().setup_done.take() {
+            self.pages_1gb = privilege == XmrigPrivilege::Capabilities && cpu_has_1gb_pages();
+            self.privilege = privilege;
+        }
+        let busy = sudo.lock().unwrap().testing;
+        let confirm_id = ui.make_persistent_id("xmrig_privilege_setup_confirm");
+        ui.horizontal(|ui| {
+            ui.label(format!("Privileges: {}", self.privilege))
+                .on_hover_text(XMRIG_PRIVILEGE);
+            ui.separator();
+            ui.add_enabled_ui(!busy, |ui| {
+                #[cfg(target_os = "linux")]
+                if ui
+                    .button("Setup without sudo")
+                    .on_hover_text(XMRIG_PRIVILEGE_SETUP)
+                    .clicked()
+                {
+                    // the warning must be accepted before anything is asked.
+                    ui.data_mut(|d| d.insert_temp(confirm_id, true));
+                }
+                if ui
+                    .button("Detect sudo without password")
+                    .on_hover_text(XMRIG_PRIVILEGE_DETECT)
+                    .clicked()
+                {
+                    SudoState::detect_no_password(sudo);
+                }
+                if ui
+                    .button("Reset")
+                    .on_hover_text(XMRIG_PRIVILEGE_RESET)
+                    .clicked()
+                {
+                    // the files of the setup can only be removed as root, the mode is kept until it is done.
+                    #[cfg(target_os = "linux")]
+                    if self.privilege == XmrigPrivilege::Capabilities {
+                        error_state.ask_sudo(sudo);
+                        sudo.lock().unwrap().setup = Some(PrivilegeSetup::removal(xmrig_path));
+                        return;
+                    }
+                    self.privilege = XmrigPrivilege::Sudo;
+                    self.pages_1gb = false;
+                }
+            });
+        });
+        #[cfg(target_os = "linux")]
+        if ui
+            .data_mut(|d| d.get_temp::<bool>(confirm_id))
+            .unwrap_or_default()
+        {
+            ui.colored_label(YELLOW, XMRIG_PRIVILEGE_SETUP_WARNING);
+            ui.horizontal(|ui| {
+                if ui.button("Continue").clicked() {
+                    ui.data_mut(|d| d.remove::<bool>(confirm_id));
+                    // asking the password resets the sudo state, the setup is given after.
+                    error_state.ask_sudo(sudo);
+                    sudo.lock().unwrap().setup =
+                        Some(PrivilegeSetup::new(xmrig_path, self.max_threads));
+                }
+                if ui.button("Cancel").clicked() {
+                    ui.data_mut(|d| d.remove::<bool>(confirm_id));
+                }
+            });
+        }
+        let sudo = sudo.lock().unwrap();
+        if busy {
+            ui.spinner();
+        } else if !sudo.setup_msg.is_empty() {
+            ui.label(&sudo.setup_msg);
+        }
+    }
     fn name_field(&mut self, ui: &mut Ui) -> bool {
         StateTextEdit::new(ui)
             .description(" Name      ")
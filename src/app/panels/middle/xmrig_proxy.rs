@@ -15,7 +15,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use egui::{Checkbox, TextStyle, Ui, vec2};
+use egui::{Checkbox, Slider, TextStyle, Ui, vec2};
 use std::net::{IpAddr, Ipv4Addr};
 use std::sync::{Arc, Mutex};
 
@@ -32,8 +32,8 @@ use crate::regex::REGEXES;
 use crate::utils::constants::IP_NOT_FOUND;
 use crate::{
     SPACE, START_OPTIONS_HOVER, XMRIG_API_IP, XMRIG_API_PORT, XMRIG_IP, XMRIG_KEEPALIVE,
-    XMRIG_NAME, XMRIG_PORT, XMRIG_PROXY_INPUT, XMRIG_PROXY_REDIRECT, XMRIG_PROXY_URL, XMRIG_RIG,
-    XMRIG_TLS,
+    XMRIG_NAME, XMRIG_PORT, XMRIG_PROXY_INPUT, XMRIG_PROXY_REDIRECT, XMRIG_PROXY_URL,
+    XMRIG_PROXY_WORKER_SILENT, XMRIG_RIG, XMRIG_TLS,
 };
 
 use super::common::list_poolnode::PoolNode;
@@ -96,6 +96,12 @@ impl XmrigProxy {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                // not an argument of XMRig-Proxy, usable with custom arguments.
+                ui.add(
+                    Slider::new(&mut self.worker_silent_minutes, 0..=120)
+                        .text("minutes without share before alerting about a worker"),
+                )
+                .on_hover_text(XMRIG_PROXY_WORKER_SILENT);
                 if !self.arguments.is_empty() {
                     ui.disable();
                 }
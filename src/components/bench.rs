@@ -0,0 +1,255 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Local benchmark of the CPU with [xmrig --bench], started from the Status tab.
+// XMRig is run in a PTY like when mining, the result is parsed from its output.
+// XMRig is started without sudo, so the hashrate is lower without the hugepages
+// and the MSR mod, unless the setup without sudo of the XMRig tab was done.
+// The runs are kept in the data directory to compare them.
+
+use crate::disk::bench_runs::{BenchRun, BenchRuns};
+use crate::disk::consts::BENCH_RUNS_TOML;
+use crate::disk::state::Xmrig;
+use crate::disk::status::{BenchMode, BenchSize};
+use anyhow::{anyhow, bail};
+use log::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// [benchmark finished in 25.530 seconds (39169.9 h/s) hash sum = 0x...]
+static BENCH_RESULT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)benchmark finished in (\d+(?:\.\d+)?) seconds \((\d+(?:\.\d+)?) h/s\)")
+        .unwrap()
+});
+
+// Seconds and hashrate of a finished benchmark.
+pub fn parse_result(line: &str) -> Option<(f32, f32)> {
+    let caps = BENCH_RESULT.captures(line)?;
+    Some((caps[1].parse().ok()?, caps[2].parse().ok()?))
+}
+
+pub struct Bench {
+    pub busy: bool,
+    pub msg: String,
+    pub start: Option<Instant>,
+    // asked by the Status tab, the run is killed on the next check.
+    pub stop: bool,
+    pub runs: BenchRuns,
+    runs_path: Option<PathBuf>,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        Self {
+            busy: false,
+            msg: String::new(),
+            start: None,
+            stop: false,
+            runs: BenchRuns::default(),
+            runs_path: None,
+        }
+    }
+
+    // Load the runs of the previous sessions.
+    pub fn init(&mut self, os_data_path: &Path) {
+        let path = os_data_path.join(BENCH_RUNS_TOML);
+        self.runs = BenchRuns::get(&path);
+        self.runs_path = Some(path);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.runs.runs.len() {
+            self.runs.runs.remove(index);
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        if let Some(path) = &self.runs_path {
+            if let Err(e) = self.runs.save(path) {
+                error!("Benchmark | Could not save the runs: {}", e);
+            }
+        }
+    }
+
+    // Same hugepages options as when mining, without pool or HTTP API.
+    pub fn args(size: BenchSize, mode: BenchMode, threads: u16, state: &Xmrig) -> Vec<String> {
+        let mut args = vec![
+            "--no-color".to_string(),
+            format!("--bench={size}"),
+            format!("--randomx-mode={mode}"),
+            "--threads".to_string(),
+            threads.to_string(),
+        ];
+        if !state.huge_pages {
+            args.push("--no-huge-pages".to_string());
+        }
+        if state.pages_1gb {
+            args.push("--randomx-1gb-pages".to_string());
+        }
+        args
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn(
+        bench: &Arc<Mutex<Self>>,
+        xmrig_path: &Path,
+        size: BenchSize,
+        mode: BenchMode,
+        threads: u16,
+        state: &Xmrig,
+    ) {
+        info!("Spawning benchmark thread...");
+        let args = Self::args(size, mode, threads, state);
+        let path = xmrig_path.to_path_buf();
+        let bench = Arc::clone(bench);
+        {
+            let mut lock = bench.lock().unwrap();
+            lock.busy = true;
+            lock.stop = false;
+            lock.start = Some(Instant::now());
+            lock.msg = format!("Benchmark {size} {mode} with {threads} threads in progress");
+        }
+        std::thread::spawn(move || {
+            let result = Self::run(&bench, &path, args);
+            let mut lock = bench.lock().unwrap();
+            match result {
+                Ok((seconds, hashrate)) => {
+                    info!("Benchmark | {size} {mode} {threads} threads ... OK ... {hashrate} H/s");
+                    let date = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or_default();
+                    lock.runs.push(BenchRun {
+                        date,
+                        size,
+                        mode,
+                        threads,
+                        seconds,
+                        hashrate,
+                    });
+                    lock.save();
+                    lock.msg = format!(
+                        "Benchmark {size} {mode} with {threads} threads: {hashrate:.1} H/s in {seconds:.1} seconds"
+                    );
+                }
+                Err(err) => {
+                    error!(
+                        "Benchmark | {size} {mode} {threads} threads ... FAIL ... {}",
+                        err
+                    );
+                    lock.msg = format!("Benchmark failed: {err}");
+                }
+            }
+            lock.busy = false;
+            lock.start = None;
+        });
+    }
+
+    fn run(
+        bench: &Arc<Mutex<Self>>,
+        path: &Path,
+        args: Vec<String>,
+    ) -> Result<(f32, f32), anyhow::Error> {
+        let pair = portable_pty::native_pty_system().openpty(portable_pty::PtySize {
+            rows: 100,
+            cols: 1000,
+            pixel_width: 0,
+            pixel_height: 0,
+        })?;
+        let mut cmd = portable_pty::CommandBuilder::new(path);
+        cmd.args(args);
+        if let Some(dir) = path.parent() {
+            cmd.cwd(dir);
+        }
+        let mut child = pair.slave.spawn_command(cmd)?;
+        drop(pair.slave);
+        let reader = pair.master.try_clone_reader()?;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::BufReader::new(reader)
+                .lines()
+                .map_while(Result::ok)
+            {
+                let line = strip_ansi_escapes::strip_str(line);
+                if let Some(result) = parse_result(&line) {
+                    let _ = tx.send(result);
+                }
+            }
+        });
+        loop {
+            if let Ok(result) = rx.try_recv() {
+                // XMRig may wait for a key before exiting.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Ok(result);
+            }
+            if bench.lock().unwrap().stop {
+                child.kill()?;
+                let _ = child.wait();
+                bail!("stopped");
+            }
+            if let Some(status) = child.try_wait()? {
+                // the last lines can still be read after the exit.
+                return rx
+                    .recv_timeout(Duration::from_secs(1))
+                    .map_err(|_| anyhow!("XMRig exited without a result: {status:?}"));
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bench_result() {
+        let line = "[2025-01-12 12:00:00.000]  bench    benchmark finished in 25.530 seconds (39169.9 h/s) hash sum = 0x9F27C7D1E8C1F4A2";
+        assert_eq!(parse_result(line), Some((25.53, 39169.9)));
+        assert_eq!(
+            parse_result("benchmark finished in 3 seconds (333 H/s)"),
+            Some((3.0, 333.0))
+        );
+        assert!(
+            parse_result("[2025-01-12] bench    start benchmark hashes 1M algo rx/0").is_none()
+        );
+        let args = Bench::args(BenchSize::M10, BenchMode::Light, 4, &Xmrig::default());
+        assert_eq!(
+            args,
+            vec![
+                "--no-color",
+                "--bench=10M",
+                "--randomx-mode=light",
+                "--threads",
+                "4"
+            ]
+        );
+    }
+}
@@ -34,9 +34,13 @@ pub struct FileWindow {
     pub picked_xp: bool,          // Did the user pick a path for xmrig-proxy?
     pub picked_node: bool,        // Did the user pick a path for node?
     pub picked_nodedb: bool,      // Did the user pick a path for node?
+    pub picked_ban_list: bool,    // Did the user pick a path for the ban list of the node?
+    pub picked_nodedb_move: bool, // Did the user pick a directory to move the node DB to?
     pub p2pool_path: String,      // The picked p2pool path
     pub node_path: String,        // The picked node path
     pub nodedb_path: String,      // The picked node path
+    pub ban_list_path: String,    // The picked ban list path
+    pub nodedb_move_path: String, // The picked directory to move the node DB to
     pub xmrig_path: String,       // The picked xmrig path
     pub xmrig_proxy_path: String, // The picked xmrig-proxy path
 }
@@ -50,9 +54,13 @@ impl FileWindow {
             picked_xp: false,
             picked_node: false,
             picked_nodedb: false,
+            picked_ban_list: false,
+            picked_nodedb_move: false,
             p2pool_path: String::new(),
             node_path: String::new(),
             nodedb_path: String::new(),
+            ban_list_path: String::new(),
+            nodedb_move_path: String::new(),
             xmrig_path: String::new(),
             xmrig_proxy_path: String::new(),
         })
@@ -66,6 +74,8 @@ pub enum FileType {
     XmrigProxy,
     Node,
     NodeDB,
+    NodeDBMove,
+    BanList,
 }
 
 //---------------------------------------------------------------------------------------------------- Ratio Lock
@@ -106,6 +116,8 @@ impl Gupax {
             XmrigProxy => "XMRigProxy",
             Node => "Node",
             NodeDB => "Node DB",
+            NodeDBMove => "Node DB move",
+            BanList => "Node ban list",
         };
         let file_window = file_window.clone();
         file_window.lock().unwrap().thread = true;
@@ -114,6 +126,12 @@ impl Gupax {
                 NodeDB => rfd::FileDialog::new()
                     .set_title("Select a directory for the DB of your Node")
                     .pick_folder(),
+                NodeDBMove => rfd::FileDialog::new()
+                    .set_title("Select the directory to move the DB of your Node to")
+                    .pick_folder(),
+                BanList => rfd::FileDialog::new()
+                    .set_title("Select the ban list of your Node")
+                    .pick_file(),
                 _ => rfd::FileDialog::new()
                     .set_title(format!("Select {} Binary for Gupaxx", name))
                     .pick_file(),
@@ -141,6 +159,14 @@ impl Gupax {
                         file_window.lock().unwrap().nodedb_path = path.display().to_string();
                         file_window.lock().unwrap().picked_nodedb = true;
                     }
+                    NodeDBMove => {
+                        file_window.lock().unwrap().nodedb_move_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_nodedb_move = true;
+                    }
+                    BanList => {
+                        file_window.lock().unwrap().ban_list_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_ban_list = true;
+                    }
                 };
             } else {
                 info!("Gupaxx | No path selected for {}", name);
@@ -15,6 +15,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod bench;
 pub mod gupax;
 pub mod node;
+pub mod node_discovery;
+pub mod node_storage;
+pub mod payout_import;
 pub mod update;
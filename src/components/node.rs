@@ -16,11 +16,19 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::components::update::get_user_agent;
+use crate::disk::consts::NODE_SCORES_TOML;
+use crate::disk::discovered_nodes::DiscoveredNode;
+use crate::disk::node_scores::{NODE_SCORE_BAD, NodeScores};
+use crate::helper::node::PubNodeApi;
+use crate::miscs::{proxy, reqwest_client};
 use crate::{constants::*, macros::*};
 use egui::Color32;
 use log::*;
+use once_cell::sync::Lazy;
+use port_check::is_port_reachable_with_timeout;
 use rand::{Rng, rng};
-use reqwest::{Client, RequestBuilder};
+use reqwest::RequestBuilder;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -42,12 +50,53 @@ pub const REMOTE_NODES: [(&str, &str, &str, &str); 9] = [
 
 pub const REMOTE_NODE_LENGTH: usize = REMOTE_NODES.len();
 
+// (IP, LOCATION, RPC_PORT, ZMQ_PORT)
+pub type RemoteNodeEntry = (String, String, String, String);
+
+// Nodes verified by the discovery in [node_discovery.rs], used after [REMOTE_NODES].
+static DISCOVERED_NODES: Lazy<Mutex<Vec<RemoteNodeEntry>>> = Lazy::new(|| Mutex::new(vec![]));
+
+// The built-in nodes followed by the discovered ones.
+pub fn remote_nodes() -> Vec<RemoteNodeEntry> {
+    REMOTE_NODES
+        .iter()
+        .map(|(ip, location, rpc, zmq)| {
+            (
+                ip.to_string(),
+                location.to_string(),
+                rpc.to_string(),
+                zmq.to_string(),
+            )
+        })
+        .chain(DISCOVERED_NODES.lock().unwrap().iter().cloned())
+        .collect()
+}
+
+// Replace the discovered nodes, the built-in nodes are never duplicated.
+pub fn set_discovered_nodes(nodes: &[DiscoveredNode]) {
+    let mut discovered = DISCOVERED_NODES.lock().unwrap();
+    discovered.clear();
+    for node in nodes {
+        if REMOTE_NODES.iter().any(|(ip, _, _, _)| *ip == node.ip)
+            || discovered.iter().any(|(ip, _, _, _)| *ip == node.ip)
+        {
+            continue;
+        }
+        discovered.push((
+            node.ip.clone(),
+            node.location.clone(),
+            node.rpc.to_string(),
+            node.zmq.to_string(),
+        ));
+    }
+}
+
 #[allow(dead_code)]
 pub struct RemoteNode {
-    pub ip: &'static str,
-    pub location: &'static str,
-    pub rpc: &'static str,
-    pub zmq: &'static str,
+    pub ip: String,
+    pub location: String,
+    pub rpc: String,
+    pub zmq: String,
 }
 
 impl Default for RemoteNode {
@@ -62,7 +111,7 @@ impl RemoteNode {
     }
 
     pub fn check_exists(og_ip: &str) -> String {
-        for (ip, _, _, _) in REMOTE_NODES {
+        for (ip, _, _, _) in remote_nodes() {
             if og_ip == ip {
                 info!("Found remote node in array: {}", ip);
                 return ip.to_string();
@@ -76,55 +125,58 @@ impl RemoteNode {
         ip
     }
 
-    // Returns a default if index is not found in the const array.
+    // Returns a default if index is not found in the built-in and discovered nodes.
     pub fn from_index(index: usize) -> Self {
-        if index > REMOTE_NODE_LENGTH {
-            Self::new()
-        } else {
-            let (ip, location, rpc, zmq) = REMOTE_NODES[index];
-            Self {
+        match remote_nodes().into_iter().nth(index) {
+            Some((ip, location, rpc, zmq)) => Self {
                 ip,
                 location,
                 rpc,
                 zmq,
-            }
+            },
+            None => Self::new(),
         }
     }
 
-    pub fn get_ip_rpc_zmq(og_ip: &str) -> (&str, &str, &str) {
-        for (ip, _, rpc, zmq) in REMOTE_NODES {
+    pub fn get_ip_rpc_zmq(og_ip: &str) -> (String, String, String) {
+        for (ip, _, rpc, zmq) in remote_nodes() {
             if og_ip == ip {
                 return (ip, rpc, zmq);
             }
         }
         let (ip, _, rpc, zmq) = REMOTE_NODES[0];
-        (ip, rpc, zmq)
+        (ip.to_string(), rpc.to_string(), zmq.to_string())
     }
 
     // Return a random node (that isn't the one already selected).
     pub fn get_random(current_ip: &str) -> String {
-        let mut random_rng = rng().random_range(0..REMOTE_NODE_LENGTH);
-        let mut node = REMOTE_NODES[random_rng].0;
-        while current_ip == node {
-            random_rng = rng().random_range(0..REMOTE_NODE_LENGTH);
-            node = REMOTE_NODES[random_rng].0;
+        let nodes = remote_nodes();
+        let mut random_rng = rng().random_range(0..nodes.len());
+        while current_ip == nodes[random_rng].0 {
+            random_rng = rng().random_range(0..nodes.len());
         }
-        node.to_string()
+        nodes[random_rng].0.clone()
     }
 
     // Return a random valid node (no input str).
     pub fn get_random_same_ok() -> Self {
-        let rng = rng().random_range(0..REMOTE_NODE_LENGTH);
-        Self::from_index(rng)
+        let mut nodes = remote_nodes();
+        let (ip, location, rpc, zmq) = nodes.swap_remove(rng().random_range(0..nodes.len()));
+        Self {
+            ip,
+            location,
+            rpc,
+            zmq,
+        }
     }
 
     // Return the node [-1] of this one
     pub fn get_last(current_ip: &str) -> String {
         let mut found = false;
-        let mut last = current_ip;
-        for (ip, _, _, _) in REMOTE_NODES {
+        let mut last = current_ip.to_string();
+        for (ip, _, _, _) in remote_nodes() {
             if found {
-                return ip.to_string();
+                return ip;
             }
             if current_ip == ip {
                 found = true;
@@ -132,15 +184,15 @@ impl RemoteNode {
                 last = ip;
             }
         }
-        last.to_string()
+        last
     }
 
     // Return the node [+1] of this one
     pub fn get_next(current_ip: &str) -> String {
         let mut found = false;
-        for (ip, _, _, _) in REMOTE_NODES {
+        for (ip, _, _, _) in remote_nodes() {
             if found {
-                return ip.to_string();
+                return ip;
             }
             if current_ip == ip {
                 found = true;
@@ -160,7 +212,7 @@ impl RemoteNode {
             if current_ip == data.ip {
                 found = true;
             } else {
-                last = data.ip;
+                last = data.ip.as_str();
             }
         }
         last.to_string()
@@ -200,13 +252,9 @@ pub fn format_ms(ms: u128) -> String {
 // format_ip_location(monero1.heitechsoft.com) -> "monero1.heitechsoft.com | XX - LOCATION"
 // [extra_space] controls whether extra space is appended so the list aligns.
 pub fn format_ip_location(og_ip: &str, extra_space: bool) -> String {
-    for (ip, location, _, _) in REMOTE_NODES {
+    for (ip, location, _, _) in remote_nodes() {
         if og_ip == ip {
-            let ip = if extra_space {
-                format_ip(ip)
-            } else {
-                ip.to_string()
-            };
+            let ip = if extra_space { format_ip(&ip) } else { ip };
             return format!("{ip} | {location}");
         }
     }
@@ -222,22 +270,27 @@ pub const GREEN_NODE_PING: u128 = 100;
 // yellow is anything in-between green/red
 pub const RED_NODE_PING: u128 = 300;
 pub const TIMEOUT_NODE_PING: u128 = 1000;
+// The fastest node of an older ping is not used as bootstrap daemon.
+pub const BOOTSTRAP_PING_MAX_AGE: Duration = Duration::from_secs(600);
 
 #[derive(Debug, Clone)]
 pub struct NodeData {
-    pub ip: &'static str,
+    pub ip: String,
     pub ms: u128,
     pub color: Color32,
+    // health score of the last ping, out of 100
+    pub score: u8,
 }
 
 impl NodeData {
     pub fn new_vec() -> Vec<Self> {
         let mut vec = Vec::new();
-        for (ip, _, _, _) in REMOTE_NODES {
+        for (ip, _, _, _) in remote_nodes() {
             vec.push(Self {
                 ip,
                 ms: 0,
                 color: Color32::LIGHT_GRAY,
+                score: 0,
             });
         }
         vec
@@ -258,22 +311,101 @@ pub struct GetInfo<'a> {
     pub result: GetInfoResult,
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct GetInfoResult {
     pub mainnet: bool,
     pub synchronized: bool,
+    #[serde(default)]
+    pub height: u64,
+    // empty on a restricted RPC
+    #[serde(default)]
+    pub version: String,
+}
+
+//---------------------------------------------------------------------------------------------------- Node health
+// Oldest monerod version accepted, older nodes miss the RPC used by P2Pool.
+pub const NODE_MIN_VERSION: (u32, u32, u32) = (0, 18, 3);
+
+// What a ping learned about a remote node.
+#[derive(Debug, Clone, Default)]
+pub struct NodeHealth {
+    pub ms: u128,
+    // [None] if the node never gave a valid [get_info]
+    pub info: Option<GetInfoResult>,
+    // [None] if the ZMQ port was not checked, behind a proxy
+    pub zmq: Option<bool>,
+}
+
+impl NodeHealth {
+    // Score out of 100:
+    //   - latency:     40 green, 25 yellow, 10 red
+    //   - synced:      20
+    //   - height lag:  20 if <= 2 blocks behind [best_height], 10 if <= 10
+    //   - ZMQ port:    10
+    //   - version:     10, 5 if hidden by a restricted RPC
+    // A ZMQ port that was not checked does not count, the score is then out of the 90 other points.
+    pub fn score(&self, best_height: u64) -> u8 {
+        let Some(info) = &self.info else {
+            return 0;
+        };
+        let mut score = match self.ms {
+            ms if ms < GREEN_NODE_PING => 40,
+            ms if ms < RED_NODE_PING => 25,
+            ms if ms < TIMEOUT_NODE_PING => 10,
+            _ => 0,
+        };
+        if info.mainnet && info.synchronized {
+            score += 20;
+        }
+        score += match best_height.saturating_sub(info.height) {
+            0..=2 => 20,
+            3..=10 => 10,
+            _ => 0,
+        };
+        score += match Self::version_ok(&info.version) {
+            Some(true) => 10,
+            Some(false) => 0,
+            None => 5,
+        };
+        match self.zmq {
+            Some(true) => score + 10,
+            Some(false) => score,
+            None => (score as u16 * 100 / 90) as u8,
+        }
+    }
+
+    // "0.18.3.4-release" -> Some(true), [None] if the version is unknown.
+    pub fn version_ok(version: &str) -> Option<bool> {
+        let mut numbers = version
+            .split('-')
+            .next()?
+            .split('.')
+            .map(|n| n.parse::<u32>());
+        match (numbers.next(), numbers.next(), numbers.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch))) => {
+                Some((major, minor, patch) >= NODE_MIN_VERSION)
+            }
+            _ => None,
+        }
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- Ping data
-#[derive(Debug)]
 pub struct Ping {
     pub nodes: Vec<NodeData>,
-    pub fastest: &'static str,
+    pub fastest: String,
     pub pinging: bool,
     pub msg: String,
     pub prog: f32,
     pub pinged: bool,
+    // time of the last successful ping, its results get stale
+    pub pinged_at: Option<Instant>,
     pub auto_selected: bool,
+    // scores of the previous sessions, saved after each ping
+    pub scores: NodeScores,
+    scores_path: Option<PathBuf>,
+    // the local node gives the best known height when it is synced
+    node_api: Option<Arc<Mutex<PubNodeApi>>>,
 }
 
 impl Default for Ping {
@@ -286,12 +418,77 @@ impl Ping {
     pub fn new() -> Self {
         Self {
             nodes: NodeData::new_vec(),
-            fastest: REMOTE_NODES[0].0,
+            fastest: REMOTE_NODES[0].0.to_string(),
             pinging: false,
             msg: "No ping in progress".to_string(),
             prog: 0.0,
             pinged: false,
+            pinged_at: None,
             auto_selected: true,
+            scores: NodeScores::default(),
+            scores_path: None,
+            node_api: None,
+        }
+    }
+
+    // Load the remembered scores and link the local node.
+    // The discovered nodes must be loaded before, to be part of the list.
+    pub fn init(&mut self, os_data_path: &Path, node_api: &Arc<Mutex<PubNodeApi>>) {
+        self.nodes = NodeData::new_vec();
+        let path = os_data_path.join(NODE_SCORES_TOML);
+        self.scores = NodeScores::get(&path);
+        self.scores_path = Some(path);
+        self.node_api = Some(Arc::clone(node_api));
+    }
+
+    // Nodes consistently bad across pings are not used as backup.
+    pub fn is_bad(&self, ip: &str) -> bool {
+        self.scores.is_bad(ip)
+    }
+
+    // [ip:rpc] of the fastest node in good health, to answer the RPC while the local node syncs.
+    // [None] if the last ping is too old to trust the node is still up.
+    pub fn bootstrap_address(&self) -> Option<String> {
+        if !self.pinged
+            || self
+                .pinged_at
+                .is_none_or(|at| at.elapsed() > BOOTSTRAP_PING_MAX_AGE)
+        {
+            return None;
+        }
+        self.nodes
+            .iter()
+            .find(|n| n.score >= NODE_SCORE_BAD && !self.is_bad(&n.ip))
+            .map(|n| {
+                let (ip, rpc, _) = RemoteNode::get_ip_rpc_zmq(&n.ip);
+                format!("{ip}:{rpc}")
+            })
+    }
+
+    // Score the pinged nodes against the best height known and remember the scores.
+    fn score(&mut self, health: &[(String, NodeHealth)]) {
+        let local_height = self.node_api.as_ref().and_then(|api| {
+            let api = api.lock().unwrap();
+            api.synchronized.then_some(api.height)
+        });
+        let best_height = health
+            .iter()
+            .filter_map(|(_, h)| h.info.as_ref().map(|i| i.height))
+            .chain(local_height)
+            .max()
+            .unwrap_or_default();
+        for (ip, health) in health {
+            let score = health.score(best_height);
+            info!("Ping | {ip} ... score {score}/100");
+            if let Some(node) = self.nodes.iter_mut().find(|n| n.ip == *ip) {
+                node.score = score;
+            }
+            self.scores.push(ip, score);
+        }
+        if let Some(path) = &self.scores_path {
+            if let Err(e) = self.scores.save(path) {
+                error!("Ping | Saving node scores ... FAIL ... {}", e);
+            }
         }
     }
 
@@ -309,6 +506,7 @@ impl Ping {
                     info!("Ping ... OK");
                     ping.lock().unwrap().msg = msg;
                     ping.lock().unwrap().pinged = true;
+                    ping.lock().unwrap().pinged_at = Some(Instant::now());
                     ping.lock().unwrap().auto_selected = false;
                     ping.lock().unwrap().prog = 100.0;
                 }
@@ -348,30 +546,31 @@ impl Ping {
         let ping = Arc::clone(ping);
         ping.lock().unwrap().pinging = true;
         ping.lock().unwrap().prog = 0.0;
-        let percent = (100.0 / (REMOTE_NODE_LENGTH as f32)).floor();
+        let nodes = remote_nodes();
+        let percent = (100.0 / (nodes.len() as f32)).floor();
 
         // Create HTTP client
         let info = "Creating HTTP Client".to_string();
         ping.lock().unwrap().msg = info;
-        let client = Client::new();
+        let client = reqwest_client();
 
         // Random User Agent
         let rand_user_agent = get_user_agent();
         // Handle vector
-        let mut handles = Vec::with_capacity(REMOTE_NODE_LENGTH);
-        let node_vec = arc_mut!(Vec::with_capacity(REMOTE_NODE_LENGTH));
+        let mut handles = Vec::with_capacity(nodes.len());
+        let node_vec = arc_mut!(Vec::with_capacity(nodes.len()));
 
-        for (ip, _, rpc, _zmq) in REMOTE_NODES {
+        for (ip, _, rpc, zmq) in nodes {
             let client = client.clone();
             let ping = Arc::clone(&ping);
             let node_vec = Arc::clone(&node_vec);
             let request = client
-                .post("http://".to_string() + ip + ":" + rpc + "/json_rpc")
+                .post(format!("http://{ip}:{rpc}/json_rpc"))
                 .header("User-Agent", rand_user_agent)
                 .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#);
 
             let handle = tokio::task::spawn(async move {
-                Self::response(request, ip, ping, percent, node_vec).await;
+                Self::response(request, ip, zmq, ping, percent, node_vec).await;
             });
             handles.push(handle);
         }
@@ -380,15 +579,19 @@ impl Ping {
             handle.await?;
         }
 
-        let mut node_vec = std::mem::take(&mut *node_vec.lock().unwrap());
+        let (mut node_vec, health): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut *node_vec.lock().unwrap())
+                .into_iter()
+                .unzip();
         node_vec.sort_by(|a, b| a.ms.cmp(&b.ms));
         let fastest_info = format!("Fastest node: {}ms ... {}", node_vec[0].ms, node_vec[0].ip);
 
         let info = "Cleaning up connections".to_string();
         info!("Ping | {}...", info);
         let mut ping = ping.lock().unwrap();
-        ping.fastest = node_vec[0].ip;
+        ping.fastest = node_vec[0].ip.clone();
         ping.nodes = node_vec;
+        ping.score(&health);
         ping.msg = info;
         drop(ping);
         Ok(fastest_info)
@@ -398,13 +601,15 @@ impl Ping {
     #[inline(never)]
     async fn response(
         request: RequestBuilder,
-        ip: &'static str,
+        ip: String,
+        zmq: String,
         ping: Arc<Mutex<Self>>,
         percent: f32,
-        node_vec: Arc<Mutex<Vec<NodeData>>>,
+        node_vec: Arc<Mutex<Vec<(NodeData, (String, NodeHealth))>>>,
     ) {
         // test multiples request as first can apparently timeout.
         let mut vec_ms = vec![];
+        let mut info = None;
         for _ in 0..6 {
             // clone request
             let req = request
@@ -419,12 +624,14 @@ impl Ping {
                     match json_rpc.bytes().await {
                         Ok(b) => match serde_json::from_slice::<GetInfo<'_>>(&b) {
                             Ok(rpc) => {
-                                if rpc.result.mainnet && rpc.result.synchronized {
+                                let ms = if rpc.result.mainnet && rpc.result.synchronized {
                                     now_req.elapsed().as_millis()
                                 } else {
                                     warn!("Ping | {ip} responded with valid get_info but is not in sync, remove this node!");
                                     TIMEOUT_NODE_PING
-                                }
+                                };
+                                info = Some(rpc.result);
+                                ms
                             }
                             _ => {
                                 warn!("Ping | {ip} responded but with invalid get_info, remove this node!");
@@ -442,6 +649,26 @@ impl Ping {
             .min()
             .expect("at least the value of timeout should be present");
 
+        // a node without a reachable ZMQ port can not be used by P2Pool.
+        // It is not checked behind a proxy, a direct connection would bypass it.
+        let zmq_port = zmq.parse::<u16>().unwrap_or_default();
+        let zmq_ip = ip.clone();
+        let zmq = if proxy().is_none() {
+            Some(
+                tokio::task::spawn_blocking(move || {
+                    is_port_reachable_with_timeout(
+                        (zmq_ip, zmq_port),
+                        Duration::from_millis(TIMEOUT_NODE_PING as u64),
+                    )
+                })
+                .await
+                .unwrap_or_default(),
+            )
+        } else {
+            None
+        };
+        let health = NodeHealth { ms, info, zmq };
+
         let info = format!("{ms}ms ... {ip}");
         info!("Ping | {ms}ms ... {ip}");
         info!("{:?}", vec_ms);
@@ -458,7 +685,15 @@ impl Ping {
         ping.msg = info;
         ping.prog += percent;
         drop(ping);
-        node_vec.lock().unwrap().push(NodeData { ip, ms, color });
+        node_vec.lock().unwrap().push((
+            NodeData {
+                ip: ip.clone(),
+                ms,
+                color,
+                score: 0,
+            },
+            (ip, health),
+        ));
     }
 }
 //---------------------------------------------------------------------------------------------------- NODE
@@ -467,8 +702,14 @@ mod test {
     use log::error;
     use reqwest::Client;
 
-    use crate::components::node::{REMOTE_NODE_LENGTH, REMOTE_NODES, format_ip};
+    use crate::components::node::{
+        BOOTSTRAP_PING_MAX_AGE, GetInfoResult, NodeHealth, Ping, REMOTE_NODE_LENGTH, REMOTE_NODES,
+        RemoteNode, format_ip, remote_nodes, set_discovered_nodes,
+    };
     use crate::components::update::get_user_agent;
+    use crate::disk::discovered_nodes::DiscoveredNode;
+    use crate::disk::node_scores::{NODE_SCORES_HISTORY, NodeScores};
+    use std::time::Instant;
     // Iterate through all nodes, find the longest domain.
     pub const REMOTE_NODE_MAX_CHARS: usize = {
         let mut len = 0;
@@ -497,6 +738,111 @@ mod test {
         }
     }
 
+    #[test]
+    fn node_health_score() {
+        let info = |synchronized, height, version: &str| GetInfoResult {
+            mainnet: true,
+            synchronized,
+            height,
+            version: version.to_string(),
+        };
+        let health = NodeHealth {
+            ms: 50,
+            info: Some(info(true, 3_000_000, "0.18.3.4-release")),
+            zmq: Some(true),
+        };
+        assert_eq!(health.score(3_000_001), 100);
+        // the ZMQ port is not checked behind a proxy, a healthy node still gets 100.
+        let unchecked = NodeHealth {
+            zmq: None,
+            ..health
+        };
+        assert_eq!(unchecked.score(3_000_001), 100);
+        // behind the local node, restricted RPC hides the version
+        let health = NodeHealth {
+            ms: 200,
+            info: Some(info(true, 2_999_995, "")),
+            zmq: Some(false),
+        };
+        assert_eq!(health.score(3_000_000), 25 + 20 + 10 + 5);
+        // no valid answer
+        assert_eq!(NodeHealth::default().score(3_000_000), 0);
+        assert_eq!(NodeHealth::version_ok("0.18.4.0-release"), Some(true));
+        assert_eq!(NodeHealth::version_ok("0.17.3.2"), Some(false));
+        assert_eq!(NodeHealth::version_ok(""), None);
+    }
+
+    #[test]
+    fn node_scores_bad() {
+        let mut scores = NodeScores::default();
+        scores.push("node", 10);
+        scores.push("node", 20);
+        assert!(!scores.is_bad("node"));
+        scores.push("node", 30);
+        assert!(scores.is_bad("node"));
+        scores.push("node", 90);
+        assert!(!scores.is_bad("node"));
+        for _ in 0..10 {
+            scores.push("node", 50);
+        }
+        assert_eq!(scores.nodes["node"].len(), NODE_SCORES_HISTORY);
+        assert_eq!(scores.average("node"), Some(50));
+        assert!(!scores.is_bad("unknown"));
+    }
+
+    #[test]
+    fn bootstrap_address_stale() {
+        let mut ping = Ping::new();
+        assert_eq!(ping.bootstrap_address(), None);
+        ping.pinged = true;
+        ping.nodes[0].score = 100;
+        let ip = ping.nodes[0].ip.clone();
+        ping.pinged_at = Some(Instant::now());
+        assert!(ping.bootstrap_address().unwrap().starts_with(&ip));
+        // the fastest node of an old ping is not trusted anymore.
+        if let Some(old) = Instant::now().checked_sub(BOOTSTRAP_PING_MAX_AGE * 2) {
+            ping.pinged_at = Some(old);
+            assert_eq!(ping.bootstrap_address(), None);
+        }
+    }
+
+    #[test]
+    fn discovered_nodes_fallback() {
+        let node = DiscoveredNode {
+            ip: "10.0.0.1".to_string(),
+            rpc: 18081,
+            zmq: 18083,
+            location: "Peer".to_string(),
+            verified: 0,
+        };
+        // a built-in node is not added twice.
+        let builtin = DiscoveredNode {
+            ip: REMOTE_NODES[0].0.to_string(),
+            ..node.clone()
+        };
+        set_discovered_nodes(&[node, builtin]);
+        assert_eq!(remote_nodes().len(), REMOTE_NODE_LENGTH + 1);
+        let discovered = RemoteNode::from_index(REMOTE_NODE_LENGTH);
+        assert_eq!(
+            (
+                discovered.ip.as_str(),
+                discovered.rpc.as_str(),
+                discovered.zmq.as_str()
+            ),
+            ("10.0.0.1", "18081", "18083")
+        );
+        assert_eq!(
+            RemoteNode::get_ip_rpc_zmq("10.0.0.1"),
+            (
+                "10.0.0.1".to_string(),
+                "18081".to_string(),
+                "18083".to_string()
+            )
+        );
+        set_discovered_nodes(&[]);
+        assert_eq!(remote_nodes().len(), REMOTE_NODE_LENGTH);
+    }
+
     #[test]
     fn spacing() {
         for (ip, _, _, _) in REMOTE_NODES {
@@ -0,0 +1,436 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Discovery of remote nodes usable by P2Pool.
+// The candidates come from the peer list of the local node ([get_peer_list]),
+// from the seeds given by the user and from the previous discoveries.
+// A candidate is kept if its public RPC answers a synced mainnet [get_info]
+// and one of its ZMQ ports is reachable.
+// The verified nodes are cached in [discovered_nodes.toml] and added
+// after the built-in nodes, so they are pinged and selectable in Simple mode.
+
+use crate::components::node::{NodeHealth, Ping, set_discovered_nodes};
+use crate::components::update::get_user_agent;
+use crate::disk::consts::DISCOVERED_NODES_TOML;
+use crate::disk::discovered_nodes::{DiscoveredNode, DiscoveredNodes};
+use crate::miscs::{post_rpc, proxy, reqwest_client};
+use crate::regex::REGEXES;
+use anyhow::bail;
+use log::*;
+use port_check::is_port_reachable_with_timeout;
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::timeout;
+
+// Candidates probed by discovery, the peers of the white list come first.
+pub const DISCOVERY_CANDIDATES_MAX: usize = 48;
+// ZMQ ports tried when a candidate does not give one, the usual ones of public nodes.
+pub const DISCOVERY_ZMQ_PORTS: [u16; 2] = [18083, 18084];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+//---------------------------------------------------------------------------------------------------- Candidates
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Candidate {
+    pub ip: String,
+    pub rpc: u16,
+    pub zmq: Option<u16>,
+    pub location: &'static str,
+}
+
+// Seeds are [host:rpc] or [host:rpc:zmq], separated by commas or spaces.
+pub fn parse_seeds(seeds: &str) -> Result<Vec<Candidate>, String> {
+    let port = |p: &str| p.parse::<u16>().ok().filter(|p| *p != 0);
+    seeds
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|seed| {
+            let parts: Vec<&str> = seed.split(':').collect();
+            let host_ok = |h: &str| REGEXES.ipv4.is_match(h) || REGEXES.domain.is_match(h);
+            let node = match parts[..] {
+                [ip, rpc] if host_ok(ip) => port(rpc).map(|rpc| (ip, rpc, None)),
+                [ip, rpc, zmq] if host_ok(ip) => port(rpc)
+                    .zip(port(zmq))
+                    .map(|(rpc, zmq)| (ip, rpc, Some(zmq))),
+                _ => None,
+            };
+            node.map(|(ip, rpc, zmq)| Candidate {
+                ip: ip.to_string(),
+                rpc,
+                zmq,
+                location: "Seed",
+            })
+            .ok_or_else(|| format!("Invalid seed: {seed}"))
+        })
+        .collect()
+}
+
+pub fn seeds_ok(seeds: &str) -> bool {
+    parse_seeds(seeds).is_ok()
+}
+
+// Response of [get_peer_list], peers without public RPC have a [rpc_port] of 0.
+#[derive(Debug, Default, Deserialize)]
+pub struct PeerList {
+    #[serde(default)]
+    pub white_list: Vec<Peer>,
+    #[serde(default)]
+    pub gray_list: Vec<Peer>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Peer {
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub rpc_port: u16,
+}
+
+impl PeerList {
+    pub fn candidates(&self) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = vec![];
+        for peer in self.white_list.iter().chain(self.gray_list.iter()) {
+            if peer.rpc_port == 0
+                || peer.host.is_empty()
+                || candidates.iter().any(|c| c.ip == peer.host)
+            {
+                continue;
+            }
+            candidates.push(Candidate {
+                ip: peer.host.clone(),
+                rpc: peer.rpc_port,
+                zmq: None,
+                location: "Peer",
+            });
+        }
+        candidates
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Discovery
+pub struct NodeDiscovery {
+    pub busy: bool,
+    pub msg: String,
+    pub prog: f32,
+    // nodes in the cache
+    pub found: usize,
+    path: Option<PathBuf>,
+}
+
+impl Default for NodeDiscovery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeDiscovery {
+    pub fn new() -> Self {
+        Self {
+            busy: false,
+            msg: "No discovery in progress".to_string(),
+            prog: 0.0,
+            found: 0,
+            path: None,
+        }
+    }
+
+    // Add the nodes of the previous discoveries to the remote nodes.
+    pub fn init(&mut self, os_data_path: &Path) {
+        let path = os_data_path.join(DISCOVERED_NODES_TOML);
+        let cache = DiscoveredNodes::get(&path);
+        set_discovered_nodes(&cache.nodes);
+        self.found = cache.nodes.len();
+        self.path = Some(path);
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Discover nodes from the local node listening on [local_rpc_port] and the [seeds],
+    // then ping all the remote nodes again.
+    pub fn spawn_thread(
+        discovery: &Arc<Mutex<Self>>,
+        ping: &Arc<Mutex<Ping>>,
+        local_rpc_port: u16,
+        local_login: Option<String>,
+        seeds: Vec<Candidate>,
+    ) {
+        info!("Spawning node discovery thread...");
+        let discovery = Arc::clone(discovery);
+        let ping = Arc::clone(ping);
+        {
+            let mut lock = discovery.lock().unwrap();
+            lock.busy = true;
+            lock.prog = 0.0;
+            lock.msg = "Discovery in progress".to_string();
+        }
+        std::thread::spawn(move || {
+            let now = Instant::now();
+            match Self::discover(&discovery, local_rpc_port, local_login, seeds) {
+                Ok(msg) => {
+                    info!("Node discovery ... OK");
+                    discovery.lock().unwrap().msg = msg;
+                    if !ping.lock().unwrap().pinging {
+                        Ping::spawn_thread(&ping);
+                    }
+                }
+                Err(err) => {
+                    error!("Node discovery ... FAIL ... {}", err);
+                    discovery.lock().unwrap().msg = err.to_string();
+                }
+            }
+            info!(
+                "Node discovery ... Took [{}] seconds...",
+                now.elapsed().as_secs_f32()
+            );
+            let mut lock = discovery.lock().unwrap();
+            lock.busy = false;
+            lock.prog = 100.0;
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[tokio::main]
+    async fn discover(
+        discovery: &Arc<Mutex<Self>>,
+        local_rpc_port: u16,
+        local_login: Option<String>,
+        seeds: Vec<Candidate>,
+    ) -> Result<String, anyhow::Error> {
+        let client = reqwest_client();
+        let path = discovery.lock().unwrap().path.clone();
+        let mut cache = path
+            .as_deref()
+            .map(DiscoveredNodes::get)
+            .unwrap_or_default();
+
+        // the seeds, then the previous discoveries and the peers of the local node.
+        discovery.lock().unwrap().msg = "Reading the peer list of the local node".to_string();
+        let peers = match Self::peer_list(&client, local_rpc_port, local_login.as_deref()).await {
+            Ok(peers) => peers.candidates(),
+            Err(e) => {
+                warn!("Node discovery | Local node peer list ... FAIL ... {}", e);
+                vec![]
+            }
+        };
+        let mut candidates: Vec<Candidate> = vec![];
+        let cached = cache.nodes.iter().map(|n| Candidate {
+            ip: n.ip.clone(),
+            rpc: n.rpc,
+            zmq: Some(n.zmq),
+            location: if n.location == "Seed" { "Seed" } else { "Peer" },
+        });
+        for candidate in seeds.into_iter().chain(cached).chain(peers) {
+            if !candidates.iter().any(|c| c.ip == candidate.ip) {
+                candidates.push(candidate);
+            }
+        }
+        candidates.truncate(DISCOVERY_CANDIDATES_MAX);
+        if candidates.is_empty() {
+            bail!("No node to probe, start the local node or add seeds");
+        }
+
+        let percent = 100.0 / candidates.len() as f32;
+        let mut handles = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let client = client.clone();
+            let discovery = Arc::clone(discovery);
+            handles.push(tokio::task::spawn(async move {
+                let verified = Self::probe(&client, &candidate).await;
+                let mut lock = discovery.lock().unwrap();
+                lock.prog += percent;
+                lock.msg = format!(
+                    "{} ... {}",
+                    if verified.is_some() { "OK" } else { "FAIL" },
+                    candidate.ip
+                );
+                (candidate, verified)
+            }));
+        }
+        let mut verified = vec![];
+        let mut failed = vec![];
+        for handle in handles {
+            match handle.await? {
+                (_, Some(node)) => verified.push(node),
+                (candidate, None) => failed.push(candidate.ip),
+            }
+        }
+
+        // dead nodes of the previous discoveries are dropped.
+        cache.nodes.retain(|n| !failed.contains(&n.ip));
+        let count = verified.len();
+        cache.merge(verified);
+        if let Some(path) = &path {
+            cache.save(path)?;
+        }
+        set_discovered_nodes(&cache.nodes);
+        discovery.lock().unwrap().found = cache.nodes.len();
+        Ok(format!(
+            "{count} node(s) verified, {} discovered node(s) in the list",
+            cache.nodes.len()
+        ))
+    }
+
+    // The local node can require the RPC login of the Node tab.
+    async fn peer_list(
+        client: &Client,
+        local_rpc_port: u16,
+        login: Option<&str>,
+    ) -> Result<PeerList, anyhow::Error> {
+        let url = format!("http://127.0.0.1:{local_rpc_port}/get_peer_list");
+        let peers = timeout(
+            PROBE_TIMEOUT,
+            post_rpc(client, &url, "{}".to_string(), login),
+        )
+        .await??
+        .json::<PeerList>()
+        .await?;
+        Ok(peers)
+    }
+
+    // A synced mainnet node with a recent enough version and a reachable ZMQ port.
+    async fn probe(client: &Client, candidate: &Candidate) -> Option<DiscoveredNode> {
+        let info = client
+            .post(format!(
+                "http://{}:{}/json_rpc",
+                candidate.ip, candidate.rpc
+            ))
+            .header("User-Agent", get_user_agent())
+            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#)
+            .timeout(PROBE_TIMEOUT)
+            .send()
+            .await
+            .ok()?
+            .json::<serde_json::Value>()
+            .await
+            .ok()?;
+        let result = info.get("result")?;
+        let flag = |name: &str| result.get(name).and_then(|v| v.as_bool()) == Some(true);
+        let version = result.get("version").and_then(|v| v.as_str()).unwrap_or("");
+        if !flag("mainnet")
+            || !flag("synchronized")
+            || NodeHealth::version_ok(version) == Some(false)
+        {
+            debug!("Node discovery | {} is not a usable node", candidate.ip);
+            return None;
+        }
+        // behind a proxy the port can not be checked without bypassing it,
+        // so only a ZMQ port already known from the seed or a previous discovery is used.
+        if proxy().is_some() {
+            let Some(zmq) = candidate.zmq else {
+                debug!(
+                    "Node discovery | {} has no known ZMQ port, it can not be checked behind the proxy",
+                    candidate.ip
+                );
+                return None;
+            };
+            return Some(DiscoveredNode {
+                ip: candidate.ip.clone(),
+                rpc: candidate.rpc,
+                zmq,
+                location: candidate.location.to_string(),
+                verified: now_secs(),
+            });
+        }
+        let ports = match candidate.zmq {
+            Some(zmq) => vec![zmq],
+            None => DISCOVERY_ZMQ_PORTS.to_vec(),
+        };
+        let ip = candidate.ip.clone();
+        let zmq = tokio::task::spawn_blocking(move || {
+            ports
+                .into_iter()
+                .find(|port| is_port_reachable_with_timeout((ip.as_str(), *port), PROBE_TIMEOUT))
+        })
+        .await
+        .ok()??;
+        Some(DiscoveredNode {
+            ip: candidate.ip.clone(),
+            rpc: candidate.rpc,
+            zmq,
+            location: candidate.location.to_string(),
+            verified: now_secs(),
+        })
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn discovery_seeds() {
+        let seeds = parse_seeds("node.example.com:18089:18084, 1.2.3.4:18081").unwrap();
+        assert_eq!(seeds.len(), 2);
+        assert_eq!(seeds[0].zmq, Some(18084));
+        assert_eq!(seeds[1].ip, "1.2.3.4");
+        assert_eq!(seeds[1].zmq, None);
+        assert!(parse_seeds("").unwrap().is_empty());
+        assert!(parse_seeds("1.2.3.4").is_err());
+        assert!(parse_seeds("1.2.3.4:0").is_err());
+        assert!(parse_seeds("bad host:18081").is_err());
+    }
+
+    #[test]
+    fn discovery_peer_list() {
+        let json = r#"{"status":"OK","white_list":[
+            {"host":"1.2.3.4","port":18080,"rpc_port":18089},
+            {"host":"5.6.7.8","port":18080,"rpc_port":0}],
+            "gray_list":[{"host":"1.2.3.4","port":18080,"rpc_port":18089},
+            {"host":"9.9.9.9","port":18080,"rpc_port":18081}]}"#;
+        let peers: PeerList = serde_json::from_str(json).unwrap();
+        let candidates = peers.candidates();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].ip, "1.2.3.4");
+        assert_eq!(candidates[1].rpc, 18081);
+    }
+
+    #[test]
+    fn discovery_cache() {
+        let node = |ip: &str, verified| DiscoveredNode {
+            ip: ip.to_string(),
+            rpc: 18089,
+            zmq: 18084,
+            location: "Peer".to_string(),
+            verified,
+        };
+        let mut cache = DiscoveredNodes::default();
+        cache.merge((0..40).map(|i| node(&format!("10.0.0.{i}"), i)).collect());
+        assert_eq!(
+            cache.nodes.len(),
+            crate::disk::discovered_nodes::DISCOVERED_NODES_MAX
+        );
+        assert_eq!(cache.nodes[0].verified, 39);
+        cache.merge(vec![node("10.0.0.39", 100)]);
+        assert_eq!(
+            cache.nodes.iter().filter(|n| n.ip == "10.0.0.39").count(),
+            1
+        );
+        assert_eq!(cache.nodes[0].verified, 100);
+        let toml = toml::to_string(&cache).unwrap();
+        assert_eq!(toml::from_str::<DiscoveredNodes>(&toml).unwrap(), cache);
+    }
+}
@@ -0,0 +1,528 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Storage of the blockchain of the local node.
+// The size of the database and the free space are read from the filesystem,
+// so they are known before the node is started.
+// Moving and pruning the database are done in a thread, with the node stopped
+// since monerod keeps the database open.
+// A corrupted database is detected in the output of monerod, the Node tab then offers
+// to start again with [--db-salvage] so monerod recovers it from its last valid state,
+// to verify it by reading it to the end with [monero-blockchain-stats], or to move it aside and resync.
+
+use anyhow::{anyhow, bail};
+use log::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::Disks;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+// Space taken by the blockchain, with some margin for its growth.
+pub const NODE_DB_SIZE_PRUNED: u64 = 100 * GIB;
+pub const NODE_DB_SIZE_FULL: u64 = 260 * GIB;
+// A database bigger than this is not pruned.
+pub const NODE_DB_PRUNED_MAX: u64 = 150 * GIB;
+// Name of the directory of the database inside the data directory of monerod.
+pub const NODE_DB_LMDB: &str = "lmdb";
+// monero-blockchain-prune leaves the database before pruning in this directory.
+pub const NODE_DB_LMDB_OLD: &str = "lmdb-old";
+// Sizes are read again at this interval while a job is running.
+pub const NODE_STORAGE_REFRESH_SECS: u64 = 1;
+#[cfg(target_os = "windows")]
+pub const NODE_PRUNE_BINARY: &str = "monero-blockchain-prune.exe";
+#[cfg(not(target_os = "windows"))]
+pub const NODE_PRUNE_BINARY: &str = "monero-blockchain-prune";
+#[cfg(target_os = "windows")]
+pub const NODE_STATS_BINARY: &str = "monero-blockchain-stats.exe";
+#[cfg(not(target_os = "windows"))]
+pub const NODE_STATS_BINARY: &str = "monero-blockchain-stats";
+
+//---------------------------------------------------------------------------------------------------- Storage info
+// Read from the filesystem when the Node tab is shown and on refresh.
+#[derive(Debug, Clone)]
+pub struct StorageInfo {
+    pub data_dir: PathBuf,
+    pub db_size: Option<u64>,
+    pub free_space: Option<u64>,
+    // size of the database left by a prune, if it was not deleted.
+    pub old_db_size: Option<u64>,
+    pub read_at: Instant,
+}
+
+impl StorageInfo {
+    pub fn read(data_dir: PathBuf) -> Self {
+        Self {
+            db_size: db_size(&data_dir),
+            free_space: free_space(&data_dir),
+            old_db_size: dir_size(&data_dir.join(NODE_DB_LMDB_OLD)),
+            read_at: Instant::now(),
+            data_dir,
+        }
+    }
+    /// Space that is missing to sync the rest of the blockchain, if any.
+    pub fn space_missing(&self, pruned: bool) -> Option<u64> {
+        let needed = if pruned {
+            NODE_DB_SIZE_PRUNED
+        } else {
+            NODE_DB_SIZE_FULL
+        };
+        let needed = needed.saturating_sub(self.db_size.unwrap_or_default());
+        self.free_space
+            .filter(|free| *free < needed)
+            .map(|free| needed - free)
+    }
+    /// An existing database that is too big to be a pruned one.
+    pub fn prunable(&self) -> bool {
+        self.db_size.is_some_and(|size| size > NODE_DB_PRUNED_MAX)
+    }
+}
+
+/// Size of the lmdb database in the data directory, if there is one.
+pub fn db_size(data_dir: &Path) -> Option<u64> {
+    std::fs::metadata(data_dir.join(NODE_DB_LMDB).join("data.mdb"))
+        .ok()
+        .map(|m| m.len())
+}
+
+/// Size of the files of a directory, if it exists.
+pub fn dir_size(dir: &Path) -> Option<u64> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    Some(
+        entries
+            .filter_map(|e| e.ok()?.metadata().ok())
+            .filter(|m| m.is_file())
+            .map(|m| m.len())
+            .sum(),
+    )
+}
+
+/// Free space of the disk of the directory.
+/// The directory might not exist yet, the disk of its first existing parent is used.
+pub fn free_space(dir: &Path) -> Option<u64> {
+    let dir = dir.ancestors().find_map(|p| p.canonicalize().ok())?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| dir.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Progress in percent from a line of [monero-blockchain-prune], given as [done/total] or [n%].
+pub fn prune_progress(line: &str) -> Option<f32> {
+    static PROGRESS: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?P<done>\d+)\s*/\s*(?P<total>\d+)|(?P<percent>\d+(\.\d+)?)%").unwrap()
+    });
+    let c = PROGRESS.captures(line)?;
+    if let Some(percent) = c.name("percent") {
+        return percent.as_str().parse::<f32>().ok().map(|p| p.min(100.0));
+    }
+    let done: f32 = c.name("done")?.as_str().parse().ok()?;
+    let total: f32 = c.name("total")?.as_str().parse().ok()?;
+    (total > 0.0 && done <= total).then(|| done / total * 100.0)
+}
+
+/// First line of the output of monerod telling that the database is corrupted or can not be opened.
+pub fn db_error(output: &str) -> Option<String> {
+    static DB_ERROR: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"(?i)MDB_(CORRUPTED|PAGE_NOTFOUND|INVALID|PANIC|BAD_TXN|VERSION_MISMATCH)|error opening database|failed to open db|failed to initialize a database|database is corrupt",
+        )
+        .unwrap()
+    });
+    output
+        .lines()
+        .find(|line| DB_ERROR.is_match(line))
+        .map(|line| line.trim().to_string())
+}
+
+//---------------------------------------------------------------------------------------------------- Storage jobs
+#[derive(Debug)]
+pub struct NodeStorage {
+    pub busy: bool,
+    pub msg: String,
+    pub prog: f32,
+    // new data directory after a move, taken by the GUI to update the state.
+    pub moved_to: Option<PathBuf>,
+    // the database was moved aside, taken by the GUI to start the node again.
+    pub resync: bool,
+    // results of the jobs, taken by the GUI to be written in the console of the node.
+    pub console: String,
+    // a job ended, taken by the GUI to read the sizes again.
+    pub done: bool,
+}
+
+impl Default for NodeStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeStorage {
+    pub fn new() -> Self {
+        Self {
+            busy: false,
+            msg: String::new(),
+            prog: 0.0,
+            moved_to: None,
+            resync: false,
+            console: String::new(),
+            done: false,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Move the database of [from] into the data directory [to].
+    pub fn spawn_move(storage: &Arc<Mutex<Self>>, from: PathBuf, to: PathBuf) {
+        info!("Spawning node database move thread...");
+        Self::spawn(storage, "Move", move |storage| {
+            Self::move_db(storage, &from, &to)?;
+            storage.lock().unwrap().moved_to = Some(to.clone());
+            Ok(format!("Database moved to {}", to.display()))
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Prune the database of [data_dir] with the binary found next to monerod.
+    pub fn spawn_prune(storage: &Arc<Mutex<Self>>, node_path: &Path, data_dir: PathBuf) {
+        info!("Spawning node database prune thread...");
+        let bin = node_path.with_file_name(NODE_PRUNE_BINARY);
+        Self::spawn(storage, "Prune", move |storage| {
+            Self::run_tool(storage, &bin, &data_dir)?;
+            Ok(format!(
+                "Database pruned, the old one is left in {}, delete it to free the space",
+                data_dir.join(NODE_DB_LMDB_OLD).display()
+            ))
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Verify the database of [data_dir] with the binary found next to monerod,
+    // it reads every block and exits on the first error of the database.
+    pub fn spawn_verify(storage: &Arc<Mutex<Self>>, node_path: &Path, data_dir: PathBuf) {
+        info!("Spawning node database verify thread...");
+        let bin = node_path.with_file_name(NODE_STATS_BINARY);
+        Self::spawn(storage, "Verify", move |storage| {
+            Self::run_tool(storage, &bin, &data_dir)?;
+            Ok("No error found while reading the whole database".to_string())
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Delete the database left by a prune in [data_dir].
+    pub fn spawn_delete_old(storage: &Arc<Mutex<Self>>, data_dir: PathBuf) {
+        info!("Spawning node old database delete thread...");
+        Self::spawn(storage, "Delete old database", move |_| {
+            let dir = data_dir.join(NODE_DB_LMDB_OLD);
+            std::fs::remove_dir_all(&dir)?;
+            Ok(format!("{} deleted", dir.display()))
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Move aside the database of [data_dir], monerod synchronizes a new one on start.
+    pub fn spawn_move_aside(storage: &Arc<Mutex<Self>>, data_dir: PathBuf) {
+        info!("Spawning node database move aside thread...");
+        Self::spawn(storage, "Move aside", move |storage| {
+            let dst = Self::move_aside(&data_dir)?;
+            storage.lock().unwrap().resync = true;
+            Ok(format!(
+                "Database moved to {}, it can be removed once the node is synchronized",
+                dst.display()
+            ))
+        });
+    }
+
+    fn spawn<F>(storage: &Arc<Mutex<Self>>, name: &'static str, job: F)
+    where
+        F: FnOnce(&Arc<Mutex<Self>>) -> Result<String, anyhow::Error> + Send + 'static,
+    {
+        let storage = Arc::clone(storage);
+        {
+            let mut lock = storage.lock().unwrap();
+            lock.busy = true;
+            lock.prog = 0.0;
+            lock.msg = format!("{name} in progress");
+        }
+        std::thread::spawn(move || {
+            let now = Instant::now();
+            let (msg, line) = match job(&storage) {
+                Ok(msg) => {
+                    info!("Node storage | {name} ... OK");
+                    storage.lock().unwrap().prog = 100.0;
+                    let line = format!("Node storage | {name} ... OK ... {msg}\n");
+                    (msg, line)
+                }
+                Err(err) => {
+                    error!("Node storage | {name} ... FAIL ... {}", err);
+                    let line = format!("Node storage | {name} ... FAIL ... {err}\n");
+                    (err.to_string(), line)
+                }
+            };
+            info!(
+                "Node storage | {name} ... Took [{}] seconds...",
+                now.elapsed().as_secs_f32()
+            );
+            let mut lock = storage.lock().unwrap();
+            lock.msg = msg;
+            lock.console.push_str(&line);
+            lock.busy = false;
+            lock.done = true;
+        });
+    }
+
+    // A rename is instant on the same disk, otherwise the files are copied then removed.
+    fn move_db(storage: &Arc<Mutex<Self>>, from: &Path, to: &Path) -> Result<(), anyhow::Error> {
+        let src = from.join(NODE_DB_LMDB);
+        let dst = to.join(NODE_DB_LMDB);
+        if !src.is_dir() {
+            bail!("No database found in {}", from.display());
+        }
+        if dst.exists() {
+            bail!("A database already exists in {}", to.display());
+        }
+        std::fs::create_dir_all(to)?;
+        if std::fs::rename(&src, &dst).is_ok() {
+            return Ok(());
+        }
+        let files = std::fs::read_dir(&src)?
+            .map(|e| e.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let total: u64 = files
+            .iter()
+            .filter_map(|f| f.metadata().ok())
+            .map(|m| m.len())
+            .sum();
+        std::fs::create_dir(&dst)?;
+        if let Err(e) = Self::copy_files(storage, &files, &dst, total) {
+            // do not leave a partial database behind.
+            let _ = std::fs::remove_dir_all(&dst);
+            return Err(e);
+        }
+        std::fs::remove_dir_all(&src)?;
+        Ok(())
+    }
+
+    // Renamed in the same directory, so it is instant and does not need more space.
+    fn move_aside(data_dir: &Path) -> Result<PathBuf, anyhow::Error> {
+        let src = data_dir.join(NODE_DB_LMDB);
+        if !src.is_dir() {
+            bail!("No database found in {}", data_dir.display());
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let dst = data_dir.join(format!("{NODE_DB_LMDB}.corrupted.{now}"));
+        std::fs::rename(&src, &dst)?;
+        Ok(dst)
+    }
+
+    fn copy_files(
+        storage: &Arc<Mutex<Self>>,
+        files: &[PathBuf],
+        dst: &Path,
+        total: u64,
+    ) -> Result<(), anyhow::Error> {
+        let mut buf = vec![0; 8 * 1024 * 1024];
+        let mut copied = 0;
+        for file in files {
+            let name = file
+                .file_name()
+                .ok_or_else(|| anyhow!("Invalid file {}", file.display()))?;
+            let mut reader = File::open(file)?;
+            let mut writer = File::create(dst.join(name))?;
+            loop {
+                let n = reader.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                writer.write_all(&buf[..n])?;
+                copied += n as u64;
+                storage.lock().unwrap().prog = (copied as f32 / total.max(1) as f32) * 100.0;
+            }
+            writer.sync_all()?;
+        }
+        Ok(())
+    }
+
+    // Run a tool of the Monero bundle on the database.
+    // The output is read until the end, lines are separated by new lines or carriage returns
+    // since the progress is written on the same line.
+    fn run_tool(
+        storage: &Arc<Mutex<Self>>,
+        bin: &Path,
+        data_dir: &Path,
+    ) -> Result<(), anyhow::Error> {
+        let name = bin.file_name().unwrap_or_default().to_string_lossy();
+        if !bin.is_file() {
+            bail!("{} not found next to monerod", name);
+        }
+        let mut child = Command::new(bin)
+            .arg("--data-dir")
+            .arg(data_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("No output from {}", name))?;
+        let mut buf = [0; 4096];
+        let mut line = Vec::new();
+        loop {
+            let n = stdout.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            for byte in &buf[..n] {
+                if *byte != b'\n' && *byte != b'\r' {
+                    line.push(*byte);
+                    continue;
+                }
+                let text = strip_ansi_escapes::strip_str(String::from_utf8_lossy(&line));
+                let text = text.trim();
+                if !text.is_empty() {
+                    debug!("Node storage | {} output: {}", name, text);
+                    let mut lock = storage.lock().unwrap();
+                    if let Some(prog) = prune_progress(text) {
+                        lock.prog = prog;
+                    }
+                    lock.msg = text.to_string();
+                }
+                line.clear();
+            }
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("{} failed: {}", name, status);
+        }
+        Ok(())
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn space_missing() {
+        let info = StorageInfo {
+            data_dir: PathBuf::new(),
+            db_size: Some(40 * GIB),
+            free_space: Some(50 * GIB),
+            old_db_size: None,
+            read_at: Instant::now(),
+        };
+        assert_eq!(info.space_missing(true), Some(10 * GIB));
+        assert_eq!(info.space_missing(false), Some(170 * GIB));
+        assert!(!info.prunable());
+        let info = StorageInfo {
+            db_size: Some(200 * GIB),
+            free_space: Some(70 * GIB),
+            ..info
+        };
+        assert_eq!(info.space_missing(true), None);
+        assert_eq!(info.space_missing(false), None);
+        assert!(info.prunable());
+        let info = StorageInfo {
+            free_space: None,
+            ..info
+        };
+        assert_eq!(info.space_missing(false), None);
+    }
+
+    #[test]
+    fn prune_progress_lines() {
+        assert_eq!(prune_progress("copying txs_pruned: 500/1000"), Some(50.0));
+        assert_eq!(prune_progress("Pruning 25.5%"), Some(25.5));
+        assert_eq!(prune_progress("Swapping databases"), None);
+        assert_eq!(prune_progress("0/0"), None);
+        assert_eq!(prune_progress("10/5"), None);
+    }
+
+    #[test]
+    fn move_db() {
+        let dir = std::env::temp_dir().join("gupaxx_node_storage_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let from = dir.join("from");
+        let to = dir.join("to");
+        std::fs::create_dir_all(from.join(NODE_DB_LMDB)).unwrap();
+        std::fs::write(from.join(NODE_DB_LMDB).join("data.mdb"), [0; 1024]).unwrap();
+        assert_eq!(db_size(&from), Some(1024));
+        let storage = Arc::new(Mutex::new(NodeStorage::new()));
+        NodeStorage::move_db(&storage, &from, &to).unwrap();
+        assert_eq!(db_size(&from), None);
+        assert_eq!(db_size(&to), Some(1024));
+        // a database is never overwritten.
+        std::fs::create_dir_all(from.join(NODE_DB_LMDB)).unwrap();
+        assert!(NodeStorage::move_db(&storage, &from, &to).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn db_errors() {
+        let output = "2025-01-01 12:00:00.000 I Loading blockchain from folder /home/user/.bitmonero/lmdb ...
+2025-01-01 12:00:00.100 E Error opening database: Failed to open db handle for m_blocks: MDB_CORRUPTED: Located page was wrong type
+2025-01-01 12:00:00.200 E Failed to initialize core";
+        assert_eq!(
+            db_error(output).unwrap(),
+            "2025-01-01 12:00:00.100 E Error opening database: Failed to open db handle for m_blocks: MDB_CORRUPTED: Located page was wrong type"
+        );
+        assert!(db_error("MDB_PAGE_NOTFOUND: Requested page not found").is_some());
+        assert!(db_error("Synced 3100000/3300000 (93%, 200000 left)").is_none());
+        assert!(db_error("").is_none());
+    }
+
+    #[test]
+    fn move_aside() {
+        let dir = std::env::temp_dir().join("gupaxx_node_move_aside_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(NodeStorage::move_aside(&dir).is_err());
+        std::fs::create_dir_all(dir.join(NODE_DB_LMDB)).unwrap();
+        std::fs::write(dir.join(NODE_DB_LMDB).join("data.mdb"), [0; 1024]).unwrap();
+        let dst = NodeStorage::move_aside(&dir).unwrap();
+        assert_eq!(db_size(&dir), None);
+        assert!(dst.join("data.mdb").is_file());
+        assert!(dst.starts_with(&dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn old_db() {
+        let dir = std::env::temp_dir().join("gupaxx_node_old_db_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(StorageInfo::read(dir.clone()).old_db_size, None);
+        std::fs::create_dir_all(dir.join(NODE_DB_LMDB_OLD)).unwrap();
+        std::fs::write(dir.join(NODE_DB_LMDB_OLD).join("data.mdb"), [0; 1024]).unwrap();
+        assert_eq!(StorageInfo::read(dir.clone()).old_db_size, Some(1024));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
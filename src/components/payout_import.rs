@@ -0,0 +1,435 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Import P2Pool payouts that were not seen in the P2Pool output by Gupaxx.
+// The coinbase transaction of every block in a chosen range is fetched from the local Monero node
+// and its outputs are checked against the view key of the wallet.
+// An output belonging to the wallet in a coinbase transaction is a payout, since P2Pool pays
+// miners directly in the coinbase of the blocks it finds.
+// P2Pool writes its side chain block in the merge mining tag of the coinbase,
+// so the blocks mined solo or by another pool are not imported.
+
+use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
+use crate::human::HumanNumber;
+use crate::miscs::{post_rpc, reqwest_client};
+use crate::xmr::AtomicUnit;
+use anyhow::{Context, anyhow};
+use log::*;
+use monero::{
+    Address, Block, PrivateKey, PublicKey, ViewPair, blockdata::transaction::SubField,
+    consensus::deserialize,
+};
+use reqwest::Client;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+// If no start height is given, scan the last ~30 days of blocks.
+pub const PAYOUT_IMPORT_DEFAULT_RANGE: u64 = 30 * 24 * 30;
+// Tries for a block before it is skipped.
+const PAYOUT_IMPORT_TRIES: u32 = 3;
+// The node is considered unreachable after this many skipped blocks in a row, the scan stops.
+const PAYOUT_IMPORT_MAX_SKIPPED: u32 = 10;
+
+//---------------------------------------------------------------------------------------------------- Import data
+#[derive(Debug)]
+pub struct PayoutImport {
+    pub address: String,
+    // never saved to disk
+    pub view_key: String,
+    pub height_start: String,
+    pub height_end: String,
+    // cleared by the scan thread when it exits
+    pub scanning: bool,
+    // asked by the user, the scan thread stops at the next block
+    pub stop: bool,
+    pub msg: String,
+    pub prog: f32,
+}
+
+impl Default for PayoutImport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PayoutImport {
+    pub fn new() -> Self {
+        Self {
+            address: String::with_capacity(95),
+            view_key: String::with_capacity(64),
+            height_start: String::new(),
+            height_end: String::new(),
+            scanning: false,
+            stop: false,
+            msg: "No scan in progress".to_string(),
+            prog: 0.0,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Intermediate function for spawning thread
+    pub fn spawn_thread(
+        import: &Arc<Mutex<Self>>,
+        gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+        node_rpc: String,
+        node_login: Option<String>,
+    ) {
+        info!("Spawning payout import thread...");
+        let import = Arc::clone(import);
+        let gupax_p2pool_api = Arc::clone(gupax_p2pool_api);
+        {
+            let mut lock = import.lock().unwrap();
+            lock.scanning = true;
+            lock.stop = false;
+        }
+        std::thread::spawn(move || {
+            let now = Instant::now();
+            match Self::scan(&import, &gupax_p2pool_api, &node_rpc, node_login.as_deref()) {
+                Ok(msg) => {
+                    info!("Payout import ... OK");
+                    import.lock().unwrap().msg = msg;
+                    import.lock().unwrap().prog = 100.0;
+                }
+                Err(err) => {
+                    error!("Payout import ... FAIL ... {}", err);
+                    import.lock().unwrap().msg = err.to_string();
+                }
+            }
+            info!(
+                "Payout import ... Took [{}] seconds...",
+                now.elapsed().as_secs_f32()
+            );
+            import.lock().unwrap().scanning = false;
+        });
+    }
+
+    // The scan goes as follows:
+    //   - Build the [ViewPair] from the address and private view key
+    //   - Get the height range, defaulting to the last 30 days up to the node's height
+    //   - For every block, ask [get_block] to the node and deserialize the blob
+    //   - Check the outputs of the coinbase transaction with the [ViewPair], if it was made by P2Pool
+    //   - Merge found payouts into the [GupaxP2poolApi], skipping blocks already present
+    //
+    // A block that can not be read after a few tries is skipped and reported at the end.
+    // The user can stop the scan by setting [stop] to true, and it stops by itself if the node stops answering.
+    // The payouts found until then are always merged.
+    #[cold]
+    #[inline(never)]
+    #[tokio::main]
+    pub async fn scan(
+        import: &Arc<Mutex<Self>>,
+        gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+        node_rpc: &str,
+        node_login: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let (address, view_key, height_start, height_end) = {
+            let mut lock = import.lock().unwrap();
+            lock.prog = 0.0;
+            lock.msg = "Checking keys".to_string();
+            (
+                lock.address.trim().to_string(),
+                lock.view_key.trim().to_string(),
+                lock.height_start.trim().to_string(),
+                lock.height_end.trim().to_string(),
+            )
+        };
+        let pair = Self::view_pair(&address, &view_key)?;
+        let client = reqwest_client();
+        let url = format!("http://{}/json_rpc", node_rpc);
+
+        // Height range
+        let end = if height_end.is_empty() {
+            Self::request_block_count(&client, &url, node_login)
+                .await?
+                .saturating_sub(1)
+        } else {
+            height_end
+                .parse::<u64>()
+                .context("End height is not a number")?
+        };
+        let start = if height_start.is_empty() {
+            end.saturating_sub(PAYOUT_IMPORT_DEFAULT_RANGE)
+        } else {
+            height_start
+                .parse::<u64>()
+                .context("Start height is not a number")?
+        };
+        if start > end {
+            return Err(anyhow!(
+                "Start height [{start}] is higher than end height [{end}]"
+            ));
+        }
+        info!("Payout import | Scanning blocks [{start}..={end}] with node [{node_rpc}]");
+
+        let total = (end - start + 1) as f32;
+        let mut payouts = vec![];
+        let mut scanned = 0;
+        let mut skipped = vec![];
+        let mut skipped_in_a_row = 0;
+        let mut not_p2pool = 0;
+        let mut unreachable = None;
+        for height in start..=end {
+            if import.lock().unwrap().stop {
+                warn!("Payout import | Stopped by the user at block [{height}]");
+                break;
+            }
+            let mut checked = Err(anyhow!("no try"));
+            for attempt in 1..=PAYOUT_IMPORT_TRIES {
+                checked = Self::check_block(&client, &url, node_login, &pair, height).await;
+                match &checked {
+                    Ok(_) => break,
+                    Err(e) => warn!(
+                        "Payout import | Block [{height}] try [{attempt}/{PAYOUT_IMPORT_TRIES}] ... FAIL ... {e}"
+                    ),
+                }
+            }
+            let (timestamp, amount, p2pool) = match checked {
+                Ok(checked) => {
+                    skipped_in_a_row = 0;
+                    checked
+                }
+                Err(e) => {
+                    skipped.push(height);
+                    skipped_in_a_row += 1;
+                    if skipped_in_a_row >= PAYOUT_IMPORT_MAX_SKIPPED {
+                        unreachable = Some(e);
+                        break;
+                    }
+                    continue;
+                }
+            };
+            if amount != 0 && !p2pool {
+                info!(
+                    "Payout import | Skipped [{amount}] atomic units in block [{height}], not mined by P2Pool"
+                );
+                not_p2pool += 1;
+            } else if amount != 0 {
+                let date = Self::format_timestamp(timestamp);
+                info!(
+                    "Payout import | Found payout of [{amount}] atomic units in block [{height}]"
+                );
+                payouts.push((
+                    date,
+                    AtomicUnit::from_u64(amount),
+                    HumanNumber::from_u64(height),
+                ));
+            }
+            scanned += 1;
+            let mut lock = import.lock().unwrap();
+            lock.prog = (scanned as f32 / total) * 100.0;
+            lock.msg = format!(
+                "Block {height} ... {}/{} ... {} payouts found",
+                scanned,
+                total as u64,
+                payouts.len()
+            );
+        }
+
+        let found = payouts.len();
+        let added = gupax_p2pool_api.lock().unwrap().merge_payouts(payouts)?;
+        let mut msg =
+            format!("Scanned {scanned} blocks, found {found} payouts, {added} new payouts added");
+        if not_p2pool != 0 {
+            msg.push_str(&format!(
+                ", {not_p2pool} blocks paying this address not mined by P2Pool were skipped"
+            ));
+        }
+        if !skipped.is_empty() {
+            msg.push_str(&format!(
+                ", {} blocks could not be read: {}",
+                skipped.len(),
+                Self::heights(&skipped)
+            ));
+        }
+        match unreachable {
+            Some(e) => Err(anyhow!("The node stopped answering ({e}). {msg}")),
+            None => Ok(msg),
+        }
+    }
+
+    // Timestamp, amount received and if the coinbase was made by P2Pool.
+    async fn check_block(
+        client: &Client,
+        url: &str,
+        login: Option<&str>,
+        pair: &ViewPair,
+        height: u64,
+    ) -> Result<(i64, u64, bool), anyhow::Error> {
+        let (blob, timestamp) = Self::request_block(client, url, login, height).await?;
+        let block: Block = deserialize(&hex::decode(blob)?)
+            .with_context(|| format!("Could not deserialize block [{height}]"))?;
+        let amount = block
+            .miner_tx
+            .check_outputs(pair, 0..1, 0..1)
+            .with_context(|| format!("Could not check outputs of block [{height}]"))?
+            .iter()
+            .filter_map(|out| out.amount())
+            .sum();
+        Ok((timestamp, amount, Self::is_p2pool_coinbase(&block)))
+    }
+
+    // P2Pool writes the id of its side chain block in the merge mining tag of the coinbase.
+    fn is_p2pool_coinbase(block: &Block) -> bool {
+        block
+            .miner_tx
+            .prefix
+            .extra
+            .0
+            .iter()
+            .any(|field| matches!(field, SubField::MergeMining(..)))
+    }
+
+    // Skipped heights shown to the user, the list can be long if the node was down for a while.
+    pub fn heights(heights: &[u64]) -> String {
+        const SHOWN: usize = 10;
+        let mut list = heights
+            .iter()
+            .take(SHOWN)
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if heights.len() > SHOWN {
+            list.push_str(&format!(" and {} more", heights.len() - SHOWN));
+        }
+        list
+    }
+
+    // Check the keys given by the user and return a [ViewPair] to check outputs.
+    pub fn view_pair(address: &str, view_key: &str) -> Result<ViewPair, anyhow::Error> {
+        let address = Address::from_str(address).context("Invalid Monero address")?;
+        let view = PrivateKey::from_str(view_key).context("Invalid private view key")?;
+        if PublicKey::from_private_key(&view) != address.public_view {
+            return Err(anyhow!(
+                "The private view key does not belong to this address"
+            ));
+        }
+        Ok(ViewPair {
+            view,
+            spend: address.public_spend,
+        })
+    }
+
+    // Same format as the date of P2Pool log lines, block timestamps are in seconds.
+    pub fn format_timestamp(timestamp: i64) -> String {
+        match chrono::DateTime::from_timestamp(timestamp, 0) {
+            Some(date) => date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S.0000")
+                .to_string(),
+            None => "????-??-?? ??:??:??.????".to_string(),
+        }
+    }
+
+    async fn request_block_count(
+        client: &Client,
+        url: &str,
+        login: Option<&str>,
+    ) -> Result<u64, anyhow::Error> {
+        let count = post_rpc(
+            client,
+            url,
+            r#"{"jsonrpc":"2.0","id":"0","method":"get_block_count"}"#.to_string(),
+            login,
+        )
+        .await?
+        .json::<GetBlockCount>()
+        .await?;
+        Ok(count.result.count)
+    }
+
+    async fn request_block(
+        client: &Client,
+        url: &str,
+        login: Option<&str>,
+        height: u64,
+    ) -> Result<(String, i64), anyhow::Error> {
+        let block = post_rpc(
+            client,
+            url,
+            format!(
+                r#"{{"jsonrpc":"2.0","id":"0","method":"get_block","params":{{"height":{height}}}}}"#
+            ),
+            login,
+        )
+        .await?
+        .json::<GetBlock>()
+        .await?;
+        Ok((block.result.blob, block.result.block_header.timestamp))
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- `/get_block`
+// Only the fields needed to check the coinbase outputs.
+#[derive(Debug, Deserialize)]
+struct GetBlockCount {
+    result: GetBlockCountResult,
+}
+#[derive(Debug, Deserialize)]
+struct GetBlockCountResult {
+    count: u64,
+}
+#[derive(Debug, Deserialize)]
+struct GetBlock {
+    result: GetBlockResult,
+}
+#[derive(Debug, Deserialize)]
+struct GetBlockResult {
+    blob: String,
+    block_header: BlockHeader,
+}
+#[derive(Debug, Deserialize)]
+struct BlockHeader {
+    timestamp: i64,
+}
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::PayoutImport;
+
+    #[test]
+    fn reject_wrong_keys() {
+        // Random valid address, with a view key that is not its own.
+        let address = "44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW";
+        let view_key = "77916d0cd56ed1920aef6ca56d8a41bac915b68e4c46a589e0956e27a7b77404";
+        assert!(PayoutImport::view_pair(address, view_key).is_err());
+        assert!(PayoutImport::view_pair("4abc", view_key).is_err());
+        assert!(PayoutImport::view_pair(address, "not a key").is_err());
+    }
+
+    #[test]
+    fn timestamp_like_p2pool_date() {
+        let date = PayoutImport::format_timestamp(1_700_000_000);
+        assert_eq!(date.len(), "2022-01-27 01:30:23.1377".len());
+        assert!(date.ends_with(".0000"));
+    }
+
+    #[test]
+    fn skipped_heights() {
+        assert_eq!(
+            PayoutImport::heights(&[3_000_000, 3_000_005]),
+            "3000000, 3000005"
+        );
+        let heights = (1..=12).collect::<Vec<u64>>();
+        assert_eq!(
+            PayoutImport::heights(&heights),
+            "1, 2, 3, 4, 5, 6, 7, 8, 9, 10 and 2 more"
+        );
+    }
+}
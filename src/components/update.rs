@@ -30,7 +30,7 @@ use crate::{
     disk::{state::State, *},
     helper::ProcessName,
     macros::*,
-    miscs::get_exe_dir,
+    miscs::{get_exe_dir, reqwest_client},
     utils::errors::{ErrorButtons, ErrorFerris, ErrorState},
 };
 use anyhow::{Error, anyhow};
@@ -397,7 +397,7 @@ impl Update {
         info!("Update | {}", msg);
         *lock.msg.lock().unwrap() = msg;
         drop(lock);
-        let client = Client::new();
+        let client = reqwest_client();
         *update.lock().unwrap().prog.lock().unwrap() += 5.0;
         info!(
             "Update | Init ... OK ... {}%",
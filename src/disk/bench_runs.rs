@@ -0,0 +1,81 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Local runs of [xmrig --bench], kept across sessions in [benchmark_runs.toml].
+
+use crate::disk::status::{BenchMode, BenchSize};
+use crate::disk::*;
+
+// Number of runs kept, the oldest ones are dropped.
+pub const BENCH_RUNS_MAX: usize = 12;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BenchRun {
+    // UNIX timestamp of the end of the run
+    pub date: u64,
+    pub size: BenchSize,
+    pub mode: BenchMode,
+    pub threads: u16,
+    pub seconds: f32,
+    pub hashrate: f32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct BenchRuns {
+    // oldest first
+    pub runs: Vec<BenchRun>,
+}
+
+impl BenchRuns {
+    // Read the runs, a missing or invalid file gives no runs.
+    pub fn get(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(string) => match toml::from_str(&string) {
+                Ok(runs) => {
+                    info!("BenchRuns | Read ... OK");
+                    runs
+                }
+                Err(e) => {
+                    warn!("BenchRuns | Parse ... FAIL ... {}", e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), TomlError> {
+        let string = toml::to_string(self).map_err(TomlError::Serialize)?;
+        fs::write(path, string)?;
+        info!("BenchRuns | Save ... OK");
+        Ok(())
+    }
+
+    pub fn push(&mut self, run: BenchRun) {
+        self.runs.push(run);
+        if self.runs.len() > BENCH_RUNS_MAX {
+            self.runs.remove(0);
+        }
+    }
+
+    // Fastest run, compared with the benchmarks of [cpu.json].
+    pub fn best(&self) -> Option<&BenchRun> {
+        self.runs
+            .iter()
+            .max_by(|a, b| a.hashrate.total_cmp(&b.hashrate))
+    }
+}
@@ -14,6 +14,10 @@ pub const DIRECTORY: &str = "gupaxx/";
 pub const STATE_TOML: &str = "state.toml";
 pub const NODE_TOML: &str = "node.toml";
 pub const POOL_TOML: &str = "pool.toml";
+pub const BAN_LIST_TXT: &str = "monero_ban_list.txt";
+pub const NODE_SCORES_TOML: &str = "node_scores.toml";
+pub const DISCOVERED_NODES_TOML: &str = "discovered_nodes.toml";
+pub const BENCH_RUNS_TOML: &str = "benchmark_runs.toml";
 
 // P2Pool API
 // Lives within the Gupax OS data directory.
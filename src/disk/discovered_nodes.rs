@@ -0,0 +1,75 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Remote nodes verified by the node discovery, kept across sessions in [discovered_nodes.toml].
+
+use crate::disk::*;
+
+// Number of discovered nodes kept, the most recently verified first.
+pub const DISCOVERED_NODES_MAX: usize = 16;
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DiscoveredNode {
+    pub ip: String,
+    pub rpc: u16,
+    pub zmq: u16,
+    // "Peer" if found in the peer list of the local node, "Seed" if given by the user
+    pub location: String,
+    // UNIX timestamp of the last successful probe
+    pub verified: u64,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DiscoveredNodes {
+    pub nodes: Vec<DiscoveredNode>,
+}
+
+impl DiscoveredNodes {
+    // Read the cache, a missing or invalid file gives an empty cache.
+    pub fn get(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(string) => match toml::from_str(&string) {
+                Ok(nodes) => {
+                    info!("DiscoveredNodes | Read ... OK");
+                    nodes
+                }
+                Err(e) => {
+                    warn!("DiscoveredNodes | Parse ... FAIL ... {}", e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), TomlError> {
+        let string = toml::to_string(self).map_err(TomlError::Serialize)?;
+        fs::write(path, string)?;
+        info!("DiscoveredNodes | Save ... OK");
+        Ok(())
+    }
+
+    // Add or refresh verified nodes, the oldest ones are dropped past [DISCOVERED_NODES_MAX].
+    pub fn merge(&mut self, verified: Vec<DiscoveredNode>) {
+        for node in verified {
+            self.nodes.retain(|n| n.ip != node.ip);
+            self.nodes.push(node);
+        }
+        self.nodes.sort_by(|a, b| b.verified.cmp(&a.verified));
+        self.nodes.truncate(DISCOVERED_NODES_MAX);
+    }
+}
@@ -211,6 +211,63 @@ impl GupaxP2poolApi {
         self.update_payout_strings();
     }
 
+    // Merge payouts found outside of the P2Pool output (e.g: by scanning the blockchain).
+    // Payouts for a block already present in the log are skipped, the log is kept ordered by date.
+    // The files on disk are rewritten and the number of payouts actually added is returned.
+    pub fn merge_payouts(
+        &mut self,
+        payouts: Vec<(String, AtomicUnit, HumanNumber)>,
+    ) -> Result<usize, TomlError> {
+        let mut blocks: Vec<String> = self
+            .log
+            .lines()
+            .map(|line| PayoutOrd::parse_formatted_payout_line(line).2.to_string())
+            .collect();
+        let mut lines: Vec<String> = self.log.lines().map(|line| line.to_string()).collect();
+        let mut added = 0;
+        for (date, atomic_unit, block) in payouts {
+            if blocks.contains(&block.to_string()) {
+                debug!(
+                    "GupaxP2poolApi | Payout of block {} already known, skipping",
+                    block
+                );
+                continue;
+            }
+            lines.push(Self::format_payout(&date, &atomic_unit, &block));
+            blocks.push(block.to_string());
+            self.payout_u64 += 1;
+            self.xmr = self.xmr.add_self(atomic_unit);
+            added += 1;
+        }
+        if added == 0 {
+            return Ok(0);
+        }
+        // The date is at the start of each line, so ordering the lines orders the payouts.
+        lines.sort();
+        let mut log = String::with_capacity(self.log.len() + 70 * added);
+        for line in lines {
+            log.push_str(&line);
+            log.push('\n');
+        }
+        self.log = log;
+        self.payout = HumanNumber::from_u64(self.payout_u64);
+        self.payout_ord.update_from_payout_log(&self.log);
+        self.update_payout_strings();
+        self.update_log_rev();
+        Self::disk_overwrite(&self.payout_u64.to_string(), &self.path_payout)?;
+        Self::disk_overwrite(&self.xmr.to_string(), &self.path_xmr)?;
+        if let Err(e) = fs::write(&self.path_log, &self.log) {
+            error!(
+                "GupaxP2poolApi | Overwrite [{}] ... FAIL: {}",
+                self.path_log.display(),
+                e
+            );
+            return Err(TomlError::Io(e));
+        }
+        info!("GupaxP2poolApi | Merged [{}] new payouts", added);
+        Ok(added)
+    }
+
     pub fn write_to_all_files(&self, formatted_log_line: &str) -> Result<(), TomlError> {
         Self::disk_overwrite(&self.payout_u64.to_string(), &self.path_payout)?;
         Self::disk_overwrite(&self.xmr.to_string(), &self.path_xmr)?;
@@ -50,10 +50,13 @@ use std::{
 
 use self::errors::TomlError;
 
+pub mod bench_runs;
 pub mod consts;
+pub mod discovered_nodes;
 pub mod errors;
 pub mod gupax_p2pool_api;
 pub mod node;
+pub mod node_scores;
 pub mod pool;
 pub mod state;
 pub mod status;
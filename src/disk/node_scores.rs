@@ -0,0 +1,86 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Health scores of the remote nodes, kept across sessions in [node_scores.toml].
+// Each ping adds a score (0-100) to the history of every remote node.
+
+use crate::disk::*;
+use std::collections::{BTreeMap, VecDeque};
+
+// Number of scores kept by node.
+pub const NODE_SCORES_HISTORY: usize = 5;
+// A node is bad if its last [NODE_SCORES_BAD_COUNT] scores are all under [NODE_SCORE_BAD].
+pub const NODE_SCORES_BAD_COUNT: usize = 3;
+pub const NODE_SCORE_BAD: u8 = 40;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NodeScores {
+    // ip -> last scores, oldest first
+    pub nodes: BTreeMap<String, VecDeque<u8>>,
+}
+
+impl NodeScores {
+    // Read the scores, a missing or invalid file gives empty scores.
+    pub fn get(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(string) => match toml::from_str(&string) {
+                Ok(scores) => {
+                    info!("NodeScores | Read ... OK");
+                    scores
+                }
+                Err(e) => {
+                    warn!("NodeScores | Parse ... FAIL ... {}", e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), TomlError> {
+        let string = toml::to_string(self).map_err(TomlError::Serialize)?;
+        fs::write(path, string)?;
+        info!("NodeScores | Save ... OK");
+        Ok(())
+    }
+
+    pub fn push(&mut self, ip: &str, score: u8) {
+        let scores = self.nodes.entry(ip.to_string()).or_default();
+        scores.push_back(score);
+        while scores.len() > NODE_SCORES_HISTORY {
+            scores.pop_front();
+        }
+    }
+
+    // Average of the remembered scores.
+    pub fn average(&self, ip: &str) -> Option<u8> {
+        let scores = self.nodes.get(ip).filter(|s| !s.is_empty())?;
+        Some((scores.iter().map(|s| *s as u32).sum::<u32>() / scores.len() as u32) as u8)
+    }
+
+    // Consistently bad, one bad ping is not enough.
+    pub fn is_bad(&self, ip: &str) -> bool {
+        self.nodes.get(ip).is_some_and(|scores| {
+            scores.len() >= NODE_SCORES_BAD_COUNT
+                && scores
+                    .iter()
+                    .rev()
+                    .take(NODE_SCORES_BAD_COUNT)
+                    .all(|s| *s < NODE_SCORE_BAD)
+        })
+    }
+}
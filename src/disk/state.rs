@@ -8,7 +8,16 @@ use crate::{
     app::panels::middle::common::list_poolnode::PoolNode,
     components::node::RemoteNode,
     disk::status::*,
-    helper::{Helper, ProcessName, node::ImgNode, p2pool::ImgP2pool, xrig::xmrig_proxy::ImgProxy},
+    helper::{
+        Helper, ProcessName,
+        node::ImgNode,
+        p2pool::ImgP2pool,
+        xrig::{config::XMRIG_PRIORITY_DEFAULT, xmrig_proxy::ImgProxy},
+    },
+    utils::{
+        args::Args,
+        regex::{REGEXES, Regexes},
+    },
 };
 //---------------------------------------------------------------------------------------------------- [State] Impl
 impl Default for State {
@@ -180,6 +189,10 @@ pub struct Status {
     pub manual_hash: bool,
     pub hashrate: f64,
     pub hash_metric: Hash,
+    pub bench_size: BenchSize,
+    pub bench_mode: BenchMode,
+    // 0 until the Benchmarks submenu sets it to the number of threads of the CPU
+    pub bench_threads: u16,
 }
 
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
@@ -201,6 +214,18 @@ pub struct Gupax {
     pub tab: Tab,
     pub ratio: Ratio,
     pub show_processes: Vec<ProcessName>,
+    pub proxy: ProxyOptions,
+}
+
+// SOCKS5 proxy (like Tor) used by the processes and the connections of Gupaxx.
+// The local processes and APIs are never reached through it.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ProxyOptions {
+    pub enabled: bool,
+    // [IP:PORT], monerod does not accept a domain.
+    pub address: String,
+    // Broadcast the transactions of the node over Tor.
+    pub tor: bool,
 }
 
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
@@ -311,6 +336,62 @@ pub struct P2pool {
     pub selected_node: SelectedPoolNode,
     pub prefer_local_node: bool,
     pub console_height: u32,
+    // chains merge mined with P2Pool, the first one is the Tari preset, the only one used in simple mode
+    pub merge_mine: Vec<MergeMine>,
+    pub pinned_peers: Vec<String>,
+    pub banned_peers: Vec<String>,
+    pub seed_nano_peers: bool,
+    // [host:rpc] or [host:rpc:zmq] probed by the node discovery, separated by commas
+    pub discovery_seeds: String,
+    pub options: P2poolOptions,
+}
+
+// Options of P2Pool used in Advanced mode, on top of the ones Gupaxx always gives.
+// [--no-igd] is an alias of [--no-upnp] and [--stratum-api] an alias of [--local-api],
+// which Gupaxx always enables to read the statistics, so they don't have their own field.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct P2poolOptions {
+    pub light_mode: bool,
+    pub no_randomx: bool,
+    pub no_upnp: bool,
+    pub onion_address: String,
+    pub socks5: String,
+}
+
+// A chain merge mined by P2Pool, given with [--merge-mine host wallet].
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct MergeMine {
+    pub enabled: bool,
+    // node of the chain, with its scheme if P2Pool needs one, like [tari://127.0.0.1:18102]
+    pub host: String,
+    pub address: String,
+}
+
+impl MergeMine {
+    /// The preset entry, a local Tari node.
+    pub fn tari() -> Self {
+        Self {
+            enabled: false,
+            host: P2POOL_MERGE_MINING_HOST_DEFAULT.to_string(),
+            address: String::new(),
+        }
+    }
+    pub fn is_tari(&self) -> bool {
+        self.host.starts_with("tari://")
+    }
+    /// [host:port] with an optional scheme, like [tari://127.0.0.1:18102].
+    pub fn host_ok(host: &str) -> bool {
+        let host = host.split_once("://").map_or(host, |(_, h)| h);
+        Regexes::host_port_ok(host)
+    }
+    /// A Tari wallet must be a Base58 Tari address, the format of the other chains is not known.
+    pub fn address_ok(&self) -> bool {
+        if self.is_tari() {
+            Regexes::tari_addr_ok(&self.address)
+        } else {
+            !self.address.trim().is_empty()
+        }
+    }
 }
 
 // compatible for P2Pool and Xmrig/Proxy
@@ -340,6 +421,28 @@ pub struct Node {
     pub path_db: String,
     pub full_memory: bool,
     pub console_height: u32,
+    pub ban_list: bool,
+    pub ban_list_path: String,
+    // Simple mode: answer the RPC requests with the fastest remote node while syncing
+    pub bootstrap: bool,
+    pub options: NodeOptions,
+}
+
+// RPC and bandwidth options of monerod used in Advanced mode, to share the node with others.
+// A limit of 0 keeps the default rate of monerod.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct NodeOptions {
+    pub restricted_rpc: bool,
+    pub public_node: bool,
+    pub restricted_port: String,
+    pub confirm_external_bind: bool,
+    pub limit_rate_up: u32,
+    pub limit_rate_down: u32,
+    pub rpc_login: String,
+    // remote node answering the RPC requests while syncing, host:port or "auto"
+    pub bootstrap_address: String,
+    pub bootstrap_login: String,
+    pub bootstrap_proxy: String,
 }
 
 impl Default for Node {
@@ -360,6 +463,10 @@ impl Default for Node {
             path_db: String::new(),
             full_memory: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            ban_list: true,
+            ban_list_path: String::new(),
+            bootstrap: false,
+            options: NodeOptions::default(),
         }
     }
 }
@@ -372,8 +479,15 @@ pub struct Xmrig {
     pub arguments: String,
     pub tls: bool,
     pub keepalive: bool,
+    // the pool of the advanced mode gives each miner its own nonce range, as NiceHash does
+    pub nicehash: bool,
     pub max_threads: u16,
     pub current_threads: u16,
+    // process priority, 0 (idle) to 5 (highest)
+    pub priority: u16,
+    pub huge_pages: bool,
+    // logical CPUs the threads are pinned to, node by node. Empty lets XMRig choose.
+    pub affinity: Vec<u16>,
     pub address: String,
     pub api_ip: String,
     pub api_port: String,
@@ -384,6 +498,116 @@ pub struct Xmrig {
     pub selected_pool: SelectedPoolNode,
     pub token: String,
     pub console_height: u32,
+    pub privilege: XmrigPrivilege,
+    // 1GB pages for the RandomX dataset, enabled by the setup if the CPU supports them
+    pub pages_1gb: bool,
+    pub idle: IdlePolicy,
+    pub thermal: ThermalPolicy,
+    // pools of the pool list tried in order when the pool above fails, advanced mode only
+    pub failover: Vec<FailoverPool>,
+}
+
+// A pool of the pool list in the failover chain of XMRig, with its own connection flags.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct FailoverPool {
+    pub name: String,
+    pub ip: String,
+    pub port: String,
+    pub rig: String,
+    pub tls: bool,
+    pub keepalive: bool,
+    // the pool gives each miner its own nonce range, as NiceHash does
+    pub nicehash: bool,
+}
+
+impl FailoverPool {
+    /// [ip:port] as given to XMRig, it doesn't understand [localhost].
+    pub fn url(&self) -> String {
+        Self::url_of(&self.ip, &self.port)
+    }
+    pub fn url_of(ip: &str, port: &str) -> String {
+        let ip = if ip == "localhost" || ip.is_empty() {
+            "127.0.0.1"
+        } else {
+            ip
+        };
+        format!("{}:{}", ip, port)
+    }
+}
+
+// Thermal guard, XMRig is throttled or paused while the CPU is too hot.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ThermalPolicy {
+    pub enabled: bool,
+    // °C
+    pub ceiling: u16,
+    // °C under the ceiling to cool down before mining again with all the threads
+    pub hysteresis: u16,
+    // threads kept while the CPU is too hot, 0 pauses XMRig
+    pub hot_threads: u16,
+}
+
+impl Default for ThermalPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ceiling: 85,
+            hysteresis: 10,
+            hot_threads: 0,
+        }
+    }
+}
+
+// Idle-only mining, XMRig is paused or throttled while the computer is in use.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct IdlePolicy {
+    pub enabled: bool,
+    // % of the CPU used by other programs above which the computer is in use
+    pub max_load: u16,
+    // programs that make the computer in use while they run, separated by commas
+    pub processes: String,
+    pub pause_on_battery: bool,
+    // threads kept while the computer is in use, 0 pauses XMRig
+    pub busy_threads: u16,
+    // seconds the computer must stay idle before mining again
+    pub resume_after: u16,
+}
+
+impl Default for IdlePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_load: 30,
+            processes: String::new(),
+            pause_on_battery: true,
+            busy_threads: 0,
+            resume_after: 60,
+        }
+    }
+}
+
+// How XMRig gets the privileges needed for the MSR mod and the hugepages, on Linux and macOS.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, Default)]
+pub enum XmrigPrivilege {
+    // the password of sudo is asked on each start
+    #[default]
+    Sudo,
+    // sudo does not ask a password, configured in sudoers
+    SudoNoPassword,
+    // hugepages, msr module and capabilities set once, XMRig is started without sudo
+    Capabilities,
+}
+
+impl Display for XmrigPrivilege {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Sudo => "sudo with password",
+            Self::SudoNoPassword => "sudo without password",
+            Self::Capabilities => "without sudo",
+        };
+
+        write!(f, "{}", text)
+    }
 }
 
 // present for future.
@@ -406,6 +630,8 @@ pub struct XmrigProxy {
     pub selected_pool: SelectedPoolNode,
     pub token: String,
     pub redirect_local_xmrig: bool,
+    // minutes without a share before alerting about a worker, 0 to disable
+    pub worker_silent_minutes: u16,
     pub console_height: u32,
 }
 
@@ -450,6 +676,7 @@ impl Default for XmrigProxy {
                 .map(char::from)
                 .collect(),
             redirect_local_xmrig: true,
+            worker_silent_minutes: 10,
             address: String::with_capacity(96),
             name: "Local P2Pool".to_string(),
             rig: GUPAX_VERSION_UNDERSCORE.to_string(),
@@ -589,6 +816,9 @@ impl Default for Status {
             manual_hash: false,
             hashrate: 1.0,
             hash_metric: Hash::default(),
+            bench_size: BenchSize::default(),
+            bench_mode: BenchMode::default(),
+            bench_threads: 0,
         }
     }
 }
@@ -613,6 +843,17 @@ impl Default for Gupax {
             ratio: Ratio::Width,
             tab: Tab::Xvb,
             show_processes: ProcessName::having_tab(),
+            proxy: ProxyOptions::default(),
+        }
+    }
+}
+
+impl Default for ProxyOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1:9050".to_string(),
+            tor: true,
         }
     }
 }
@@ -646,6 +887,24 @@ impl Default for P2pool {
             },
             prefer_local_node: true,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            merge_mine: vec![MergeMine::tari()],
+            pinned_peers: vec![],
+            banned_peers: vec![],
+            seed_nano_peers: true,
+            discovery_seeds: String::new(),
+            options: P2poolOptions::default(),
+        }
+    }
+}
+
+impl Default for P2poolOptions {
+    fn default() -> Self {
+        Self {
+            light_mode: true,
+            no_randomx: false,
+            no_upnp: false,
+            onion_address: String::new(),
+            socks5: String::new(),
         }
     }
 }
@@ -683,8 +942,12 @@ impl Default for Xmrig {
             api_port: XMRIG_API_PORT_DEFAULT.to_string(),
             tls: false,
             keepalive: false,
+            nicehash: false,
             current_threads: 1,
             max_threads: 1,
+            priority: XMRIG_PRIORITY_DEFAULT,
+            huge_pages: true,
+            affinity: vec![],
             selected_pool: SelectedPoolNode {
                 index: 0,
                 name: "Local Monero Node".to_string(),
@@ -698,6 +961,11 @@ impl Default for Xmrig {
                 .map(char::from)
                 .collect(),
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            privilege: XmrigPrivilege::default(),
+            pages_1gb: false,
+            idle: IdlePolicy::default(),
+            thermal: ThermalPolicy::default(),
+            failover: vec![],
         }
     }
 }
@@ -738,7 +1006,47 @@ impl Node {
         ProcessName::Node
     }
     pub fn start_options(&self, mode: StartOptionsMode) -> String {
-        Helper::build_node_args(self, mode).join(" ")
+        let proxy = self.proxy_args(&mode);
+        let bootstrap = self.bootstrap_args(&mode, None);
+        let mut args = Helper::build_node_args(self, mode);
+        args.extend(bootstrap);
+        args.extend(proxy);
+        args.join(" ")
+    }
+    /// arguments of the proxy of the Gupaxx tab, custom arguments are left as they are.
+    /// The bootstrap daemon goes through it too, unless it has its own proxy.
+    pub fn proxy_args(&self, mode: &StartOptionsMode) -> Vec<String> {
+        match crate::miscs::proxy() {
+            Some(proxy) if !matches!(mode, StartOptionsMode::Custom) => {
+                let mut args = proxy.node_args();
+                let bootstrap = match mode {
+                    StartOptionsMode::Simple => self.bootstrap,
+                    _ => {
+                        !self.options.bootstrap_address.is_empty()
+                            && self.options.bootstrap_proxy.is_empty()
+                    }
+                };
+                if bootstrap {
+                    args.push("--bootstrap-daemon-proxy".to_string());
+                    args.push(proxy.address);
+                }
+                args
+            }
+            _ => vec![],
+        }
+    }
+    /// bootstrap daemon of the Simple mode if the user opted in, the fastest remote node of a recent ping.
+    /// monerod picks a public node itself with "auto" if no recent ping was done.
+    pub fn bootstrap_args(&self, mode: &StartOptionsMode, fastest: Option<String>) -> Vec<String> {
+        if matches!(mode, StartOptionsMode::Simple) && self.bootstrap {
+            let address = fastest.unwrap_or_else(|| {
+                info!("Node | No recent ping, monerod picks the bootstrap daemon itself");
+                "auto".to_string()
+            });
+            vec!["--bootstrap-daemon-address".to_string(), address]
+        } else {
+            vec![]
+        }
     }
     /// Return rpc port, zmq port from state
     pub fn ports(&self) -> (u16, u16) {
@@ -748,24 +1056,11 @@ impl Node {
             zmq_port = NODE_ZMQ_PORT_DEFAULT;
             rpc_port = NODE_RPC_PORT_DEFAULT;
         } else if !self.arguments.is_empty() {
-            // This parses the input and attempts to fill out
-            // the [ImgXmrig]... This is pretty bad code...
-            let mut last = "";
-            for arg in self.arguments.split_whitespace() {
-                match last {
-                    "--zmq-pub" => {
-                        zmq_port = last
-                            .split(":")
-                            .last()
-                            .unwrap_or(&NODE_ZMQ_PORT_DEFAULT.to_string())
-                            .parse()
-                            .unwrap_or(NODE_ZMQ_PORT_DEFAULT);
-                    }
-                    "--rpc-bind-port" => zmq_port = last.parse().unwrap_or(NODE_RPC_PORT_DEFAULT),
-                    _ => (),
-                }
-                last = arg;
-            }
+            let args = Args::parse(&self.arguments);
+            zmq_port = args.port(&["--zmq-pub"]).unwrap_or(NODE_ZMQ_PORT_DEFAULT);
+            rpc_port = args
+                .port(&["--rpc-bind-port"])
+                .unwrap_or(NODE_RPC_PORT_DEFAULT);
         } else {
             zmq_port = if self.api_port.is_empty() {
                 NODE_ZMQ_PORT_DEFAULT
@@ -780,10 +1075,173 @@ impl Node {
         }
         (rpc_port, zmq_port)
     }
+    /// address of the RPC of the local node, reachable from Gupaxx
+    pub fn rpc_address(&self) -> String {
+        let ip = if self.simple || self.api_ip == "0.0.0.0" || self.api_ip == "localhost" {
+            "127.0.0.1"
+        } else {
+            &self.api_ip
+        };
+        format!("{}:{}", ip, self.ports().0)
+    }
+    /// check the RPC options before starting the node in Advanced mode, empty fields are not used.
+    pub fn validate_options(&self) -> Result<(), &'static str> {
+        let options = &self.options;
+        if !options.restricted_port.is_empty()
+            && (!REGEXES.port.is_match(&options.restricted_port)
+                || options.restricted_port == self.api_port
+                || options.restricted_port == self.zmq_port)
+        {
+            return Err(NODE_RESTRICTED_PORT);
+        }
+        // monerod refuses to advertise a RPC that is not restricted.
+        if options.public_node && !options.restricted_rpc && options.restricted_port.is_empty() {
+            return Err(NODE_PUBLIC_NODE);
+        }
+        // monerod refuses to bind an unrestricted RPC outside of localhost without confirmation.
+        let local = self.api_ip == "127.0.0.1" || self.api_ip == "localhost";
+        if !local && !options.restricted_rpc && !options.confirm_external_bind {
+            return Err(NODE_CONFIRM_EXTERNAL_BIND);
+        }
+        if !options.rpc_login.is_empty() && !Self::rpc_login_ok(&options.rpc_login) {
+            return Err(NODE_RPC_LOGIN);
+        }
+        if !options.bootstrap_address.is_empty()
+            && !Self::bootstrap_address_ok(&options.bootstrap_address)
+        {
+            return Err(NODE_BOOTSTRAP_ADDRESS);
+        }
+        // the login and proxy are useless without a bootstrap daemon.
+        if options.bootstrap_address.is_empty()
+            && (!options.bootstrap_login.is_empty() || !options.bootstrap_proxy.is_empty())
+        {
+            return Err(NODE_BOOTSTRAP_ADDRESS);
+        }
+        if !options.bootstrap_login.is_empty() && !Self::rpc_login_ok(&options.bootstrap_login) {
+            return Err(NODE_BOOTSTRAP_LOGIN);
+        }
+        if !options.bootstrap_proxy.is_empty()
+            && !ProxyOptions::address_ok(&options.bootstrap_proxy)
+        {
+            return Err(NODE_BOOTSTRAP_PROXY);
+        }
+        Ok(())
+    }
+    /// [host:port] of a remote node, or [auto] to let monerod pick a public node.
+    pub fn bootstrap_address_ok(address: &str) -> bool {
+        address == "auto"
+            || address.rsplit_once(':').is_some_and(|(host, port)| {
+                (REGEXES.ipv4.is_match(host)
+                    || REGEXES.domain.is_match(host)
+                    || REGEXES.onion.is_match(host))
+                    && REGEXES.port.is_match(port)
+            })
+    }
+    /// [user:password], without the password monerod would ask for it on the terminal.
+    pub fn rpc_login_ok(login: &str) -> bool {
+        login.split_once(':').is_some_and(|(user, password)| {
+            !user.is_empty() && !password.is_empty() && !login.contains(char::is_whitespace)
+        })
+    }
+    /// port of the RPC that can be shared with others, the restricted one if there is one.
+    /// [None] if the RPC only listens on localhost.
+    pub fn public_rpc_port(&self) -> Option<u16> {
+        if self.simple {
+            return None;
+        }
+        if let Ok(port) = self.options.restricted_port.parse() {
+            return Some(port);
+        }
+        let localhost = ["127.0.0.1", "localhost", "::1"].contains(&self.api_ip.as_str());
+        (!localhost).then(|| self.ports().0)
+    }
+    /// [user:password] protecting the RPC with the current start options, used by P2Pool and Gupaxx to reach it.
+    pub fn rpc_login(&self) -> Option<&str> {
+        let login = if self.simple {
+            None
+        } else if !self.arguments.is_empty() {
+            Args::parse(&self.arguments).value(&["--rpc-login"])
+        } else {
+            Some(self.options.rpc_login.as_str())
+        };
+        login.filter(|l| !l.is_empty())
+    }
+    /// is the blockchain pruned with the current start options.
+    pub fn pruned_db(&self) -> bool {
+        if self.simple {
+            true
+        } else if !self.arguments.is_empty() {
+            Args::parse(&self.arguments).has(&["--prune-blockchain"])
+        } else {
+            self.pruned
+        }
+    }
+    /// data directory used by monerod with the current start options.
+    /// Relative paths are resolved from the directory of monerod, since it is started from there.
+    pub fn data_dir(&self, node_path: &Path) -> Option<PathBuf> {
+        let dir = if self.simple {
+            None
+        } else if !self.arguments.is_empty() {
+            Args::parse(&self.arguments)
+                .value(&["--data-dir"])
+                .map(PathBuf::from)
+        } else if self.path_db.is_empty() {
+            Some(PathBuf::from(".bitmonero"))
+        } else {
+            Some(PathBuf::from(&self.path_db))
+        };
+        // default directory of monerod when [--data-dir] is not given.
+        #[cfg(target_os = "windows")]
+        let dir = dir.unwrap_or_else(|| PathBuf::from(r#"C:\ProgramData\bitmonero"#));
+        #[cfg(not(target_os = "windows"))]
+        let dir = match dir {
+            Some(dir) => dir,
+            None => dirs::home_dir()?.join(".bitmonero"),
+        };
+        if dir.is_relative() {
+            node_path.parent().map(|parent| parent.join(dir))
+        } else {
+            Some(dir)
+        }
+    }
+    /// ban list given to monerod, the one of the user or else the one bundled with Gupaxx.
+    pub fn ban_list_file(&self) -> Option<PathBuf> {
+        if !self.ban_list {
+            None
+        } else if !self.ban_list_path.is_empty() {
+            Some(PathBuf::from(&self.ban_list_path))
+        } else {
+            dirs::data_dir().map(|path| path.join(DIRECTORY).join(BAN_LIST_TXT))
+        }
+    }
+    /// number of IPs/subnets in a ban list, empty lines are ignored like monerod does.
+    pub fn ban_list_entries(path: &Path) -> std::io::Result<usize> {
+        Ok(std::fs::read_to_string(path)?
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .count())
+    }
+    /// install the ban list bundled with Gupaxx in the data directory.
+    /// It is replaced at each start so that updates of Gupaxx bring the new list.
+    pub fn install_ban_list(os_data_path: &Path) {
+        let path = os_data_path.join(BAN_LIST_TXT);
+        if std::fs::read_to_string(&path).is_ok_and(|list| list == NODE_BAN_LIST) {
+            return;
+        }
+        match std::fs::write(&path, NODE_BAN_LIST) {
+            Ok(_) => info!("Node | Install ban list [{}] ... OK", path.display()),
+            Err(e) => error!(
+                "Node | Install ban list [{}] ... FAIL ... {}",
+                path.display(),
+                e
+            ),
+        }
+    }
     /// get the ports that the node process is currently using or that it will use if started with current settings
+    /// return (rpc port, zmq port)
     pub fn current_ports(&self, alive: bool, img_node: &ImgNode) -> (u16, u16) {
         if alive {
-            (img_node.zmq_port, img_node.rpc_port)
+            (img_node.rpc_port, img_node.zmq_port)
         } else {
             self.ports()
         }
@@ -801,40 +1259,139 @@ impl P2pool {
         local_node_zmq_port: u16,
         local_node_rpc_port: u16,
     ) -> String {
-        Helper::build_p2pool_args(
+        let proxy = self.proxy_args(&mode);
+        let mut args = Helper::build_p2pool_args(
             self,
             path,
             backup_nodes,
             false,
-            local_node_rpc_port,
             local_node_zmq_port,
+            local_node_rpc_port,
             mode,
-        )
-        .join(" ")
+        );
+        args.extend(proxy);
+        args.join(" ")
+    }
+    /// arguments of the proxy of the Gupaxx tab, unless a SOCKS5 proxy is set in the P2Pool options.
+    pub fn proxy_args(&self, mode: &StartOptionsMode) -> Vec<String> {
+        match crate::miscs::proxy() {
+            Some(proxy)
+                if matches!(mode, StartOptionsMode::Simple)
+                    || (matches!(mode, StartOptionsMode::Advanced)
+                        && self.options.socks5.is_empty()) =>
+            {
+                proxy.p2pool_args()
+            }
+            _ => vec![],
+        }
     }
     /// get the port that the p2pool process would use for stratum if it were using the current settings
     pub fn stratum_port(&self) -> u16 {
         if self.simple {
             P2POOL_PORT_DEFAULT
         } else if !self.arguments.is_empty() {
-            let mut last = "";
-            for arg in self.arguments.split_whitespace() {
-                if last == "--stratum" {
-                    return last
-                        .split(":")
-                        .last()
-                        .unwrap_or(&P2POOL_PORT_DEFAULT.to_string())
-                        .parse()
-                        .unwrap_or(P2POOL_PORT_DEFAULT);
-                }
-                last = arg;
-            }
-            return P2POOL_PORT_DEFAULT;
+            Args::parse(&self.arguments)
+                .port(&["--stratum"])
+                .unwrap_or(P2POOL_PORT_DEFAULT)
         } else {
-            return self.stratum_port;
+            self.stratum_port
         }
     }
 
+    /// The Tari preset, the first entry of the merge mining list.
+    pub fn tari(&mut self) -> &mut MergeMine {
+        if self.merge_mine.is_empty() {
+            self.merge_mine.push(MergeMine::tari());
+        }
+        &mut self.merge_mine[0]
+    }
+    /// enabled chains used in this mode, simple mode only has the Tari preset.
+    fn merge_mine_used(&self, mode: &StartOptionsMode) -> impl Iterator<Item = &MergeMine> {
+        let shown = match mode {
+            StartOptionsMode::Simple => 1,
+            _ => self.merge_mine.len(),
+        };
+        self.merge_mine.iter().take(shown).filter(|m| m.enabled)
+    }
+    /// check the enabled merge mining chains before starting P2Pool.
+    pub fn validate_merge_mining(&self) -> Result<(), &'static str> {
+        let mode = if self.simple {
+            StartOptionsMode::Simple
+        } else {
+            StartOptionsMode::Advanced
+        };
+        let mut hosts = vec![];
+        for chain in self.merge_mine_used(&mode) {
+            if !MergeMine::host_ok(&chain.host) {
+                return Err(P2POOL_MERGE_MINING_HOST);
+            }
+            if !chain.address_ok() {
+                return Err(P2POOL_MERGE_MINING_ADDRESS);
+            }
+            if hosts.contains(&&chain.host) {
+                return Err(P2POOL_MERGE_MINING_DUPLICATE);
+            }
+            hosts.push(&chain.host);
+        }
+        Ok(())
+    }
+    /// arguments given to P2Pool to merge mine, one [--merge-mine] by host.
+    pub fn merge_mining_args(&self, mode: &StartOptionsMode) -> Vec<String> {
+        let mut hosts = vec![];
+        let mut args = vec![];
+        for chain in self.merge_mine_used(mode) {
+            if hosts.contains(&&chain.host) {
+                continue;
+            }
+            hosts.push(&chain.host);
+            args.push("--merge-mine".to_string());
+            args.push(chain.host.clone());
+            args.push(chain.address.clone());
+        }
+        args
+    }
+    /// arguments given to P2Pool to connect to the pinned peers, the peers not to add are never given.
+    pub fn addpeers_args(&self) -> Vec<String> {
+        let peers: Vec<&str> = self
+            .pinned_peers
+            .iter()
+            .filter(|p| !self.banned_peers.contains(p))
+            .map(|p| p.as_str())
+            .collect();
+        if peers.is_empty() {
+            return vec![];
+        }
+        vec!["--addpeers".to_string(), peers.join(",")]
+    }
+    /// the bundled peer list of the nano chain, without the peers not to add.
+    pub fn nano_peers(&self) -> String {
+        P2POOL_NANO_PEER_LIST
+            .lines()
+            .filter(|p| !self.banned_peers.iter().any(|b| b == p))
+            .fold(String::new(), |mut list, p| {
+                list.push_str(p);
+                list.push('\n');
+                list
+            })
+    }
+    /// pin a peer, or unpin it if it was already. A pinned peer is always added.
+    pub fn toggle_pinned_peer(&mut self, peer: &str) {
+        if let Some(i) = self.pinned_peers.iter().position(|p| p == peer) {
+            self.pinned_peers.remove(i);
+        } else {
+            self.banned_peers.retain(|p| p != peer);
+            self.pinned_peers.push(peer.to_string());
+        }
+    }
+    /// never add a peer, or add it again if it was already. It is not a ban, P2Pool can still find the peer.
+    pub fn toggle_banned_peer(&mut self, peer: &str) {
+        if let Some(i) = self.banned_peers.iter().position(|p| p == peer) {
+            self.banned_peers.remove(i);
+        } else {
+            self.pinned_peers.retain(|p| p != peer);
+            self.banned_peers.push(peer.to_string());
+        }
+    }
     /// get the ports that the node process is currently using or that it will use if started with current settings
     pub fn current_port(&self, alive: bool, img_p2pool: &ImgP2pool) -> u16 {
         if alive {
@@ -844,13 +1401,194 @@ impl P2pool {
         }
     }
 }
+
+impl P2poolOptions {
+    /// check the options before starting P2Pool, empty fields are not used.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !self.onion_address.is_empty() && !REGEXES.onion.is_match(&self.onion_address) {
+            return Err(P2POOL_ONION_ADDRESS);
+        }
+        if !self.socks5.is_empty() && !Regexes::host_port_ok(&self.socks5) {
+            return Err(P2POOL_SOCKS5);
+        }
+        Ok(())
+    }
+    /// arguments given to P2Pool for these options
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.light_mode {
+            args.push("--light-mode".to_string()); // Assume user is not using P2Pool to mine.
+        }
+        if self.no_randomx {
+            args.push("--no-randomx".to_string());
+        }
+        if self.no_upnp {
+            args.push("--no-upnp".to_string());
+        }
+        if !self.onion_address.is_empty() {
+            args.push("--onion-address".to_string());
+            args.push(self.onion_address.clone());
+        }
+        if !self.socks5.is_empty() {
+            args.push("--socks5".to_string());
+            args.push(self.socks5.clone());
+        }
+        args
+    }
+}
+impl ProxyOptions {
+    pub fn address_ok(address: &str) -> bool {
+        match address.rsplit_once(':') {
+            Some((ip, port)) => REGEXES.ipv4.is_match(ip) && REGEXES.port.is_match(port),
+            None => false,
+        }
+    }
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.enabled && !Self::address_ok(&self.address) {
+            return Err(GUPAX_PROXY_ADDRESS);
+        }
+        Ok(())
+    }
+    /// a host only reachable through the proxy.
+    pub fn is_onion(host: &str) -> bool {
+        host.ends_with(".onion")
+    }
+    /// a host reached directly even if the proxy is enabled.
+    pub fn is_local(host: &str) -> bool {
+        host.is_empty() || host == "localhost" || host.starts_with("127.") || host == "::1"
+    }
+    /// arguments given to monerod, all the P2P connections go through the proxy.
+    pub fn node_args(&self) -> Vec<String> {
+        let mut args = vec!["--proxy".to_string(), self.address.clone()];
+        if self.tor {
+            args.push("--tx-proxy".to_string());
+            args.push(format!("tor,{},16", self.address));
+        }
+        args
+    }
+    /// arguments given to P2Pool, for its P2P connections and a remote node.
+    pub fn p2pool_args(&self) -> Vec<String> {
+        vec!["--socks5".to_string(), self.address.clone()]
+    }
+    /// arguments given to XMRig for the pool set before them.
+    pub fn xmrig_args(&self) -> Vec<String> {
+        vec!["--proxy".to_string(), self.address.clone()]
+    }
+}
+impl NodeOptions {
+    /// arguments given to monerod for these options
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.restricted_rpc {
+            args.push("--restricted-rpc".to_string());
+        }
+        if !self.restricted_port.is_empty() {
+            // the restricted RPC is made to be reached by others.
+            args.push("--rpc-restricted-bind-ip".to_string());
+            args.push("0.0.0.0".to_string());
+            args.push("--rpc-restricted-bind-port".to_string());
+            args.push(self.restricted_port.clone());
+        }
+        if self.public_node {
+            args.push("--public-node".to_string());
+        }
+        if self.confirm_external_bind {
+            args.push("--confirm-external-bind".to_string());
+        }
+        if self.limit_rate_up > 0 {
+            args.push("--limit-rate-up".to_string());
+            args.push(self.limit_rate_up.to_string());
+        }
+        if self.limit_rate_down > 0 {
+            args.push("--limit-rate-down".to_string());
+            args.push(self.limit_rate_down.to_string());
+        }
+        if !self.rpc_login.is_empty() {
+            args.push("--rpc-login".to_string());
+            args.push(self.rpc_login.clone());
+        }
+        if !self.bootstrap_address.is_empty() {
+            args.push("--bootstrap-daemon-address".to_string());
+            args.push(self.bootstrap_address.clone());
+            if !self.bootstrap_login.is_empty() {
+                args.push("--bootstrap-daemon-login".to_string());
+                args.push(self.bootstrap_login.clone());
+            }
+            if !self.bootstrap_proxy.is_empty() {
+                args.push("--bootstrap-daemon-proxy".to_string());
+                args.push(self.bootstrap_proxy.clone());
+            }
+        }
+        args
+    }
+}
 impl Xmrig {
     pub const fn process_name() -> ProcessName {
         ProcessName::Xmrig
     }
+    /// CPUs the threads are pinned to, only in advanced mode.
+    pub fn pinned(&self) -> &[u16] {
+        if self.simple { &[] } else { &self.affinity }
+    }
+    /// Number of threads, one by pinned CPU if there are any.
+    pub fn threads(&self) -> u16 {
+        match self.pinned().len() {
+            0 => self.current_threads,
+            n => n as u16,
+        }
+    }
     pub fn start_options(&self, mode: StartOptionsMode, p2pool_stratum_port: u16) -> String {
         Helper::build_xmrig_args(self, mode, p2pool_stratum_port).join(" ")
     }
+    /// arguments of the proxy of the Gupaxx tab for a pool of the advanced mode.
+    /// XMRig applies them to the [--url] before, simple mode mines on the local P2Pool, reached directly.
+    pub fn proxy_args(ip: &str) -> Vec<String> {
+        match crate::miscs::proxy() {
+            Some(proxy) if !ProxyOptions::is_local(ip) => proxy.xmrig_args(),
+            _ => vec![],
+        }
+    }
+    /// The pool of the advanced mode followed by the failover pools, in the order given to XMRig.
+    pub fn pool_chain(&self) -> Vec<FailoverPool> {
+        let pool = FailoverPool {
+            name: self.name.clone(),
+            ip: self.ip.clone(),
+            port: self.port.clone(),
+            rig: self.rig.clone(),
+            tls: self.tls,
+            keepalive: self.keepalive,
+            nicehash: self.nicehash,
+        };
+        std::iter::once(pool)
+            .chain(self.failover.iter().cloned())
+            .collect()
+    }
+    /// The failover pools are found again by name in the pool list, so a pool edited there is not left with its old address.
+    /// A pool already in the chain is removed, XMRig would only try it twice.
+    pub fn sync_failover(&mut self, pools: &[(String, PoolNode)]) {
+        for failover in &mut self.failover {
+            if let Some((_, pool)) = pools.iter().find(|(name, _)| *name == failover.name) {
+                failover.ip = pool.ip().to_string();
+                failover.port = pool.port().to_string();
+                failover.rig = pool.custom().to_string();
+            }
+        }
+        let mut urls = vec![self.pool_chain()[0].url()];
+        self.failover.retain(|pool| {
+            let url = pool.url();
+            let new = !urls.contains(&url);
+            urls.push(url);
+            new
+        });
+    }
+    /// The pool can be added to the failover chain, it is not in it yet.
+    pub fn failover_accepts(&self, name: &str, pool: &PoolNode) -> bool {
+        let url = FailoverPool::url_of(pool.ip(), pool.port());
+        !self
+            .pool_chain()
+            .iter()
+            .any(|p| p.name == name || p.url() == url)
+    }
 }
 impl XmrigProxy {
     pub const fn process_name() -> ProcessName {
@@ -864,16 +1602,11 @@ impl XmrigProxy {
         if self.simple {
             PROXY_API_PORT_DEFAULT
         } else if !self.arguments.is_empty() {
-            let mut last = "";
-            for arg in self.arguments.split_whitespace() {
-                if last == "--http-host" {
-                    return last.parse().unwrap_or(PROXY_API_PORT_DEFAULT);
-                }
-                last = arg;
-            }
-            return PROXY_API_PORT_DEFAULT;
+            Args::parse(&self.arguments)
+                .port(&["--http-port"])
+                .unwrap_or(PROXY_API_PORT_DEFAULT)
         } else {
-            return self.api_port.parse().unwrap_or(PROXY_API_PORT_DEFAULT);
+            self.api_port.parse().unwrap_or(PROXY_API_PORT_DEFAULT)
         }
     }
     /// get the port that would be used if xmrig was started with the current settings
@@ -881,21 +1614,11 @@ impl XmrigProxy {
         if self.simple {
             PROXY_PORT_DEFAULT
         } else if !self.arguments.is_empty() {
-            let mut last = "";
-            for arg in self.arguments.split_whitespace() {
-                if last == "--bind" || last == "-b" {
-                    return last
-                        .split(":")
-                        .last()
-                        .unwrap_or_default()
-                        .parse()
-                        .unwrap_or(PROXY_PORT_DEFAULT);
-                }
-                last = arg;
-            }
-            return PROXY_PORT_DEFAULT;
+            Args::parse(&self.arguments)
+                .port(&["--bind", "-b"])
+                .unwrap_or(PROXY_PORT_DEFAULT)
         } else {
-            return self.port.parse().unwrap_or(PROXY_PORT_DEFAULT);
+            self.port.parse().unwrap_or(PROXY_PORT_DEFAULT)
         }
     }
     /// get the port that proxy process is currently using or that it will use if started with current settings
@@ -24,6 +24,7 @@ use super::*;
 pub enum Submenu {
     Processes,
     P2pool,
+    Xmrig,
     Benchmarks,
 }
 
@@ -38,6 +39,7 @@ impl Display for Submenu {
         use Submenu::*;
         match self {
             P2pool => write!(f, "P2Pool"),
+            Xmrig => write!(f, "XMRig"),
             _ => write!(f, "{:?}", self),
         }
     }
@@ -78,6 +80,34 @@ impl Default for PayoutView {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- [BenchSize/BenchMode] enums for [Status/Benchmarks]
+// Number of hashes computed by [xmrig --bench].
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize, EnumIter)]
+pub enum BenchSize {
+    #[default]
+    M1,
+    M10,
+}
+
+impl Display for BenchSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::M1 => write!(f, "1M"),
+            Self::M10 => write!(f, "10M"),
+        }
+    }
+}
+
+// RandomX mode, light uses 256MB instead of the 2GB dataset.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize, Display, EnumIter)]
+pub enum BenchMode {
+    #[display("light")]
+    Light,
+    #[default]
+    #[display("fast")]
+    Fast,
+}
+
 //---------------------------------------------------------------------------------------------------- [Hash] enum for [Status/P2Pool]
 #[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize)]
 #[allow(clippy::enum_variant_names)]
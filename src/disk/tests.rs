@@ -59,6 +59,11 @@ mod test {
             save_before_quit = true
             processes = []
 
+            [gupax.proxy]
+            enabled = false
+            address = "127.0.0.1:9050"
+            tor = true
+
 			[status]
 			submenu = "P2pool"
 			payout_view = "Oldest"
@@ -66,6 +71,9 @@ mod test {
 			manual_hash = false
 			hashrate = 1241.23
 			hash_metric = "Hash"
+			bench_size = "M10"
+			bench_mode = "Fast"
+			bench_threads = 8
 			
 
 			[p2pool]
@@ -88,6 +96,11 @@ mod test {
             prefer_local_node = true
             console_height = 360
             stratum_port = 3333
+            merge_mine = [{ enabled = false, host = "tari://127.0.0.1:18102", address = "" }]
+            pinned_peers = ["1.2.3.4:37889"]
+            banned_peers = []
+            seed_nano_peers = true
+            discovery_seeds = "node.example.com:18089:18084"
 
             [p2pool.selected_node]
             index = 0
@@ -96,6 +109,13 @@ mod test {
             rpc = "18081"
             zmq_rig = "18083"
 
+            [p2pool.options]
+            light_mode = true
+            no_randomx = false
+            no_upnp = true
+            onion_address = ""
+            socks5 = "127.0.0.1:9050"
+
 			[xmrig]
 			simple = true
 			pause = 0
@@ -103,8 +123,12 @@ mod test {
 			arguments = ""
 			tls = false
 			keepalive = false
+			nicehash = false
 			max_threads = 32
 			current_threads = 16
+			priority = 2
+			huge_pages = true
+			affinity = []
 			address = ""
 			api_ip = "localhost"
 			api_port = "18088"
@@ -114,7 +138,23 @@ mod test {
 			port = "3333"
             token = "testtoken"
             console_height = 360
+            privilege = "Sudo"
+            pages_1gb = false
+            failover = []
+
+            [xmrig.idle]
+            enabled = false
+            max_load = 30
+            processes = ""
+            pause_on_battery = true
+            busy_threads = 0
+            resume_after = 60
 
+            [xmrig.thermal]
+            enabled = false
+            ceiling = 85
+            hysteresis = 10
+            hot_threads = 0
 
             [xmrig.selected_pool]
             index = 0
@@ -141,6 +181,7 @@ mod test {
 			p2pool_port = "18088"
             token = "testtoken"
             redirect_local_xmrig = true
+            worker_silent_minutes = 10
             console_height = 360
 
             [xmrig_proxy.selected_pool]
@@ -183,6 +224,21 @@ mod test {
             path_db = ""
             full_memory = false
             console_height = 360
+            ban_list = true
+            ban_list_path = ""
+            bootstrap = true
+
+            [node.options]
+            restricted_rpc = false
+            public_node = false
+            restricted_port = ""
+            confirm_external_bind = false
+            limit_rate_up = 0
+            limit_rate_down = 0
+            rpc_login = ""
+            bootstrap_address = ""
+            bootstrap_login = ""
+            bootstrap_proxy = ""
 
 			[version]
 			gupax = "v1.3.0"
@@ -361,6 +417,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn merge_payouts_skip_known_blocks() {
+        use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
+        use crate::human::HumanNumber;
+        use crate::xmr::AtomicUnit;
+
+        let mut path = std::env::temp_dir();
+        path.push("gupaxx_test_merge_payouts");
+        std::fs::create_dir_all(&path).unwrap();
+        let mut api = GupaxP2poolApi::new();
+        GupaxP2poolApi::fill_paths(&mut api, &path);
+        api.add_payout(
+            "2022-01-27 01:30:23.1377 | 0.000000000001 XMR | Block 2,642,816",
+            "2022-01-27 01:30:23.1377".to_string(),
+            AtomicUnit::from_u64(1),
+            HumanNumber::from_u64(2642816),
+        );
+
+        // One known block, one older new block.
+        let payouts = vec![
+            (
+                "2022-01-27 01:30:23.0000".to_string(),
+                AtomicUnit::from_u64(1),
+                HumanNumber::from_u64(2642816),
+            ),
+            (
+                "2022-01-26 10:00:00.0000".to_string(),
+                AtomicUnit::from_u64(3),
+                HumanNumber::from_u64(2642000),
+            ),
+        ];
+        assert_eq!(api.merge_payouts(payouts).unwrap(), 1);
+        assert_eq!(api.payout_u64, 2);
+        assert_eq!(api.xmr.to_u64(), 4);
+        assert!(
+            api.log
+                .starts_with("2022-01-26 10:00:00.0000 | 0.000000000003 XMR | Block 2,642,000")
+        );
+        GupaxP2poolApi::read_all_files_and_update(&mut api).unwrap();
+        assert_eq!(api.payout_u64, 2);
+        assert_eq!(api.log.lines().count(), 2);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
     #[test]
     fn convert_hash() {
         use crate::disk::status::Hash;
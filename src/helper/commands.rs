@@ -0,0 +1,287 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Commands of the command palette of the Node and P2Pool tabs.
+// A command is written to the STDIN of the process like a command typed by the user,
+// the response is the output printed after it and is parsed into a table.
+
+use derive_more::Display;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use strum::EnumIter;
+
+use crate::constants::*;
+use crate::regex::REGEXES;
+
+// Argument of a command: (name, hint, optional)
+pub type CommandArg = (&'static str, &'static str, bool);
+
+// How the response of a command is read.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ResponseKind {
+    // [key<sep>value] lines, also separated by commas if [commas].
+    Pairs { sep: char, commas: bool },
+    // a header followed by rows, columns separated by at least 2 spaces.
+    Columns,
+    // plain lines
+    Text,
+}
+
+pub trait PaletteCommand: Copy + Eq + std::fmt::Display + Send + Sync + 'static {
+    fn args(&self) -> &'static [CommandArg];
+    fn kind(&self) -> ResponseKind;
+    fn hover(&self) -> &'static str;
+    /// Lines to write to STDIN, if the arguments are valid.
+    fn lines(&self, args: &[String]) -> Result<Vec<String>, &'static str>;
+    /// Content of an output line, or [None] if the line is not part of a response.
+    fn content(line: &str) -> Option<&str>;
+}
+
+//---------------------------------------------------------------------------------------------------- Node
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Display, EnumIter)]
+pub enum NodeCommand {
+    #[display("status")]
+    Status,
+    #[display("sync_info")]
+    SyncInfo,
+    #[display("print_cn")]
+    PrintCn,
+    #[display("ban")]
+    Ban,
+    #[display("unban")]
+    Unban,
+    #[display("set_limit")]
+    SetLimit,
+    #[display("flush_txpool")]
+    FlushTxpool,
+    #[display("save")]
+    Save,
+}
+
+impl PaletteCommand for NodeCommand {
+    fn args(&self) -> &'static [CommandArg] {
+        match self {
+            Self::Ban => &[
+                ("IP", "1.2.3.4 or 1.2.3.0/24", false),
+                ("Seconds", "86400", true),
+            ],
+            Self::Unban => &[("IP", "1.2.3.4 or 1.2.3.0/24", false)],
+            Self::SetLimit => &[("Up kB/s", "2048", true), ("Down kB/s", "8192", true)],
+            Self::FlushTxpool => &[("Transaction ID", "all transactions if empty", true)],
+            _ => &[],
+        }
+    }
+    fn kind(&self) -> ResponseKind {
+        match self {
+            Self::Status => ResponseKind::Pairs {
+                sep: ':',
+                commas: true,
+            },
+            Self::SyncInfo | Self::PrintCn => ResponseKind::Columns,
+            _ => ResponseKind::Text,
+        }
+    }
+    fn hover(&self) -> &'static str {
+        match self {
+            Self::Status => NODE_COMMAND_STATUS,
+            Self::SyncInfo => NODE_COMMAND_SYNC_INFO,
+            Self::PrintCn => NODE_COMMAND_PRINT_CN,
+            Self::Ban => NODE_COMMAND_BAN,
+            Self::Unban => NODE_COMMAND_UNBAN,
+            Self::SetLimit => NODE_COMMAND_SET_LIMIT,
+            Self::FlushTxpool => NODE_COMMAND_FLUSH_TXPOOL,
+            Self::Save => NODE_COMMAND_SAVE,
+        }
+    }
+    fn lines(&self, args: &[String]) -> Result<Vec<String>, &'static str> {
+        let arg = |i: usize| args.get(i).map(|a| a.trim()).unwrap_or_default();
+        let ip_ok = |ip: &str| {
+            let (ip, mask) = ip.split_once('/').unwrap_or((ip, "32"));
+            REGEXES.ipv4.is_match(ip) && mask.parse::<u8>().is_ok_and(|m| m <= 32)
+        };
+        let number_ok = |n: &str| n.is_empty() || n.parse::<u32>().is_ok();
+        let line = match self {
+            Self::Ban => {
+                if !ip_ok(arg(0)) {
+                    return Err("Invalid IP or subnet");
+                }
+                if !number_ok(arg(1)) {
+                    return Err("Invalid number of seconds");
+                }
+                format!("ban {} {}", arg(0), arg(1))
+            }
+            Self::Unban => {
+                if !ip_ok(arg(0)) {
+                    return Err("Invalid IP or subnet");
+                }
+                format!("unban {}", arg(0))
+            }
+            // monerod has one command by direction, [limit] alone shows the limits.
+            Self::SetLimit => {
+                if !number_ok(arg(0)) || !number_ok(arg(1)) {
+                    return Err("Invalid limit");
+                }
+                let mut lines = vec![];
+                if !arg(0).is_empty() {
+                    lines.push(format!("limit_up {}", arg(0)));
+                }
+                if !arg(1).is_empty() {
+                    lines.push(format!("limit_down {}", arg(1)));
+                }
+                if lines.is_empty() {
+                    lines.push("limit".to_string());
+                }
+                return Ok(lines);
+            }
+            Self::FlushTxpool => {
+                let txid = arg(0);
+                if !txid.is_empty()
+                    && (txid.len() != 64 || !txid.chars().all(|c| c.is_ascii_hexdigit()))
+                {
+                    return Err("Invalid transaction ID");
+                }
+                format!("flush_txpool {}", txid)
+            }
+            _ => self.to_string(),
+        };
+        Ok(vec![line.trim_end().to_string()])
+    }
+    // monerod prints the responses without prefix, the lines with a date are its logs.
+    fn content(line: &str) -> Option<&str> {
+        static LOG: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}").unwrap());
+        (!LOG.is_match(line)).then_some(line)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- P2Pool
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Display, EnumIter)]
+pub enum P2poolCommand {
+    #[display("status")]
+    Status,
+    #[display("peers")]
+    Peers,
+    #[display("workers")]
+    Workers,
+}
+
+impl PaletteCommand for P2poolCommand {
+    fn args(&self) -> &'static [CommandArg] {
+        &[]
+    }
+    fn kind(&self) -> ResponseKind {
+        match self {
+            Self::Status => ResponseKind::Pairs {
+                sep: '=',
+                commas: false,
+            },
+            Self::Peers | Self::Workers => ResponseKind::Columns,
+        }
+    }
+    fn hover(&self) -> &'static str {
+        match self {
+            Self::Status => P2POOL_COMMAND_STATUS,
+            Self::Peers => P2POOL_COMMAND_PEERS,
+            Self::Workers => P2POOL_COMMAND_WORKERS,
+        }
+    }
+    fn lines(&self, _args: &[String]) -> Result<Vec<String>, &'static str> {
+        Ok(vec![self.to_string()])
+    }
+    // P2Pool prints the responses in its logs, the prefix of the log is removed.
+    fn content(line: &str) -> Option<&str> {
+        static LOG: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r"^(NOTICE|INFO|WARNING|ERROR)\s+\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+\s+",
+            )
+            .unwrap()
+        });
+        Some(LOG.find(line).map_or(line, |m| &line[m.end()..]))
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Response
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResponseTable {
+    // lines that are not part of the table
+    pub notes: Vec<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ResponseTable {
+    /// Parse the output printed after the [sent] lines of [command] were written.
+    /// The echo of the lines by the terminal is skipped.
+    pub fn parse<C: PaletteCommand>(command: C, sent: &[String], output: &str) -> Self {
+        let lines = output
+            .lines()
+            .filter_map(C::content)
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !sent.iter().any(|s| s == l));
+        let mut table = Self::default();
+        table.fill(command.kind(), lines);
+        table
+    }
+
+    fn fill<'a>(&mut self, kind: ResponseKind, lines: impl Iterator<Item = &'a str>) {
+        static COLUMNS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s{2,}|\t").unwrap());
+        match kind {
+            ResponseKind::Pairs { sep, commas } => {
+                self.headers = vec!["Name".to_string(), "Value".to_string()];
+                for line in lines {
+                    let parts: Vec<&str> = if commas {
+                        line.split(',').collect()
+                    } else {
+                        vec![line]
+                    };
+                    for part in parts {
+                        let row = match part.split_once(sep) {
+                            Some((name, value)) => vec![name.trim(), value.trim()],
+                            None => vec!["", part.trim()],
+                        };
+                        self.rows.push(row.into_iter().map(String::from).collect());
+                    }
+                }
+            }
+            // the header is the first line with at least 3 columns.
+            ResponseKind::Columns => {
+                for line in lines {
+                    let cells: Vec<String> = COLUMNS.split(line).map(String::from).collect();
+                    if self.headers.is_empty() {
+                        if cells.len() >= 3 {
+                            self.headers = cells;
+                        } else {
+                            self.notes.push(line.to_string());
+                        }
+                    } else if cells.len() >= 2 {
+                        let mut row = cells;
+                        // cells past the last header are kept in the last column.
+                        if row.len() > self.headers.len() {
+                            let extra = row.split_off(self.headers.len() - 1).join("  ");
+                            row.push(extra);
+                        }
+                        row.resize(self.headers.len(), String::new());
+                        self.rows.push(row);
+                    } else {
+                        self.notes.push(line.to_string());
+                    }
+                }
+            }
+            ResponseKind::Text => self.notes = lines.map(String::from).collect(),
+        }
+    }
+}
@@ -41,6 +41,7 @@ use crate::helper::{
     p2pool::{ImgP2pool, PubP2poolApi},
     xrig::{xmrig::ImgXmrig, xmrig::PubXmrigApi},
 };
+use crate::utils::topology::CpuTopology;
 use crate::{constants::*, disk::gupax_p2pool_api::GupaxP2poolApi, human::*, macros::*};
 use derive_more::derive::Display;
 use enclose::enc;
@@ -54,15 +55,19 @@ use std::fmt::Write;
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::Path;
 use std::{
+    collections::VecDeque,
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
     time::*,
 };
 use strum::{EnumCount, EnumIter};
+use xrig::idle::IdleInputs;
+use xrig::thermal::{THERMAL_HISTORY, cpu_temperature};
 use xrig::xmrig_proxy::ImgProxy;
 
 use self::xvb::{PubXvbApi, nodes::Pool};
+pub mod commands;
 pub mod node;
 pub mod p2pool;
 pub mod tests;
@@ -128,6 +133,15 @@ pub struct Sys {
     pub system_cpu_model: String,
     pub system_memory: String,
     pub system_cpu_usage: String,
+    // usage of every logical CPU, in the order of [topology]
+    pub cpu_usage: Vec<f32>,
+    pub topology: CpuTopology,
+    // read for the idle policy of XMRig
+    pub idle: IdleInputs,
+    // °C of the hottest sensor of the CPU, None without sensor
+    pub cpu_temperature: Option<f32>,
+    // one value per second, newest last
+    pub temperature_history: VecDeque<f32>,
 }
 
 impl Sys {
@@ -139,6 +153,11 @@ impl Sys {
             system_cpu_usage: "???%".to_string(),
             system_memory: "???GB / ???GB".to_string(),
             system_cpu_model: "???".to_string(),
+            cpu_usage: vec![],
+            topology: CpuTopology::default(),
+            idle: IdleInputs::default(),
+            cpu_temperature: None,
+            temperature_history: VecDeque::new(),
         }
     }
 }
@@ -478,6 +497,7 @@ impl Helper {
     #[inline(always)] // called once
     fn update_pub_sys_from_sysinfo(
         sysinfo: &sysinfo::System,
+        components: &sysinfo::Components,
         pub_sys: &mut Sys,
         pid: &sysinfo::Pid,
         helper: &Helper,
@@ -505,6 +525,21 @@ impl Helper {
             }
             format!("{:.2}%", total / (max_threads as f32))
         };
+        let cpu_usage = sysinfo.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        // the topology does not change, it is read once.
+        let topology = if pub_sys.topology.nodes.is_empty() {
+            CpuTopology::detect(sysinfo.cpus().len())
+        } else {
+            std::mem::take(&mut pub_sys.topology)
+        };
+        let cpu_temperature = cpu_temperature(components);
+        let mut temperature_history = std::mem::take(&mut pub_sys.temperature_history);
+        if let Some(temperature) = cpu_temperature {
+            temperature_history.push_back(temperature);
+            if temperature_history.len() > THERMAL_HISTORY {
+                temperature_history.pop_front();
+            }
+        }
         *pub_sys = Sys {
             gupax_uptime,
             gupax_cpu_usage,
@@ -512,6 +547,11 @@ impl Helper {
             system_cpu_usage,
             system_memory,
             system_cpu_model,
+            cpu_usage,
+            topology,
+            idle: IdleInputs::from_sysinfo(sysinfo, max_threads),
+            cpu_temperature,
+            temperature_history,
         };
     }
 
@@ -562,7 +602,7 @@ impl Helper {
         drop(lock);
 
         let sysinfo_cpu = sysinfo::CpuRefreshKind::everything();
-        let sysinfo_processes = sysinfo::ProcessRefreshKind::nothing().with_cpu();
+        let mut components = sysinfo::Components::new_with_refreshed_list();
 
         thread::spawn(move || {
             info!(
@@ -653,18 +693,17 @@ impl Helper {
 
                 // 2. Selectively refresh [sysinfo] for only what we need (better performance).
                 sysinfo.refresh_cpu_specifics(sysinfo_cpu);
-                debug!("Helper | Sysinfo refresh (1/3) ... [cpu]");
-                sysinfo.refresh_processes_specifics(
-                    sysinfo::ProcessesToUpdate::All,
-                    false,
-                    sysinfo_processes,
-                );
-                debug!("Helper | Sysinfo refresh (2/3) ... [processes]");
+                debug!("Helper | Sysinfo refresh (1/4) ... [cpu]");
+                IdleInputs::refresh_processes(&mut sysinfo);
+                debug!("Helper | Sysinfo refresh (2/4) ... [processes]");
                 sysinfo.refresh_memory();
-                debug!("Helper | Sysinfo refresh (3/3) ... [memory]");
+                debug!("Helper | Sysinfo refresh (3/4) ... [memory]");
+                components.refresh(false);
+                debug!("Helper | Sysinfo refresh (4/4) ... [components]");
                 debug!("Helper | Sysinfo OK, running [update_pub_sys_from_sysinfo()]");
                 Self::update_pub_sys_from_sysinfo(
                     &sysinfo,
+                    &components,
                     &mut lock_pub_sys,
                     &pid,
                     &lock,
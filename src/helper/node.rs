@@ -23,6 +23,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(target_os = "windows")]
 use std::os::windows::fs::MetadataExt;
 use std::{
+    collections::VecDeque,
     path::Path,
     sync::{Arc, Mutex},
     thread,
@@ -32,17 +33,24 @@ use sysinfo::MemoryRefreshKind;
 use tokio::spawn;
 
 use crate::{
+    components::node_storage::db_error,
     disk::state::{Node, StartOptionsMode},
     helper::{
         ProcessName, ProcessSignal, ProcessState, check_died, check_user_input, signal_end,
         sleep_end_loop,
     },
     macros::{arc_mut, sleep},
+    miscs::post_rpc,
 };
 use std::fmt::Write;
 
 use super::{Helper, HumanNumber, HumanTime, Process};
 
+// Time window on which the sync speed of the node is calculated.
+const NODE_SYNC_SPEED_WINDOW: Duration = Duration::from_secs(300);
+// Argument of monerod to recover a corrupted database.
+const NODE_DB_SALVAGE: &str = "--db-salvage";
+
 impl Helper {
     #[cold]
     #[inline(never)]
@@ -73,6 +81,29 @@ impl Helper {
             }
         }
     }
+    // Keep the first database error of the output, monerod exits after it.
+    fn check_db_error(
+        output_parse: &Arc<Mutex<String>>,
+        pub_api: &Arc<Mutex<PubNodeApi>>,
+        gui_api: &Arc<Mutex<PubNodeApi>>,
+    ) {
+        let output = std::mem::take(&mut *output_parse.lock().unwrap());
+        let Some(line) = db_error(&output) else {
+            return;
+        };
+        let mut gui_api = gui_api.lock().unwrap();
+        if gui_api.db_error.is_some() {
+            return;
+        }
+        error!("Node Watchdog | Database error detected: {}", line);
+        let _ = writeln!(
+            gui_api.output,
+            "Recovery | Database error detected: {line}\nRecovery | The Node tab can retry with {NODE_DB_SALVAGE}, verify or move aside the database"
+        );
+        gui_api.db_error = Some(line.clone());
+        drop(gui_api);
+        pub_api.lock().unwrap().db_error = Some(line);
+    }
     pub fn build_node_args(
         state: &crate::disk::state::Node,
         mode: StartOptionsMode,
@@ -97,6 +128,10 @@ impl Helper {
                 args.push("--enable-dns-blocklist".to_string());
                 args.push("--sync-pruned-blocks".to_string());
                 args.push("--prune-blockchain".to_string());
+                if let Some(path) = state.ban_list_file() {
+                    args.push("--ban-list".to_string());
+                    args.push(path.display().to_string());
+                }
             }
             StartOptionsMode::Advanced => {
                 let dir = if state.path_db.is_empty() {
@@ -128,6 +163,11 @@ impl Helper {
                 if state.pruned {
                     args.push("--prune-blockchain".to_string());
                 }
+                if let Some(path) = state.ban_list_file() {
+                    args.push("--ban-list".to_string());
+                    args.push(path.display().to_string());
+                }
+                args.extend(state.options.args());
             }
             StartOptionsMode::Custom => {
                 // This parses the input
@@ -156,7 +196,12 @@ impl Helper {
     #[inline(never)]
     // The "restart frontend" to a "frontend" function.
     // Basically calls to kill the current p2pool, waits a little, then starts the below function in a a new thread, then exit.
-    pub fn restart_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path) {
+    pub fn restart_node(
+        helper: &Arc<Mutex<Self>>,
+        state: &Node,
+        path: &Path,
+        bootstrap: Option<String>,
+    ) {
         info!("Node | Attempting to restart...");
         helper.lock().unwrap().node.lock().unwrap().signal = ProcessSignal::Restart;
         helper.lock().unwrap().node.lock().unwrap().state = ProcessState::Middle;
@@ -172,14 +217,47 @@ impl Helper {
             }
             // Ok, process is not alive, start the new one!
             info!("Node | Old process seems dead, starting new one!");
-            Self::start_node(&helper, &state, &path);
+            Self::start_node(&helper, &state, &path, bootstrap);
         });
         info!("Node | Restart ... OK");
     }
     #[cold]
     #[inline(never)]
     // The "frontend" function that parses the arguments, and spawns either the [Simple] or [Advanced] Node watchdog thread.
-    pub fn start_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path) {
+    // [bootstrap] is the remote node used as bootstrap daemon in [Simple] mode.
+    pub fn start_node(
+        helper: &Arc<Mutex<Self>>,
+        state: &Node,
+        path: &Path,
+        bootstrap: Option<String>,
+    ) {
+        Self::start_node_with(helper, state, path, bootstrap, vec![]);
+    }
+    #[cold]
+    #[inline(never)]
+    // Start the node once with [--db-salvage], to recover a corrupted database.
+    pub fn start_node_salvage(
+        helper: &Arc<Mutex<Self>>,
+        state: &Node,
+        path: &Path,
+        bootstrap: Option<String>,
+    ) {
+        info!("Node | Starting with --db-salvage...");
+        Self::start_node_with(
+            helper,
+            state,
+            path,
+            bootstrap,
+            vec![NODE_DB_SALVAGE.to_string()],
+        );
+    }
+    fn start_node_with(
+        helper: &Arc<Mutex<Self>>,
+        state: &Node,
+        path: &Path,
+        bootstrap: Option<String>,
+        extra: Vec<String>,
+    ) {
         helper.lock().unwrap().node.lock().unwrap().state = ProcessState::Middle;
         let mode = if state.simple {
             StartOptionsMode::Simple
@@ -190,7 +268,12 @@ impl Helper {
         };
         let (rpc_port, zmq_port) = state.ports();
         *helper.lock().unwrap().img_node.lock().unwrap() = ImgNode { rpc_port, zmq_port };
-        let args = Self::build_node_args(state, mode);
+        let proxy = state.proxy_args(&mode);
+        let bootstrap = state.bootstrap_args(&mode, bootstrap);
+        let mut args = Self::build_node_args(state, mode);
+        args.extend(bootstrap);
+        args.extend(proxy);
+        args.extend(extra);
 
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("Node | Launch arguments: {:#?}", args));
@@ -238,6 +321,7 @@ impl Helper {
         }));
         // 1b. Create command
         debug!("Node | Creating command...");
+        let salvage = args.iter().any(|arg| arg == NODE_DB_SALVAGE);
         let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new(path.clone());
         cmd.args(args);
         // if in simple state and enough free memory, enable full memory env
@@ -263,6 +347,14 @@ impl Helper {
         // reset stats
         *pub_api.lock().unwrap() = PubNodeApi::new();
         *gui_api.lock().unwrap() = PubNodeApi::new();
+        // the console was just reset, the recovery step is written after.
+        let mut salvaged = false;
+        if salvage {
+            let _ = writeln!(
+                gui_api.lock().unwrap().output,
+                "Recovery | Starting with {NODE_DB_SALVAGE}, the database is repaired if possible"
+            );
+        }
         // loop
         let start = process.lock().unwrap().start;
         info!("Node | Entering watchdog mode... woof!");
@@ -271,6 +363,7 @@ impl Helper {
             debug!("Node Watchdog | ----------- Start of loop -----------");
             {
                 // scope to drop locked mutex before the sleep
+                Self::check_db_error(&output_parse, pub_api, gui_api);
                 // check state
                 if check_died(
                     &child_pty,
@@ -315,7 +408,23 @@ impl Helper {
                         if priv_api.result.synchronized && priv_api.result.status == "OK" {
                             process.lock().unwrap().state = ProcessState::Alive
                         }
+                        // the RPC answers once the database is open.
+                        if salvage && !salvaged {
+                            salvaged = true;
+                            info!("Node | Database opened with {NODE_DB_SALVAGE} ... OK");
+                            let _ = writeln!(
+                                gui_api.lock().unwrap().output,
+                                "Recovery | Database opened with {NODE_DB_SALVAGE} ... OK"
+                            );
+                        }
                         PubNodeApi::update_from_priv(pub_api, priv_api);
+                        // peer details are not available on a restricted RPC, keep the counts only.
+                        match PrivNodeApi::request_connections(&client, &state).await {
+                            Ok(connections) => pub_api.lock().unwrap().connections = connections,
+                            Err(err) => {
+                                debug!("Node Watchdog | Could not get the connections\n{}", err)
+                            }
+                        }
                     }
                     Err(err) => {
                         // if node is just starting, do not throw an error
@@ -332,6 +441,9 @@ impl Helper {
             sleep_end_loop(now, ProcessName::Node).await;
         }
 
+        // the last lines of a failed start might be read after its exit.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        Self::check_db_error(&output_parse, pub_api, gui_api);
         // 5. If loop broke, we must be done here.
         info!("XMRig-Proxy Watchdog | Watchdog thread exiting... Goodbye!");
         // sleep
@@ -350,6 +462,20 @@ pub struct PubNodeApi {
     pub incoming_connections: u16,
     pub status: String,
     pub synchronized: bool,
+    pub height: u64,
+    pub target_height: u64,
+    pub busy_syncing: bool,
+    pub cumulative_difficulty: HumanNumber,
+    pub tx_pool_size: u64,
+    pub connections: Vec<NodeConnection>,
+    // remote node set with --bootstrap-daemon-address, empty if none
+    pub bootstrap_daemon: String,
+    // the last answer came from the bootstrap daemon, the local node is not synced yet
+    pub bootstrap_used: bool,
+    // corruption or open failure of the database found in the output
+    pub db_error: Option<String>,
+    // (time, height) of the last minutes, to calculate the sync speed.
+    sync_samples: VecDeque<(Instant, u64)>,
 }
 impl Default for PubNodeApi {
     fn default() -> Self {
@@ -371,6 +497,16 @@ impl PubNodeApi {
             incoming_connections: 0,
             status: String::from("Offline"),
             synchronized: false,
+            height: 0,
+            target_height: 0,
+            busy_syncing: false,
+            cumulative_difficulty: HumanNumber::unknown(),
+            tx_pool_size: 0,
+            connections: vec![],
+            bootstrap_daemon: String::new(),
+            bootstrap_used: false,
+            db_error: None,
+            sync_samples: VecDeque::new(),
         }
     }
     pub fn combine_gui_pub_api(gui_api: &mut Self, pub_api: &mut Self) {
@@ -384,8 +520,16 @@ impl PubNodeApi {
             gui_api.output.push_str(&buf);
         }
     }
-    fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivNodeApi) {
+    fn update_from_priv(public: &Arc<Mutex<Self>>, mut private: PrivNodeApi) {
+        // monerod answers with the state of the bootstrap daemon while it is used,
+        // only [height_without_bootstrap] is about the local node.
+        if private.result.untrusted {
+            private.result.target_height = private.result.height;
+            private.result.height = private.result.height_without_bootstrap;
+            private.result.synchronized = false;
+        }
         let mut public = public.lock().unwrap();
+        public.add_sync_sample(Instant::now(), private.result.height);
         *public = Self {
             blockheight: HumanNumber::from_u64(private.result.height),
             difficulty: HumanNumber::from_u64(private.result.difficulty),
@@ -396,9 +540,71 @@ impl PubNodeApi {
             incoming_connections: private.result.incoming_connections_count,
             status: private.result.status,
             synchronized: private.result.synchronized,
+            height: private.result.height,
+            target_height: private.result.target_height,
+            busy_syncing: private.result.busy_syncing,
+            cumulative_difficulty: HumanNumber::from_u64(private.result.cumulative_difficulty),
+            tx_pool_size: private.result.tx_pool_size,
+            bootstrap_daemon: private.result.bootstrap_daemon_address,
+            bootstrap_used: private.result.untrusted,
             ..std::mem::take(&mut *public)
         }
     }
+    pub(super) fn add_sync_sample(&mut self, now: Instant, height: u64) {
+        self.sync_samples.push_back((now, height));
+        while self
+            .sync_samples
+            .front()
+            .is_some_and(|(t, _)| now.duration_since(*t) > NODE_SYNC_SPEED_WINDOW)
+        {
+            self.sync_samples.pop_front();
+        }
+    }
+    /// Whether the RPC requests are answered by the bootstrap daemon.
+    pub fn bootstrap_status(&self) -> String {
+        if self.bootstrap_daemon.is_empty() {
+            "not used".to_string()
+        } else if self.bootstrap_used {
+            format!("answering with {}", self.bootstrap_daemon)
+        } else {
+            format!("{} on standby", self.bootstrap_daemon)
+        }
+    }
+    /// Height the node is synchronizing to.
+    /// monerod returns 0 for the target height once it is synchronized.
+    pub fn target_height(&self) -> u64 {
+        self.target_height.max(self.height)
+    }
+    pub fn sync_percent(&self) -> f32 {
+        if self.synchronized {
+            return 100.0;
+        }
+        match self.target_height() {
+            0 => 0.0,
+            target => self.height as f32 / target as f32 * 100.0,
+        }
+    }
+    /// Blocks added to the chain by minute, on the last minutes.
+    pub fn blocks_per_minute(&self) -> f32 {
+        match (self.sync_samples.front(), self.sync_samples.back()) {
+            (Some((start, first)), Some((end, last))) if end > start => {
+                last.saturating_sub(*first) as f32 / end.duration_since(*start).as_secs_f32() * 60.0
+            }
+            _ => 0.0,
+        }
+    }
+    /// Estimated time before the node is synchronized, None if it can not be estimated yet.
+    pub fn sync_eta(&self) -> Option<Duration> {
+        if self.synchronized {
+            return Some(Duration::ZERO);
+        }
+        let speed = self.blocks_per_minute();
+        if speed <= 0.0 || self.target_height() == 0 {
+            return None;
+        }
+        let remaining = self.target_height().saturating_sub(self.height);
+        Some(Duration::from_secs_f32(remaining as f32 / speed * 60.0))
+    }
     pub fn update_from_output(
         public: &Arc<Mutex<Self>>,
         output_pub: &Arc<Mutex<String>>,
@@ -432,29 +638,85 @@ struct ResultNodeJson {
     pub incoming_connections_count: u16,
     pub status: String,
     pub synchronized: bool,
+    #[serde(default)]
+    pub target_height: u64,
+    #[serde(default)]
+    pub busy_syncing: bool,
+    #[serde(default)]
+    pub cumulative_difficulty: u64,
+    #[serde(default)]
+    pub tx_pool_size: u64,
+    #[serde(default)]
+    pub bootstrap_daemon_address: String,
+    #[serde(default)]
+    pub untrusted: bool,
+    #[serde(default)]
+    pub height_without_bootstrap: u64,
+}
+#[derive(Deserialize, Serialize)]
+struct PrivNodeConnections {
+    result: ResultConnectionsJson,
+}
+#[derive(Deserialize, Serialize)]
+struct ResultConnectionsJson {
+    #[serde(default)]
+    connections: Vec<NodeConnection>,
+}
+/// Peer of the node, from the get_connections RPC method.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NodeConnection {
+    pub address: String,
+    pub incoming: bool,
+    pub height: u64,
+    pub live_time: u64,
+    pub state: String,
+    // kB/s
+    pub current_download: u64,
+    pub current_upload: u64,
 }
 impl PrivNodeApi {
+    async fn request_connections(
+        client: &Client,
+        state: &Node,
+    ) -> std::result::Result<Vec<NodeConnection>, anyhow::Error> {
+        let adr = format!("http://{}:{}/json_rpc", state.api_ip, state.api_port);
+        let private = post_rpc(
+            client,
+            &adr,
+            r#"{"jsonrpc":"2.0","id":"0","method":"get_connections"}"#.to_string(),
+            state.rpc_login(),
+        )
+        .await?
+        .json::<PrivNodeConnections>()
+        .await?;
+        Ok(private.result.connections)
+    }
     async fn request_api(
         client: &Client,
         state: &Node,
     ) -> std::result::Result<Self, anyhow::Error> {
         let adr = format!("http://{}:{}/json_rpc", state.api_ip, state.api_port);
         #[cfg(target_os = "windows")]
-        let mut private = client
-            .post(adr)
-            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#)
-            .send()
-            .await?
-            .json::<PrivNodeApi>()
-            .await?;
+        let mut private = post_rpc(
+            client,
+            &adr,
+            r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#.to_string(),
+            state.rpc_login(),
+        )
+        .await?
+        .json::<PrivNodeApi>()
+        .await?;
         #[cfg(not(target_os = "windows"))]
-        let private = client
-            .post(adr)
-            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#)
-            .send()
-            .await?
-            .json::<PrivNodeApi>()
-            .await?;
+        let private = post_rpc(
+            client,
+            &adr,
+            r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#.to_string(),
+            state.rpc_login(),
+        )
+        .await?
+        .json::<PrivNodeApi>()
+        .await?;
         #[cfg(target_os = "windows")]
         // api returns 0 for DB size for Windows so we read the size directly from the filesystem.
         // https://github.com/monero-project/monero/issues/9513
@@ -37,7 +37,9 @@ use crate::regex::contains_yourhashrate;
 use crate::regex::contains_yourshare;
 use crate::regex::contains_zmq_failure;
 use crate::regex::estimated_hr;
+use crate::regex::merge_mining_status;
 use crate::regex::nb_current_shares;
+use crate::utils::args::Args;
 use crate::{
     constants::*,
     disk::gupax_p2pool_api::GupaxP2poolApi,
@@ -46,6 +48,7 @@ use crate::{
     macros::*,
     xmr::*,
 };
+use derive_more::Display;
 use enclose::enc;
 use log::*;
 use serde::{Deserialize, Serialize};
@@ -260,12 +263,14 @@ impl Helper {
                     error!("Could not write the p2pool nano chain config file: {err}");
                 }
             }
-            // also replace the peers list
-            let mut path_peer_list = path.to_path_buf();
-            path_peer_list.pop();
-            path_peer_list.push("p2pool_peers.txt");
-            if let Err(err) = std::fs::write(&path_peer_list, P2POOL_NANO_PEER_LIST) {
-                error!("Could not write the p2pool peer list file for the nano chain: {err}");
+            // also replace the peers list, if the user wants to seed from the bundled one.
+            if state.seed_nano_peers {
+                let mut path_peer_list = path.to_path_buf();
+                path_peer_list.pop();
+                path_peer_list.push("p2pool_peers.txt");
+                if let Err(err) = std::fs::write(&path_peer_list, state.nano_peers()) {
+                    error!("Could not write the p2pool peer list file for the nano chain: {err}");
+                }
             }
         }
 
@@ -274,11 +279,13 @@ impl Helper {
         // the user will need to restart p2pool
         let node_process = Arc::clone(&helper.lock().unwrap().node);
         let img_node = Arc::clone(&helper.lock().unwrap().img_node);
-        let (local_node_zmq, local_node_rpc) = state_node.current_ports(
+        let (local_node_rpc, local_node_zmq) = state_node.current_ports(
             node_process.lock().unwrap().is_alive(),
             &img_node.lock().unwrap(),
         );
-        let args = Self::build_p2pool_args(
+        let proxy = state.proxy_args(&mode);
+        let custom = matches!(mode, StartOptionsMode::Custom);
+        let mut args = Self::build_p2pool_args(
             state,
             path,
             &backup_hosts,
@@ -287,6 +294,11 @@ impl Helper {
             local_node_rpc,
             mode,
         );
+        args.extend(proxy);
+        // P2Pool needs the login of the local node to use its RPC.
+        if let Some(login) = state_node.rpc_login().filter(|_| !custom) {
+            Self::add_local_node_login(&mut args, login, local_node_rpc);
+        }
 
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!(
@@ -363,44 +375,39 @@ impl Helper {
                 stratum_port: P2POOL_PORT_DEFAULT,
             };
         } else if !state.arguments.is_empty() {
-            // This parses the input and attempts to fill out
-            // the [ImgP2pool]... This is pretty bad code...
-            let mut last = "";
-            let lock = helper.lock().unwrap();
-            let mut p2pool_image = lock.img_p2pool.lock().unwrap();
-            let mut chain = P2poolChain::Main;
-            for arg in state.arguments.split_whitespace() {
-                match last {
-                    "--mini" => {
-                        chain = P2poolChain::Mini;
-                        p2pool_image.chain = chain.to_string();
-                    }
-                    // used for nano chain, Gupaxx will not recognize another custom chain
-                    "--sidechain-config" => {
-                        chain = P2poolChain::Nano;
-                        p2pool_image.chain = chain.to_string();
-                    }
-                    "--wallet" => p2pool_image.address = Self::head_tail_of_monero_address(arg),
-                    "--host" => p2pool_image.host = arg.to_string(),
-                    "--rpc-port" => p2pool_image.rpc = arg.to_string(),
-                    "--zmq-port" => p2pool_image.zmq = arg.to_string(),
-                    "--out-peers" => p2pool_image.out_peers = arg.to_string(),
-                    "--in-peers" => p2pool_image.in_peers = arg.to_string(),
-                    "--data-api" => api_path = PathBuf::from(arg),
-                    "--stratum" => {
-                        p2pool_image.stratum_port = last
-                            .split(":")
-                            .last()
-                            .unwrap_or(&P2POOL_PORT_DEFAULT.to_string())
-                            .parse()
-                            .unwrap_or(P2POOL_PORT_DEFAULT)
-                    }
-                    _ => (),
-                }
-                p2pool_image.chain = chain.to_string();
-                let arg = if arg == "localhost" { "127.0.0.1" } else { arg };
-                last = arg;
+            // Parse the custom arguments to fill out the [ImgP2pool],
+            // using the values P2Pool takes by default when a flag is not given.
+            let args = Args::parse(&state.arguments);
+            // used for nano chain, Gupaxx will not recognize another custom chain
+            let chain = if args.has(&["--mini"]) {
+                P2poolChain::Mini
+            } else if args.has(&["--nano", "--sidechain-config"]) {
+                P2poolChain::Nano
+            } else {
+                P2poolChain::Main
+            };
+            if let Some(path) = args.value(&["--data-api"]) {
+                api_path = PathBuf::from(path);
             }
+            *helper.lock().unwrap().img_p2pool.lock().unwrap() = ImgP2pool {
+                chain: chain.to_string(),
+                address: args
+                    .value(&["--wallet"])
+                    .map(Self::head_tail_of_monero_address)
+                    .unwrap_or_else(|| "???".to_string()),
+                host: args.host(&["--host"]).unwrap_or("127.0.0.1").to_string(),
+                rpc: args
+                    .port(&["--rpc-port"])
+                    .unwrap_or(NODE_RPC_PORT_DEFAULT)
+                    .to_string(),
+                zmq: args
+                    .port(&["--zmq-port"])
+                    .unwrap_or(NODE_ZMQ_PORT_DEFAULT)
+                    .to_string(),
+                out_peers: args.value(&["--out-peers"]).unwrap_or("10").to_string(),
+                in_peers: args.value(&["--in-peers"]).unwrap_or("10").to_string(),
+                stratum_port: args.port(&["--stratum"]).unwrap_or(P2POOL_PORT_DEFAULT),
+            };
         } else {
             *helper.lock().unwrap().img_p2pool.lock().unwrap() = ImgP2pool {
                 chain: state.chain.to_string(),
@@ -471,11 +478,15 @@ impl Helper {
                 args.push("nano_config.json".to_string());
                 args.push("--p2p".to_string());
                 args.push("0.0.0.0:37890".to_string());
+                args.extend(state.merge_mining_args(&mode)); // Tari and other chains
+                args.extend(state.addpeers_args()); // Pinned peers
 
                 // Push other nodes if `backup_host`.
                 if let Some(nodes) = backup_hosts {
                     for node in nodes {
-                        if (node.ip(), node.port(), node.custom()) != (ip, rpc, zmq) {
+                        if (node.ip(), node.port(), node.custom())
+                            != (ip.as_str(), rpc.as_str(), zmq.as_str())
+                        {
                             args.push("--host".to_string());
                             args.push(node.ip().to_string());
                             args.push("--rpc-port".to_string());
@@ -506,6 +517,8 @@ impl Helper {
                 args.push("nano_config.json".to_string());
                 args.push("--p2p".to_string());
                 args.push("0.0.0.0:37890".to_string());
+                args.extend(state.merge_mining_args(&mode)); // Tari and other chains
+                args.extend(state.addpeers_args()); // Pinned peers
             }
             StartOptionsMode::Advanced => {
                 // build the argument
@@ -532,7 +545,14 @@ impl Helper {
                 args.push(api_path.display().to_string()); // API Path
                 args.push("--local-api".to_string()); // Enable API
                 args.push("--no-color".to_string()); // Remove color escape sequences
-                args.push("--light-mode".to_string()); // Assume user is not using P2Pool to mine.
+                args.extend(state.options.args()); // Light mode, RandomX, Tor...
+                args.extend(state.merge_mining_args(&mode)); // Tari and other chains
+                args.extend(state.addpeers_args()); // Pinned peers
+                // P2Pool listens on its default port if the stratum port is not given.
+                if state.stratum_port != P2POOL_PORT_DEFAULT {
+                    args.push("--stratum".to_string());
+                    args.push(format!("0.0.0.0:{}", state.stratum_port));
+                }
                 if state.chain == P2poolChain::Mini {
                     args.push("--mini".to_string());
                 } else if state.chain == P2poolChain::Nano {
@@ -573,6 +593,25 @@ impl Helper {
         args
     }
 
+    // [--rpc-login] applies to the [--host] given before it, so it is added to every host pointing to the local node.
+    pub fn add_local_node_login(args: &mut Vec<String>, login: &str, local_node_rpc_port: u16) {
+        let port = local_node_rpc_port.to_string();
+        let hosts: Vec<usize> = args
+            .windows(4)
+            .enumerate()
+            .filter(|(_, w)| {
+                w[0] == "--host"
+                    && (w[1] == "127.0.0.1" || w[1] == "localhost")
+                    && w[2] == "--rpc-port"
+                    && w[3] == port
+            })
+            .map(|(i, _)| i + 4)
+            .collect();
+        for i in hosts.into_iter().rev() {
+            args.splice(i..i, ["--rpc-login".to_string(), login.to_string()]);
+        }
+    }
+
     #[cold]
     #[inline(never)]
     // The P2Pool watchdog. Spawns 1 OS thread for reading a PTY (STDOUT+STDERR), and combines the [Child] with a PTY so STDIN actually works.
@@ -605,6 +644,8 @@ impl Helper {
             .unwrap();
         // 1b. Create command
         debug!("P2Pool | Creating command...");
+        // the status follows the Tari client, other chains are not watched.
+        let merge_mining = args.iter().any(|arg| arg.starts_with("tari://"));
         let mut cmd = portable_pty::CommandBuilder::new(path.as_path());
         cmd.args(args);
         cmd.env("NO_COLOR", "true");
@@ -687,6 +728,9 @@ impl Helper {
 
         // Reset stats before loop, except action parameters without a need for saving to state.
         reset_data_p2pool(&pub_api, &gui_api);
+        if merge_mining {
+            pub_api.lock().unwrap().merge_mining = MergeMiningStatus::Connecting;
+        }
 
         // 4. Loop as watchdog
         let mut first_loop = true;
@@ -823,6 +867,11 @@ impl Helper {
         // 5. If loop broke, we must be done here.
         info!("P2Pool Watchdog | Watchdog thread exiting... Goodbye!");
     }
+    // Leaving a working remote node is only worth it if the local node is alive and has (almost) no block left to download.
+    // The node can still be behind while alive, after a suspend for example, or its ETA can be unknown.
+    pub(crate) fn local_node_ready(node: ProcessState, eta: Option<Duration>) -> bool {
+        node == ProcessState::Alive && eta.is_some_and(|eta| eta <= P2POOL_LOCAL_NODE_MAX_ETA)
+    }
     #[tokio::main]
     #[allow(clippy::await_holding_lock)]
     async fn watch_switch_p2pool_to_local_node(
@@ -840,11 +889,22 @@ impl Helper {
             let helper_lock = helper.lock().unwrap();
             let node_process = helper_lock.node.lock().unwrap();
             let process = helper_lock.p2pool.lock().unwrap();
-            let gui_api = helper_lock.gui_api_p2pool.lock().unwrap();
+            let local_node_eta = if matches!(
+                node_process.state,
+                ProcessState::Syncing | ProcessState::Alive
+            ) {
+                helper_lock.gui_api_node.lock().unwrap().sync_eta()
+            } else {
+                None
+            };
+            let ready = Self::local_node_ready(node_process.state, local_node_eta);
+            let mut gui_api = helper_lock.gui_api_p2pool.lock().unwrap();
+            // let the user know when the switch is expected while the local node is catching up.
+            gui_api.local_node_eta = if ready { None } else { local_node_eta };
             if gui_api.prefer_local_node
                 && state.simple
                 && !state.local_node
-                && node_process.state == ProcessState::Alive
+                && ready
                 && process.is_alive()
             {
                 drop(gui_api);
@@ -965,6 +1025,21 @@ pub struct PubP2poolApi {
     pub p2p_connected: u32,
     pub node_connected: bool,
     pub prefer_local_node: bool,
+    // time left before the local node is synchronized and P2Pool switches to it.
+    pub local_node_eta: Option<Duration>,
+    // from the lines of the merge mining client
+    pub merge_mining: MergeMiningStatus,
+    // from local/p2p
+    pub peers: Vec<P2poolPeer>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display)]
+pub enum MergeMiningStatus {
+    #[default]
+    Disabled,
+    Connecting,
+    Connected,
+    Error,
 }
 
 impl Default for PubP2poolApi {
@@ -1020,7 +1095,10 @@ impl PubP2poolApi {
             p2p_connected: 0,
             node_connected: false,
             prefer_local_node: true,
+            local_node_eta: None,
             fails_zmq_since: None,
+            merge_mining: MergeMiningStatus::Disabled,
+            peers: vec![],
         }
     }
 
@@ -1041,6 +1119,7 @@ impl PubP2poolApi {
             sidechain_shares: std::mem::take(&mut gui_api.sidechain_shares),
             sidechain_ehr: std::mem::take(&mut gui_api.sidechain_ehr),
             prefer_local_node: std::mem::take(&mut gui_api.prefer_local_node),
+            local_node_eta: std::mem::take(&mut gui_api.local_node_eta),
             ..pub_api.clone()
         };
     }
@@ -1089,6 +1168,12 @@ impl PubP2poolApi {
             warn!("P2Pool Watchdog | a ZMQ failure was seen, check connection to Node");
             public.fails_zmq_since = Some(0);
         }
+        if let Some(status) = merge_mining_status(&output_parse) {
+            if status != public.merge_mining {
+                info!("P2Pool Watchdog | Merge mining is now [{}]", status);
+            }
+            public.merge_mining = status;
+        }
 
         // 3. Throw away [output_parse]
         output_parse.clear();
@@ -1175,6 +1260,11 @@ impl PubP2poolApi {
             // It will take two minutes to detect that the node is dead.
             // If the timeframe is reduced, it can have false positive.
             node_connected: p2p.zmq_last_active.is_some_and(|x| x < 120),
+            peers: p2p
+                .peers
+                .iter()
+                .filter_map(|p| P2poolPeer::from_api_str(p))
+                .collect(),
             ..std::mem::take(&mut *public)
         };
     }
@@ -1427,10 +1517,14 @@ impl PoolStatistics {
 }
 //---------------------------------------------------------------------------------------------------- Private P2Pool "Network" API
 // This matches P2Pool's [local/p2p] JSON API file.
+// Each peer is a line like [O,3600,25,P2Pool v4.5,10412345,1.2.3.4:37889]:
+// direction, seconds since connection, ping in ms, software, sidechain height and address.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(super) struct PrivP2PoolP2PApi {
     pub connections: u32,
     pub zmq_last_active: Option<u32>,
+    #[serde(default)]
+    pub peers: Vec<String>,
 }
 
 impl Default for PrivP2PoolP2PApi {
@@ -1444,6 +1538,7 @@ impl PrivP2PoolP2PApi {
         Self {
             connections: 0,
             zmq_last_active: None,
+            peers: vec![],
         }
     }
 
@@ -1457,6 +1552,35 @@ impl PrivP2PoolP2PApi {
         }
     }
 }
+// A peer of the P2Pool sidechain, see [PrivP2PoolP2PApi].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct P2poolPeer {
+    pub incoming: bool,
+    pub uptime: HumanTime,
+    pub ping: u64,
+    pub software: String,
+    pub height: u64,
+    pub address: String,
+}
+
+impl P2poolPeer {
+    pub fn from_api_str(peer: &str) -> Option<Self> {
+        let fields: Vec<&str> = peer.split(',').map(|f| f.trim()).collect();
+        if fields.len() != 6 {
+            warn!("P2Pool P2P API | Could not parse peer: {}", peer);
+            return None;
+        }
+        Some(Self {
+            incoming: fields[0] == "I",
+            uptime: HumanTime::from_u64(fields[1].parse().unwrap_or_default()),
+            ping: fields[2].parse().unwrap_or_default(),
+            software: fields[3].to_string(),
+            height: fields[4].parse().unwrap_or_default(),
+            address: fields[5].to_string(),
+        })
+    }
+}
+
 fn reset_data_p2pool(pub_api: &Arc<Mutex<PubP2poolApi>>, gui_api: &Arc<Mutex<PubP2poolApi>>) {
     let current_pref = mem::take(&mut pub_api.lock().unwrap().prefer_local_node);
     // even if it is a restart, we want to keep set values by the user without the need from him to click on save button.
@@ -490,12 +490,22 @@ Uptime         = 0h 2m 4s
       10.97,
       10.58,
       10.47
-    ]
+    ],
+    "memory": {
+      "free": 123,
+      "total": 123123,
+      "resident_set_memory": 123123123
+    }
   },
   "connection": {
     "diff": 123,
     "accepted": 123,
-    "rejected": 123
+    "rejected": 123,
+    "pool": "localhost:3333",
+    "tls": null,
+    "ping": 0,
+    "uptime": 123,
+    "failures": 0
   },
   "hashrate": {
     "total": [
@@ -503,6 +513,55 @@ Uptime         = 0h 2m 4s
       111.11,
       111.11
     ]
+  },
+  "version": "6.18.0",
+  "algo": "rx/0",
+  "paused": false,
+  "features": [
+    "api",
+    "asm",
+    "http",
+    "hwloc",
+    "tls",
+    "opencl",
+    "cuda"
+  ],
+  "cpu": {
+    "brand": "blah blah blah",
+    "msr": "ryzen_19h",
+    "assembly": "ryzen",
+    "flags": [
+      "aes",
+      "vaes",
+      "avx",
+      "avx2",
+      "bmi2",
+      "osxsave",
+      "pdpe1gb",
+      "sse2",
+      "ssse3",
+      "sse4.1",
+      "popcnt",
+      "cat_l3"
+    ]
+  },
+  "hugepages": true,
+  "results": {
+    "shares_good": 123,
+    "shares_total": 123,
+    "avg_time_ms": 123,
+    "best": [
+      123,
+      123,
+      123,
+      13,
+      123,
+      123,
+      123,
+      123,
+      123,
+      123
+    ]
   }
 }"#;
         assert_eq!(data_after_ser, json)
@@ -871,4 +930,796 @@ Uptime         = 0h 2m 4s
             args
         );
     }
+
+    #[test]
+    fn node_sync_progress() {
+        use crate::helper::node::PubNodeApi;
+        use std::time::{Duration, Instant};
+        let mut api = PubNodeApi::new();
+        assert_eq!(api.sync_eta(), None);
+        let start = Instant::now();
+        api.add_sync_sample(start, 1000);
+        api.add_sync_sample(start + Duration::from_secs(30), 1500);
+        api.add_sync_sample(start + Duration::from_secs(60), 2000);
+        api.height = 2000;
+        api.target_height = 4000;
+        assert!((api.blocks_per_minute() - 1000.0).abs() < 0.01);
+        assert_eq!(api.sync_percent(), 50.0);
+        assert_eq!(api.sync_eta().unwrap().as_secs_f32().round(), 120.0);
+        // samples older than the window are dropped.
+        api.add_sync_sample(start + Duration::from_secs(400), 2000);
+        assert_eq!(api.blocks_per_minute(), 0.0);
+        assert_eq!(api.sync_eta(), None);
+        // monerod returns 0 as target height once synchronized.
+        api.target_height = 0;
+        api.synchronized = true;
+        assert_eq!(api.target_height(), 2000);
+        assert_eq!(api.sync_percent(), 100.0);
+        assert_eq!(api.sync_eta(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn local_node_ready_p2pool() {
+        use std::time::Duration;
+        // syncing, even almost done: P2Pool needs a synchronized node.
+        assert!(!Helper::local_node_ready(
+            ProcessState::Syncing,
+            Some(Duration::from_secs(10))
+        ));
+        // alive but still far behind.
+        assert!(!Helper::local_node_ready(
+            ProcessState::Alive,
+            Some(Duration::from_secs(600))
+        ));
+        // alive with no estimate.
+        assert!(!Helper::local_node_ready(ProcessState::Alive, None));
+        assert!(Helper::local_node_ready(
+            ProcessState::Alive,
+            Some(Duration::ZERO)
+        ));
+        assert!(Helper::local_node_ready(
+            ProcessState::Alive,
+            Some(Duration::from_secs(30))
+        ));
+    }
+
+    #[test]
+    fn ban_list_node() {
+        use crate::disk::state::Node;
+        let mut state = Node {
+            ban_list_path: "/tmp/my_ban_list.txt".to_string(),
+            ..Default::default()
+        };
+        for mode in [StartOptionsMode::Simple, StartOptionsMode::Advanced] {
+            let args = Helper::build_node_args(&state, mode).join(" ");
+            assert!(args.contains("--ban-list /tmp/my_ban_list.txt"));
+        }
+        state.ban_list = false;
+        let args = Helper::build_node_args(&state, StartOptionsMode::Advanced);
+        assert!(!args.contains(&"--ban-list".to_string()));
+        // the bundled list is installed in the data directory.
+        state.ban_list = true;
+        state.ban_list_path.clear();
+        let path = state.ban_list_file().unwrap();
+        assert!(path.ends_with(crate::disk::consts::BAN_LIST_TXT));
+        let dir = std::env::temp_dir();
+        Node::install_ban_list(&dir);
+        let entries = Node::ban_list_entries(&dir.join(crate::disk::consts::BAN_LIST_TXT));
+        assert_eq!(
+            entries.unwrap(),
+            crate::constants::NODE_BAN_LIST.lines().count()
+        );
+    }
+
+    #[test]
+    fn rpc_options_node() {
+        use crate::disk::state::{Node, NodeOptions};
+        let mut state = Node::default();
+        assert!(state.validate_options().is_ok());
+        // the RPC only listens on localhost by default.
+        assert_eq!(state.public_rpc_port(), None);
+        state.simple = false;
+        state.api_ip = "0.0.0.0".to_string();
+        assert_eq!(state.public_rpc_port(), Some(18081));
+        state.api_ip = "127.0.0.1".to_string();
+        assert_eq!(state.public_rpc_port(), None);
+        state.options = NodeOptions {
+            restricted_rpc: true,
+            public_node: true,
+            restricted_port: "18089".to_string(),
+            limit_rate_up: 1024,
+            limit_rate_down: 0,
+            rpc_login: "user:pass".to_string(),
+            ..Default::default()
+        };
+        assert!(state.validate_options().is_ok());
+        assert_eq!(state.public_rpc_port(), Some(18089));
+        assert_eq!(state.rpc_login(), Some("user:pass"));
+        let args = Helper::build_node_args(&state, StartOptionsMode::Advanced).join(" ");
+        assert!(args.contains("--restricted-rpc"));
+        assert!(args.contains("--rpc-restricted-bind-ip 0.0.0.0 --rpc-restricted-bind-port 18089"));
+        assert!(args.contains("--public-node"));
+        assert!(args.contains("--limit-rate-up 1024"));
+        assert!(!args.contains("--limit-rate-down"));
+        assert!(args.contains("--rpc-login user:pass"));
+        // P2Pool is given the login for the hosts of the local node only.
+        let mut p2pool_args: Vec<String> =
+            "--host 127.0.0.1 --rpc-port 18081 --zmq-port 18083 --host 1.2.3.4 --rpc-port 18081"
+                .split_whitespace()
+                .map(str::to_string)
+                .collect();
+        Helper::add_local_node_login(&mut p2pool_args, "user:pass", 18081);
+        assert_eq!(
+            p2pool_args.join(" "),
+            "--host 127.0.0.1 --rpc-port 18081 --rpc-login user:pass --zmq-port 18083 --host 1.2.3.4 --rpc-port 18081"
+        );
+        // the options are not used in simple mode.
+        let args = Helper::build_node_args(&state, StartOptionsMode::Simple).join(" ");
+        assert!(!args.contains("--restricted-rpc"));
+        state.simple = true;
+        assert_eq!(state.rpc_login(), None);
+        state.simple = false;
+        // the restricted port can not take the port of the RPC or ZMQ.
+        state.options.restricted_port = "18083".to_string();
+        assert!(state.validate_options().is_err());
+        state.options.restricted_port.clear();
+        // a public node needs a restricted RPC.
+        state.options.restricted_rpc = false;
+        assert!(state.validate_options().is_err());
+        state.options.public_node = false;
+        assert!(state.validate_options().is_ok());
+        // an unrestricted RPC outside of localhost needs to be confirmed.
+        state.api_ip = "0.0.0.0".to_string();
+        assert!(state.validate_options().is_err());
+        state.options.confirm_external_bind = true;
+        assert!(state.validate_options().is_ok());
+        for login in ["user", "user:", ":pass", "us er:pass"] {
+            state.options.rpc_login = login.to_string();
+            assert!(state.validate_options().is_err());
+        }
+    }
+
+    #[test]
+    fn bootstrap_node() {
+        use crate::disk::state::Node;
+        let mut state = Node::default();
+        // the user must opt in, the remote node sees the requests.
+        assert!(
+            state
+                .bootstrap_args(&StartOptionsMode::Simple, None)
+                .is_empty()
+        );
+        state.bootstrap = true;
+        // Simple mode uses the fastest node of the ping, or lets monerod pick one.
+        let fastest = Some("node.sethforprivacy.com:18089".to_string());
+        assert_eq!(
+            state.bootstrap_args(&StartOptionsMode::Simple, fastest),
+            vec![
+                "--bootstrap-daemon-address",
+                "node.sethforprivacy.com:18089"
+            ]
+        );
+        assert_eq!(
+            state.bootstrap_args(&StartOptionsMode::Simple, None),
+            vec!["--bootstrap-daemon-address", "auto"]
+        );
+        assert!(
+            state
+                .bootstrap_args(&StartOptionsMode::Advanced, None)
+                .is_empty()
+        );
+        state.bootstrap = false;
+        assert!(
+            state
+                .bootstrap_args(&StartOptionsMode::Simple, None)
+                .is_empty()
+        );
+        // Advanced mode takes the options.
+        state.options.bootstrap_address = "xmr.example.org:18081".to_string();
+        state.options.bootstrap_login = "user:pass".to_string();
+        state.options.bootstrap_proxy = "127.0.0.1:9050".to_string();
+        assert!(state.validate_options().is_ok());
+        let args = Helper::build_node_args(&state, StartOptionsMode::Advanced).join(" ");
+        assert!(args.contains("--bootstrap-daemon-address xmr.example.org:18081"));
+        assert!(args.contains("--bootstrap-daemon-login user:pass"));
+        assert!(args.contains("--bootstrap-daemon-proxy 127.0.0.1:9050"));
+        for address in [
+            "xmr.example.org",
+            "xmr.example.org:0",
+            "xmr example.org:18081",
+        ] {
+            state.options.bootstrap_address = address.to_string();
+            assert!(state.validate_options().is_err());
+        }
+        state.options.bootstrap_address = "auto".to_string();
+        assert!(state.validate_options().is_ok());
+        state.options.bootstrap_proxy = "localhost:9050".to_string();
+        assert!(state.validate_options().is_err());
+        // a login or proxy without bootstrap daemon is a mistake.
+        state.options.bootstrap_address.clear();
+        state.options.bootstrap_proxy.clear();
+        assert!(state.validate_options().is_err());
+    }
+
+    #[test]
+    fn data_dir_node() {
+        use crate::disk::state::Node;
+        use std::path::{Path, PathBuf};
+        let node_path = Path::new("/opt/gupaxx/node/monerod");
+        let mut state = Node {
+            simple: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            state.data_dir(node_path),
+            Some(PathBuf::from("/opt/gupaxx/node/.bitmonero"))
+        );
+        state.path_db = "/mnt/blockchain".to_string();
+        assert_eq!(
+            state.data_dir(node_path),
+            Some(PathBuf::from("/mnt/blockchain"))
+        );
+        assert!(!state.pruned_db());
+        state.arguments = "--data-dir db --prune-blockchain".to_string();
+        assert_eq!(
+            state.data_dir(node_path),
+            Some(PathBuf::from("/opt/gupaxx/node/db"))
+        );
+        assert!(state.pruned_db());
+        state.simple = true;
+        assert!(state.pruned_db());
+    }
+
+    #[test]
+    fn command_palette_lines() {
+        use crate::helper::commands::{NodeCommand, P2poolCommand, PaletteCommand};
+        let args = |a: &[&str]| a.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            NodeCommand::Status.lines(&[]),
+            Ok(vec!["status".to_string()])
+        );
+        assert_eq!(
+            NodeCommand::Ban.lines(&args(&["1.2.3.0/24", "3600"])),
+            Ok(vec!["ban 1.2.3.0/24 3600".to_string()])
+        );
+        assert_eq!(
+            NodeCommand::Ban.lines(&args(&["1.2.3.4", ""])),
+            Ok(vec!["ban 1.2.3.4".to_string()])
+        );
+        assert!(NodeCommand::Ban.lines(&args(&["1.2.3.4/33", ""])).is_err());
+        assert!(
+            NodeCommand::Unban
+                .lines(&args(&["node.example.com"]))
+                .is_err()
+        );
+        assert_eq!(
+            NodeCommand::SetLimit.lines(&args(&["1024", "4096"])),
+            Ok(vec![
+                "limit_up 1024".to_string(),
+                "limit_down 4096".to_string()
+            ])
+        );
+        assert_eq!(
+            NodeCommand::SetLimit.lines(&args(&["", ""])),
+            Ok(vec!["limit".to_string()])
+        );
+        assert!(NodeCommand::FlushTxpool.lines(&args(&["abc"])).is_err());
+        assert_eq!(
+            NodeCommand::FlushTxpool.lines(&args(&[""])),
+            Ok(vec!["flush_txpool".to_string()])
+        );
+        assert_eq!(
+            P2poolCommand::Peers.lines(&[]),
+            Ok(vec!["peers".to_string()])
+        );
+    }
+
+    #[test]
+    fn command_palette_responses() {
+        use crate::helper::commands::{NodeCommand, P2poolCommand, ResponseTable};
+        let sent = vec!["status".to_string()];
+        let output = "status\n2025-01-01 12:00:00.000\tI Synced 3386880/3386880\nHeight: 3386880/3386880 (100.0%) on mainnet, not mining, 12(out)+0(in) connections\n";
+        let table = ResponseTable::parse(NodeCommand::Status, &sent, output);
+        assert_eq!(
+            table.rows[0],
+            vec!["Height", "3386880/3386880 (100.0%) on mainnet"]
+        );
+        assert_eq!(table.rows[1], vec!["", "not mining"]);
+        assert_eq!(table.rows.len(), 3);
+        let sent = vec!["print_cn".to_string()];
+        let output = "print_cn\nRemote Host      Peer_ID           State     Height\n1.2.3.4:18080    0123456789abcdef  normal    3386880\n\n";
+        let table = ResponseTable::parse(NodeCommand::PrintCn, &sent, output);
+        assert_eq!(
+            table.headers,
+            vec!["Remote Host", "Peer_ID", "State", "Height"]
+        );
+        assert_eq!(
+            table.rows,
+            vec![vec![
+                "1.2.3.4:18080",
+                "0123456789abcdef",
+                "normal",
+                "3386880"
+            ]]
+        );
+        // cells past the headers are joined in the last column.
+        let output = "print_cn\nRemote Host      Peer_ID           State     Height\n1.2.3.4:18080    0123456789abcdef  normal    3386880    12 kB/s    5\n";
+        let table = ResponseTable::parse(NodeCommand::PrintCn, &sent, output);
+        assert_eq!(table.rows[0][3], "3386880  12 kB/s  5");
+        assert_eq!(table.rows[0].len(), 4);
+        let sent = vec!["status".to_string()];
+        let output = "NOTICE  2025-01-01 12:00:00.0000 SideChain status\nMain chain height         = 3386880\nYour shares               = 2 blocks (+0 uncles, 0 orphans)\n";
+        let table = ResponseTable::parse(P2poolCommand::Status, &sent, output);
+        assert_eq!(table.rows[0], vec!["", "SideChain status"]);
+        assert_eq!(table.rows[1], vec!["Main chain height", "3386880"]);
+        assert_eq!(
+            table.rows[2],
+            vec!["Your shares", "2 blocks (+0 uncles, 0 orphans)"]
+        );
+        let table = ResponseTable::parse(
+            NodeCommand::Save,
+            &["save".to_string()],
+            "save\nBlockchain saved\n",
+        );
+        assert_eq!(table.notes, vec!["Blockchain saved"]);
+    }
+
+    #[test]
+    fn proxy_args() {
+        use crate::disk::state::ProxyOptions;
+        let tor = ProxyOptions {
+            enabled: true,
+            address: "127.0.0.1:9050".to_string(),
+            tor: true,
+        };
+        assert!(tor.validate().is_ok());
+        assert_eq!(
+            tor.node_args(),
+            vec![
+                "--proxy",
+                "127.0.0.1:9050",
+                "--tx-proxy",
+                "tor,127.0.0.1:9050,16"
+            ]
+        );
+        assert_eq!(tor.p2pool_args(), vec!["--socks5", "127.0.0.1:9050"]);
+        assert_eq!(tor.xmrig_args(), vec!["--proxy", "127.0.0.1:9050"]);
+        let socks = ProxyOptions {
+            tor: false,
+            ..tor.clone()
+        };
+        assert_eq!(socks.node_args(), vec!["--proxy", "127.0.0.1:9050"]);
+        let invalid = ProxyOptions {
+            address: "localhost:9050".to_string(),
+            ..tor
+        };
+        assert!(invalid.validate().is_err());
+        assert!(ProxyOptions::is_onion(
+            "xmrchain7cnqrrdnsvhwfkudvnoam2hmwbfu6m6s5bxnkzaoomjb2ryd.onion"
+        ));
+        assert!(ProxyOptions::is_local("127.0.0.1"));
+        assert!(!ProxyOptions::is_local("p2pool.io"));
+    }
+
+    #[test]
+    fn proxy_socks5_onion() {
+        use crate::disk::state::ProxyOptions;
+        use crate::miscs::proxied_client;
+        use std::io::{Read, Write};
+        // SOCKS5 stand-in answering the HTTP request itself.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let stand_in = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 512];
+            // greeting, accept without authentication
+            assert!(stream.read(&mut buf).unwrap() > 0);
+            assert_eq!(buf[0], 5);
+            stream.write_all(&[5, 0]).unwrap();
+            // CONNECT with a domain name, the proxy must do the resolution
+            assert!(stream.read(&mut buf).unwrap() > 0);
+            assert_eq!(buf[..4], [5, 1, 0, 3]);
+            let len = buf[4] as usize;
+            let host = String::from_utf8(buf[5..5 + len].to_vec()).unwrap();
+            stream.write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0, 0]).unwrap();
+            assert!(stream.read(&mut buf).unwrap() > 0);
+            let response = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+            stream.write_all(response.as_bytes()).unwrap();
+            host
+        });
+        let options = ProxyOptions {
+            enabled: true,
+            address,
+            tor: true,
+        };
+        let client = proxied_client(Some(&options));
+        let body = get_body(&client, "http://gupaxxtest.onion/");
+        assert_eq!(body, "ok");
+        assert_eq!(stand_in.join().unwrap(), "gupaxxtest.onion");
+    }
+    #[tokio::main]
+    async fn get_body(client: &reqwest::Client, url: &str) -> String {
+        client.get(url).send().await.unwrap().text().await.unwrap()
+    }
+
+    #[test]
+    fn custom_args_ports() {
+        let node = crate::disk::state::Node {
+            simple: false,
+            arguments: "--rpc-bind-port=18089 --zmq-pub tcp://127.0.0.1:18084".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(node.ports(), (18089, 18084));
+        let p2pool = P2pool {
+            simple: false,
+            arguments: "--stratum [::]:3334,0.0.0.0:3334 --mini".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(p2pool.stratum_port(), 3334);
+        let proxy = XmrigProxy {
+            simple: false,
+            arguments: "-b 0.0.0.0:3356 --http-port=18090".to_string(),
+            ..Default::default()
+        };
+        assert_eq!((proxy.bind_port(), proxy.api_port()), (3356, 18090));
+        // flags missing from the custom arguments fall back to the defaults of the binaries.
+        let proxy = XmrigProxy {
+            simple: false,
+            arguments: "--no-color".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            (proxy.bind_port(), proxy.api_port()),
+            (
+                crate::constants::PROXY_PORT_DEFAULT,
+                crate::constants::PROXY_API_PORT_DEFAULT
+            )
+        );
+    }
+
+    #[test]
+    fn advanced_options_p2pool() {
+        // check that the options are given to P2Pool in Advanced mode and are validated.
+        let mut state = P2pool {
+            simple: false,
+            stratum_port: 3334,
+            ..Default::default()
+        };
+        state.options.no_randomx = true;
+        state.options.socks5 = "127.0.0.1:9050".to_string();
+        assert!(state.options.validate().is_ok());
+        let args = Helper::build_p2pool_args(
+            &state,
+            Path::new(""),
+            &None,
+            false,
+            18083,
+            18081,
+            StartOptionsMode::Advanced,
+        )
+        .join(" ");
+        assert!(args.contains("--light-mode --no-randomx --socks5 127.0.0.1:9050"));
+        assert!(args.contains("--stratum 0.0.0.0:3334"));
+        assert!(!args.contains("--onion-address"));
+        // Simple mode doesn't use the options.
+        state.simple = true;
+        let args = Helper::build_p2pool_args(
+            &state,
+            Path::new(""),
+            &None,
+            false,
+            18083,
+            18081,
+            StartOptionsMode::Simple,
+        );
+        assert!(!args.contains(&"--no-randomx".to_string()));
+        // Invalid options
+        state.options.onion_address = "p2pool.onion".to_string();
+        assert!(state.options.validate().is_err());
+        state.options.onion_address = String::new();
+        state.options.socks5 = "127.0.0.1".to_string();
+        assert!(state.options.validate().is_err());
+    }
+
+    #[test]
+    fn failover_pools_xmrig() {
+        // every pool of the chain is a [--url] followed by its own options.
+        use crate::disk::state::{FailoverPool, Xmrig};
+        let state = Xmrig {
+            simple: false,
+            address: "44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW".to_string(),
+            failover: vec![FailoverPool {
+                name: "Backup".to_string(),
+                ip: "pool.example.com".to_string(),
+                port: "443".to_string(),
+                rig: "backup_rig".to_string(),
+                tls: true,
+                keepalive: true,
+                nicehash: true,
+            }],
+            ..Default::default()
+        };
+        let args = Helper::build_xmrig_args(&state, StartOptionsMode::Advanced, 3333).join(" ");
+        assert!(args.contains(&format!(
+            "--url 127.0.0.1:3333 --user {} --rig-id {} --url",
+            state.address, state.rig
+        )));
+        assert!(args.contains(&format!(
+            "--url pool.example.com:443 --user {} --rig-id backup_rig --tls --keepalive --nicehash",
+            state.address
+        )));
+        assert_eq!(args.matches("--url").count(), 2);
+        let urls = state
+            .pool_chain()
+            .iter()
+            .map(|p| p.url())
+            .collect::<Vec<_>>();
+        assert_eq!(urls, vec!["127.0.0.1:3333", "pool.example.com:443"]);
+        // Simple mode mines on the local P2Pool only.
+        let args = Helper::build_xmrig_args(&state, StartOptionsMode::Simple, 3333);
+        assert_eq!(args.iter().filter(|a| *a == "--url").count(), 1);
+    }
+
+    #[test]
+    fn failover_pools_sync() {
+        // the failover pools follow the pool list, and a pool is only once in the chain.
+        use crate::app::panels::middle::common::list_poolnode::PoolNode;
+        use crate::disk::pool::Pool;
+        use crate::disk::state::{FailoverPool, Xmrig};
+        let failover = |name: &str, ip: &str| FailoverPool {
+            name: name.to_string(),
+            ip: ip.to_string(),
+            port: "443".to_string(),
+            rig: "old_rig".to_string(),
+            tls: true,
+            keepalive: false,
+            nicehash: false,
+        };
+        let mut state = Xmrig {
+            nicehash: true,
+            failover: vec![
+                failover("Backup", "old.example.com"),
+                failover("Local", "localhost"),
+                failover("Copy", "pool.example.com"),
+            ],
+            ..Default::default()
+        };
+        state.port = "443".to_string();
+        let pools = vec![(
+            "Backup".to_string(),
+            PoolNode::Pool(Pool {
+                rig: "backup_rig".to_string(),
+                ip: "pool.example.com".to_string(),
+                port: "443".to_string(),
+            }),
+        )];
+        state.sync_failover(&pools);
+        // [Local] is the main pool, [Copy] is now the same pool as [Backup].
+        assert_eq!(state.failover.len(), 1);
+        assert_eq!(state.failover[0].ip, "pool.example.com");
+        assert_eq!(state.failover[0].rig, "backup_rig");
+        assert!(state.failover[0].tls);
+        assert!(!state.failover_accepts("Backup", &pools[0].1));
+        // the main pool has its own Nicehash flag.
+        assert!(state.pool_chain()[0].nicehash);
+        assert!(!state.pool_chain()[1].nicehash);
+    }
+
+    #[test]
+    fn tuning_args_xmrig() {
+        // the priority and the hugepages are options of the advanced mode only.
+        use crate::disk::state::Xmrig;
+        use crate::helper::xrig::config::{XMRIG_PRIORITY_DEFAULT, XmrigTuning};
+        let mut state = Xmrig {
+            priority: 4,
+            huge_pages: false,
+            ..Default::default()
+        };
+        for (simple, mode) in [
+            (true, StartOptionsMode::Simple),
+            (false, StartOptionsMode::Advanced),
+        ] {
+            state.simple = simple;
+            let args = Helper::build_xmrig_args(&state, mode, 3333);
+            assert_eq!(args.contains(&"--cpu-priority".to_string()), !simple);
+            assert_eq!(args.contains(&"--no-huge-pages".to_string()), !simple);
+            assert!(args.contains(&"--threads".to_string()));
+            assert!(!args.contains(&"--cpu-max-threads-hint".to_string()));
+            let tuning = XmrigTuning::from_state(&state);
+            if simple {
+                assert_eq!(tuning.priority, XMRIG_PRIORITY_DEFAULT);
+                assert!(tuning.huge_pages);
+            } else {
+                assert_eq!(tuning.priority, 4);
+                assert!(!tuning.huge_pages);
+            }
+        }
+    }
+
+    #[test]
+    fn merge_mining_p2pool() {
+        // check that merge mining is given to P2Pool in Simple and Advanced mode only if enabled and valid.
+        use crate::disk::state::MergeMine;
+        let address = "12FrDe7ebUeZ5Ppr5xGSCKMEbR5uL3MJvvzh1TDYwXsSgHcXRZxJBcDeKmTeGvznDdALhS6bLaWQQ6JdnsXaj7a6dWs";
+        let mut state = P2pool::default();
+        state.tari().address = address.to_string();
+        for mode in [StartOptionsMode::Simple, StartOptionsMode::Advanced] {
+            let args =
+                Helper::build_p2pool_args(&state, Path::new(""), &None, false, 18083, 18081, mode);
+            assert!(!args.contains(&"--merge-mine".to_string()));
+        }
+        state.tari().enabled = true;
+        assert!(state.validate_merge_mining().is_ok());
+        for mode in [StartOptionsMode::Simple, StartOptionsMode::Advanced] {
+            let args =
+                Helper::build_p2pool_args(&state, Path::new(""), &None, false, 18083, 18081, mode)
+                    .join(" ");
+            assert!(args.contains(&format!("--merge-mine tari://127.0.0.1:18102 {address}")));
+        }
+        state.tari().address = "4abc".to_string();
+        assert!(state.validate_merge_mining().is_err());
+        state.tari().address = address.to_string();
+        // other chains have any scheme and address, and are only used in advanced mode.
+        state.merge_mine.push(MergeMine {
+            enabled: true,
+            host: "127.0.0.1:18102".to_string(),
+            address: "other_address".to_string(),
+        });
+        state.simple = false;
+        assert!(state.validate_merge_mining().is_ok());
+        let args = state
+            .merge_mining_args(&StartOptionsMode::Advanced)
+            .join(" ");
+        assert!(args.ends_with("--merge-mine 127.0.0.1:18102 other_address"));
+        let args = state.merge_mining_args(&StartOptionsMode::Simple);
+        assert_eq!(args.len(), 3);
+        state.merge_mine[1].address = String::new();
+        assert!(state.validate_merge_mining().is_err());
+        state.merge_mine[1].host = "not a host".to_string();
+        assert!(state.validate_merge_mining().is_err());
+    }
+
+    #[test]
+    fn merge_mining_once_p2pool() {
+        // the same node enabled twice is refused, and never given twice to P2Pool.
+        let address = "12FrDe7ebUeZ5Ppr5xGSCKMEbR5uL3MJvvzh1TDYwXsSgHcXRZxJBcDeKmTeGvznDdALhS6bLaWQQ6JdnsXaj7a6dWs";
+        let mut state = P2pool {
+            simple: false,
+            ..Default::default()
+        };
+        state.tari().enabled = true;
+        state.tari().address = address.to_string();
+        state.merge_mine.push(state.merge_mine[0].clone());
+        assert!(state.validate_merge_mining().is_err());
+        let args = Helper::build_p2pool_args(
+            &state,
+            Path::new(""),
+            &None,
+            false,
+            18083,
+            18081,
+            StartOptionsMode::Advanced,
+        );
+        assert_eq!(args.iter().filter(|a| *a == "--merge-mine").count(), 1);
+        assert_eq!(
+            args.iter()
+                .filter(|a| *a == crate::constants::P2POOL_MERGE_MINING_HOST_DEFAULT)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn update_pub_p2pool_peers_from_p2p() {
+        use crate::helper::p2pool::PrivP2PoolP2PApi;
+        let data = r#"{"connections":2,"incoming_connections":1,"peer_list_size":40,"peers":["O,3600,25,P2Pool v4.5,10412345,1.2.3.4:37889","I,60,120,P2Pool v4.4,10412344,5.6.7.8:37890","broken"],"uptime":3700,"zmq_last_active":5}"#;
+        let p2p = PrivP2PoolP2PApi::from_str(data).unwrap();
+        let mut public = PubP2poolApi::new();
+        PubP2poolApi::update_from_p2p(&mut public, p2p);
+        assert_eq!(public.p2p_connected, 2);
+        assert_eq!(public.peers.len(), 2);
+        assert!(!public.peers[0].incoming);
+        assert_eq!(public.peers[0].ping, 25);
+        assert_eq!(public.peers[0].software, "P2Pool v4.5");
+        assert_eq!(public.peers[0].height, 10412345);
+        assert_eq!(public.peers[0].address, "1.2.3.4:37889");
+        assert!(public.peers[1].incoming);
+    }
+
+    #[test]
+    fn pinned_banned_peers_p2pool() {
+        let mut state = P2pool::default();
+        assert!(state.addpeers_args().is_empty());
+        state.toggle_pinned_peer("1.2.3.4:37889");
+        state.toggle_pinned_peer("5.6.7.8:37889");
+        assert_eq!(
+            state.addpeers_args(),
+            vec!["--addpeers", "1.2.3.4:37889,5.6.7.8:37889"]
+        );
+        // banning a pinned peer unpins it.
+        state.toggle_banned_peer("5.6.7.8:37889");
+        assert_eq!(state.addpeers_args(), vec!["--addpeers", "1.2.3.4:37889"]);
+        let args = Helper::build_p2pool_args(
+            &state,
+            Path::new(""),
+            &None,
+            false,
+            18083,
+            18081,
+            StartOptionsMode::Simple,
+        )
+        .join(" ");
+        assert!(args.contains("--addpeers 1.2.3.4:37889"));
+        // banned peers are removed from the bundled list.
+        let bundled = crate::constants::P2POOL_NANO_PEER_LIST
+            .lines()
+            .next()
+            .unwrap();
+        state.toggle_banned_peer(bundled);
+        assert!(!state.nano_peers().lines().any(|p| p == bundled));
+        state.toggle_banned_peer(bundled);
+        assert!(state.nano_peers().lines().any(|p| p == bundled));
+    }
+
+    #[test]
+    fn xmrig_config_tuning() {
+        use crate::helper::xrig::config::{XmrigTuning, XrigConfig};
+        let data = r#"{"api":{"id":null,"worker-id":null},"autosave":true,"cpu":{"enabled":true,"huge-pages":true,"huge-pages-jit":false,"priority":null,"memory-pool":false,"max-threads-hint":100,"asm":true,"*":{"intensity":1,"threads":8,"affinity":-1},"rx":[0,1,2,3]},"pause-on-active":false,"pools":[{"algo":null,"url":"127.0.0.1:3333","user":"Gupaxx","pass":null,"rig-id":null,"nicehash":false,"keepalive":false,"enabled":true,"tls":false,"daemon":false}]}"#;
+        let mut config: XrigConfig = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            config.tuning(),
+            Some(XmrigTuning {
+                threads: 8,
+                priority: 2,
+                huge_pages: true,
+                pause_on_active: 0,
+                affinity: vec![],
+            })
+        );
+        let tuning = XmrigTuning {
+            threads: 4,
+            priority: 1,
+            huge_pages: false,
+            pause_on_active: 30,
+            affinity: vec![],
+        };
+        config.set_tuning(&tuning).unwrap();
+        // the fields not handled by Gupaxx are sent back untouched.
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        assert_eq!(json["cpu"]["*"]["intensity"], 1);
+        assert_eq!(json["cpu"]["*"]["threads"], 4);
+        assert_eq!(json["cpu"]["rx"], "*");
+        assert_eq!(json["cpu"]["huge-pages-jit"], false);
+        assert_eq!(json["cpu"]["max-threads-hint"], 100);
+        assert_eq!(json["pools"][0]["nicehash"], false);
+        assert_eq!(json["api"]["id"], serde_json::Value::Null);
+        assert_eq!(json["pause-on-active"], 30);
+        let mut config: XrigConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.tuning(), Some(tuning.clone()));
+        // pinned threads are written in [cpu.rx], node by node.
+        let pinned = XmrigTuning {
+            threads: 3,
+            affinity: vec![0, 2, 1],
+            ..tuning.clone()
+        };
+        config.set_tuning(&pinned).unwrap();
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["cpu"]["rx"], serde_json::json!([0, 2, 1]));
+        assert_eq!(json["cpu"]["*"]["threads"], 3);
+        assert_eq!(config.tuning(), Some(pinned));
+        // XMRig can give the threads back as [intensity, CPU].
+        config.cpu.as_mut().unwrap().other.insert(
+            "rx".to_string(),
+            serde_json::json!([[1, 0], [1, 2], [1, 1]]),
+        );
+        assert_eq!(config.tuning().unwrap().affinity, vec![0, 2, 1]);
+        // unpinned again, [cpu.rx] uses the profile of every algorithm.
+        config.set_tuning(&tuning).unwrap();
+        assert_eq!(serde_json::to_value(&config).unwrap()["cpu"]["rx"], "*");
+        // the config of XMRig-Proxy has no cpu.
+        let mut proxy: XrigConfig = serde_json::from_str(r#"{"pools":[]}"#).unwrap();
+        assert!(proxy.set_tuning(&tuning).is_err());
+        assert!(proxy.tuning().is_none());
+    }
 }
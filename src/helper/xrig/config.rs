@@ -0,0 +1,269 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Typed model of the config given by the HTTP API of XMRig and XMRig-Proxy ([/1/config]).
+// Only the fields changed by Gupaxx are typed, the others are kept untouched in [other]
+// so that the config sent back is the same as the one read, except for these fields.
+
+use crate::disk::state::Xmrig;
+use crate::helper::Pool;
+use anyhow::Result;
+use anyhow::anyhow;
+use log::info;
+use reqwest::header::AUTHORIZATION;
+use reqwest_middleware::ClientWithMiddleware as Client;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// XMRig considers [pause-on-active: true] as 60 seconds.
+const PAUSE_ON_ACTIVE_DEFAULT: u16 = 60;
+// Priority used by XMRig if it is not in the config.
+// The default priority is not given to XMRig, which keeps the priority of its threads.
+pub const XMRIG_PRIORITY_DEFAULT: u16 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XrigConfig {
+    #[serde(default)]
+    pub pools: Vec<XrigPool>,
+    // absent from the config of XMRig-Proxy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<XrigCpu>,
+    #[serde(rename = "pause-on-active", skip_serializing_if = "Option::is_none")]
+    pub pause_on_active: Option<Switch>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XrigPool {
+    pub url: Option<String>,
+    pub user: Option<String>,
+    #[serde(rename = "rig-id")]
+    pub rig_id: Option<String>,
+    #[serde(default)]
+    pub tls: bool,
+    #[serde(default)]
+    pub keepalive: Switch,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XrigCpu {
+    #[serde(rename = "huge-pages", default = "huge_pages_default")]
+    pub huge_pages: bool,
+    #[serde(default)]
+    pub priority: Option<u16>,
+    // profile used for every algorithm, given by [--threads]
+    #[serde(rename = "*", skip_serializing_if = "Option::is_none")]
+    pub profile: Option<XrigCpuThreads>,
+    // [max-threads-hint] is kept as it is in [other] and is not tuned: XMRig only reads it to generate
+    // the threads of an algorithm without a profile, but Gupaxx always gives one,
+    // with [--threads] or with the [*] or [rx] profile written by the tuning.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+fn huge_pages_default() -> bool {
+    true
+}
+
+// A profile can also be a list of threads or an alias in a custom config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum XrigCpuThreads {
+    Count {
+        threads: u16,
+        #[serde(flatten)]
+        other: Map<String, Value>,
+    },
+    Other(Value),
+}
+
+// Options that can be a boolean or a number of seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Switch {
+    Bool(bool),
+    Seconds(u64),
+}
+
+impl Default for Switch {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+// Settings of the XMRig tab that can be changed while XMRig is running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmrigTuning {
+    pub threads: u16,
+    pub priority: u16,
+    pub huge_pages: bool,
+    // seconds, 0 is disabled
+    pub pause_on_active: u16,
+    // logical CPUs of the threads, written in [cpu.rx]. Empty lets XMRig choose.
+    pub affinity: Vec<u16>,
+}
+
+impl XmrigTuning {
+    // The priority and the hugepages are only given in advanced mode, simple mode keeps the defaults of XMRig.
+    pub fn from_state(state: &Xmrig) -> Self {
+        Self {
+            threads: state.threads(),
+            priority: if state.simple {
+                XMRIG_PRIORITY_DEFAULT
+            } else {
+                state.priority
+            },
+            huge_pages: state.simple || state.huge_pages,
+            pause_on_active: state.pause,
+            affinity: state.pinned().to_vec(),
+        }
+    }
+}
+
+impl XrigConfig {
+    // point the first pool to the node, used by the XvB algorithm and the failovers.
+    pub fn set_pool(&mut self, node: &Pool, address: &str, rig: &str) -> Result<()> {
+        let pool = self
+            .pools
+            .first_mut()
+            .ok_or_else(|| anyhow!("pools/0 does not exist in xmrig config"))?;
+        pool.url = Some([node.url(), ":".to_string(), node.port()].concat());
+        pool.user = Some(node.user(&address.chars().take(8).collect::<String>()));
+        pool.rig_id = Some(rig.to_string());
+        pool.tls = node.tls();
+        pool.keepalive = Switch::Bool(node.keepalive());
+        Ok(())
+    }
+    pub fn set_tuning(&mut self, tuning: &XmrigTuning) -> Result<()> {
+        let cpu = self
+            .cpu
+            .as_mut()
+            .ok_or_else(|| anyhow!("cpu does not exist in xmrig config"))?;
+        cpu.huge_pages = tuning.huge_pages;
+        cpu.priority = (tuning.priority != XMRIG_PRIORITY_DEFAULT).then_some(tuning.priority);
+        match &mut cpu.profile {
+            Some(XrigCpuThreads::Count { threads, .. }) => *threads = tuning.threads,
+            _ => {
+                cpu.profile = Some(XrigCpuThreads::Count {
+                    threads: tuning.threads,
+                    other: Map::from_iter([("affinity".to_string(), Value::from(-1))]),
+                })
+            }
+        }
+        if !tuning.affinity.is_empty() {
+            // one thread pinned to each CPU, for RandomX.
+            let threads = tuning
+                .affinity
+                .iter()
+                .map(|cpu| Value::from(*cpu))
+                .collect();
+            cpu.other.insert("rx".to_string(), Value::Array(threads));
+        } else if cpu.other.contains_key("rx") {
+            // a profile generated for RandomX would be used instead of the one for every algorithm.
+            cpu.other.insert("rx".to_string(), Value::from("*"));
+        }
+        self.pause_on_active = Some(if tuning.pause_on_active == 0 {
+            Switch::Bool(false)
+        } else {
+            Switch::Seconds(tuning.pause_on_active.into())
+        });
+        Ok(())
+    }
+    // None if the config does not have a number of threads for every algorithm.
+    pub fn tuning(&self) -> Option<XmrigTuning> {
+        let cpu = self.cpu.as_ref()?;
+        // threads are either a list in [cpu.rx] or a number for every algorithm.
+        let (threads, affinity) = match (cpu.other.get("rx"), &cpu.profile) {
+            (Some(Value::Array(rx)), _) => {
+                // a thread is its CPU, or [intensity, CPU]. -1 is not pinned.
+                let affinity = rx
+                    .iter()
+                    .filter_map(|thread| match thread {
+                        Value::Array(thread) => thread.get(1).and_then(Value::as_u64),
+                        thread => thread.as_u64(),
+                    })
+                    .map(|cpu| cpu as u16)
+                    .collect();
+                (rx.len() as u16, affinity)
+            }
+            (_, Some(XrigCpuThreads::Count { threads, .. })) => (*threads, vec![]),
+            _ => return None,
+        };
+        let pause_on_active = match self.pause_on_active.unwrap_or_default() {
+            Switch::Bool(false) => 0,
+            Switch::Bool(true) => PAUSE_ON_ACTIVE_DEFAULT,
+            Switch::Seconds(s) => s.min(u16::MAX.into()) as u16,
+        };
+        Some(XmrigTuning {
+            threads,
+            priority: cpu.priority.unwrap_or(XMRIG_PRIORITY_DEFAULT),
+            huge_pages: cpu.huge_pages,
+            pause_on_active,
+            affinity,
+        })
+    }
+}
+
+pub async fn get_xrig_config(client: &Client, api_uri: &str, token: &str) -> Result<XrigConfig> {
+    let request = client
+        .get(api_uri)
+        .header(AUTHORIZATION, ["Bearer ", token].concat());
+    Ok(request.send().await?.json::<XrigConfig>().await?)
+}
+
+pub async fn put_xrig_config(
+    client: &Client,
+    api_uri: &str,
+    token: &str,
+    config: &XrigConfig,
+) -> Result<()> {
+    client
+        .put(api_uri)
+        .header(AUTHORIZATION, ["Bearer ", token].concat())
+        .header("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(5))
+        .body(serde_json::to_string(config)?)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+// Push the tuning to XMRig and read the config back to confirm it was applied.
+pub async fn tune_xmrig_config(
+    client: &Client,
+    api_uri: &str,
+    token: &str,
+    tuning: &XmrigTuning,
+) -> Result<XmrigTuning> {
+    let mut config = get_xrig_config(client, api_uri, token).await?;
+    config.set_tuning(tuning)?;
+    info!("replace xmrig from api url {api_uri} config with tuning {tuning:?}");
+    put_xrig_config(client, api_uri, token, &config).await?;
+    let applied = get_xrig_config(client, api_uri, token)
+        .await?
+        .tuning()
+        .ok_or_else(|| anyhow!("threads are missing from xmrig config"))?;
+    if applied != *tuning {
+        return Err(anyhow!("xmrig config is {applied:?} instead of {tuning:?}"));
+    }
+    Ok(applied)
+}
@@ -0,0 +1,285 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Details of XMRig given by the full [/1/summary] and by [/2/backends],
+// shown in the XMRig submenu of the Status tab.
+// Every field has a default, XMRig leaves some of them out depending on its version and build.
+
+use crate::utils::constants::{
+    STATUS_XMRIG_DETAIL_HUGEPAGES_WARNING, STATUS_XMRIG_DETAIL_MSR_WARNING,
+    STATUS_XMRIG_DETAIL_PAUSED_WARNING,
+};
+use anyhow::Result;
+use reqwest::header::AUTHORIZATION;
+use reqwest_middleware::ClientWithMiddleware as Client;
+use serde::{Deserialize, Serialize};
+
+//---------------------------------------------------------------------------------------------------- [/1/summary]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy)]
+pub(super) struct Memory {
+    #[serde(default)]
+    pub free: u64,
+    #[serde(default)]
+    pub total: u64,
+    #[serde(default)]
+    pub resident_set_memory: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(super) struct SummaryCpu {
+    #[serde(default)]
+    pub brand: String,
+    // preset of the MSR mod for this CPU, [none] if there is no preset.
+    #[serde(default)]
+    pub msr: String,
+    #[serde(default)]
+    pub assembly: String,
+    #[serde(default)]
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(super) struct SummaryResults {
+    #[serde(default)]
+    pub shares_good: u64,
+    #[serde(default)]
+    pub shares_total: u64,
+    #[serde(default)]
+    pub avg_time_ms: u64,
+    #[serde(default)]
+    pub best: Vec<u64>,
+}
+
+// [true]/[false] in older versions, [allocated, total] pages since 6.x.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum HugePages {
+    Pages([u64; 2]),
+    Enabled(bool),
+}
+
+impl HugePages {
+    pub fn available(&self) -> bool {
+        match self {
+            Self::Pages([allocated, total]) => *total != 0 && allocated >= total,
+            Self::Enabled(enabled) => *enabled,
+        }
+    }
+}
+
+impl std::fmt::Display for HugePages {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Pages([allocated, total]) => {
+                let percent = if *total == 0 {
+                    0.0
+                } else {
+                    *allocated as f32 / *total as f32 * 100.0
+                };
+                write!(f, "{allocated}/{total} ({percent:.0}%)")
+            }
+            Self::Enabled(true) => write!(f, "Enabled"),
+            Self::Enabled(false) => write!(f, "Disabled"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [/2/backends]
+// e.g: [wget -qO- localhost:18088/2/backends].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(super) struct PrivXmrigBackend {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub msr: Option<bool>,
+    #[serde(default)]
+    pub hugepages: Option<HugePages>,
+    // bytes used by the threads and the dataset
+    #[serde(default)]
+    pub memory: u64,
+    #[serde(default)]
+    pub threads: Vec<BackendThread>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(super) struct BackendThread {
+    #[serde(default)]
+    pub affinity: i64,
+    // 10s/60s/15m, [null] until measured
+    #[serde(default)]
+    pub hashrate: Vec<Option<f32>>,
+}
+
+impl PrivXmrigBackend {
+    pub(super) async fn request_backends(
+        client: &Client,
+        api_uri: &str,
+        token: &str,
+    ) -> Result<Vec<Self>> {
+        let request = client
+            .get(api_uri)
+            .header(AUTHORIZATION, ["Bearer ", token].concat());
+        Ok(request
+            .timeout(std::time::Duration::from_millis(5000))
+            .send()
+            .await?
+            .json()
+            .await?)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Public detail
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThreadHashrate {
+    // None if the thread is not pinned
+    pub cpu: Option<u16>,
+    pub hashrate: [Option<f32>; 3],
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmrigDetail {
+    pub version: String,
+    pub algo: String,
+    pub paused: bool,
+    // features of the build of XMRig (tls, hwloc, opencl...)
+    pub features: Vec<String>,
+    pub cpu_brand: String,
+    pub cpu_flags: Vec<String>,
+    pub assembly: String,
+    pub msr_preset: String,
+    // None until [/2/backends] answered
+    pub msr: Option<bool>,
+    pub hugepages: Option<HugePages>,
+    // bytes
+    pub memory_rss: u64,
+    pub memory_backend: u64,
+    pub memory_free: u64,
+    pub memory_total: u64,
+    pub threads: Vec<ThreadHashrate>,
+    pub pool: String,
+    pub tls: Option<String>,
+    pub ping_ms: u64,
+    pub pool_uptime: u64,
+    pub failures: u64,
+    pub share_time_ms: u64,
+    pub shares_good: u64,
+    pub shares_total: u64,
+    pub best_diff: u64,
+}
+
+impl XmrigDetail {
+    // Per-thread hashrate, hugepages, memory and MSR status of the CPU backend.
+    pub(super) fn update_from_backends(&mut self, backends: Vec<PrivXmrigBackend>) {
+        let Some(cpu) = backends.into_iter().find(|b| b.kind == "cpu" && b.enabled) else {
+            return;
+        };
+        self.msr = cpu.msr;
+        if cpu.hugepages.is_some() {
+            self.hugepages = cpu.hugepages;
+        }
+        self.memory_backend = cpu.memory;
+        self.threads = cpu
+            .threads
+            .into_iter()
+            .map(|thread| ThreadHashrate {
+                cpu: u16::try_from(thread.affinity).ok(),
+                hashrate: [0, 1, 2].map(|i| thread.hashrate.get(i).copied().flatten()),
+            })
+            .collect();
+    }
+    // What lowers the hashrate, empty if everything is fine.
+    pub fn warnings(&self) -> Vec<&'static str> {
+        let mut warnings = vec![];
+        if self.hugepages.is_some_and(|h| !h.available()) {
+            warnings.push(STATUS_XMRIG_DETAIL_HUGEPAGES_WARNING);
+        }
+        // without a preset for this CPU, the MSR mod does not apply.
+        let preset = !self.msr_preset.is_empty() && self.msr_preset != "none";
+        if preset && self.msr == Some(false) {
+            warnings.push(STATUS_XMRIG_DETAIL_MSR_WARNING);
+        }
+        if self.paused {
+            warnings.push(STATUS_XMRIG_DETAIL_PAUSED_WARNING);
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn xmrig_backends() {
+        let data = r#"[
+		    {
+		        "type": "cpu",
+		        "enabled": true,
+		        "algo": "rx/0",
+		        "profile": "rx",
+		        "hw-aes": true,
+		        "priority": -1,
+		        "msr": false,
+		        "asm": "ryzen",
+		        "argon2-impl": "AVX2",
+		        "hugepages": [1168, 1176],
+		        "memory": 2432696320,
+		        "hashrate": [2100.5, 2098.1, null],
+		        "threads": [
+		            {"intensity": 1, "affinity": 0, "av": 1, "hashrate": [1050.2, 1049.0, null]},
+		            {"intensity": 1, "affinity": -1, "av": 1, "hashrate": [1050.3, 1049.1, null]}
+		        ]
+		    },
+		    {"type": "opencl", "enabled": false, "algo": null, "profile": null, "platform": null}
+		]"#;
+        let backends = serde_json::from_str::<Vec<PrivXmrigBackend>>(data).unwrap();
+        let mut detail = XmrigDetail {
+            msr_preset: "ryzen_19h".to_string(),
+            hugepages: Some(HugePages::Enabled(true)),
+            ..Default::default()
+        };
+        detail.update_from_backends(backends);
+        assert_eq!(detail.hugepages, Some(HugePages::Pages([1168, 1176])));
+        assert_eq!(detail.memory_backend, 2432696320);
+        assert_eq!(
+            detail.threads,
+            vec![
+                ThreadHashrate {
+                    cpu: Some(0),
+                    hashrate: [Some(1050.2), Some(1049.0), None]
+                },
+                ThreadHashrate {
+                    cpu: None,
+                    hashrate: [Some(1050.3), Some(1049.1), None]
+                },
+            ]
+        );
+        assert_eq!(
+            detail.warnings(),
+            vec![
+                STATUS_XMRIG_DETAIL_HUGEPAGES_WARNING,
+                STATUS_XMRIG_DETAIL_MSR_WARNING
+            ]
+        );
+        // no MSR preset for this CPU, all hugepages allocated.
+        detail.msr_preset = "none".to_string();
+        detail.hugepages = Some(HugePages::Pages([1176, 1176]));
+        assert!(detail.warnings().is_empty());
+    }
+}
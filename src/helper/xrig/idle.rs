@@ -0,0 +1,435 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Idle-only mining: XMRig is paused or throttled while the computer is in use.
+// The Helper reads the load, the running programs and the power supply into [Sys] every second,
+// the XMRig watchdog decides with the [IdlePolicy] of the XMRig tab and applies it with the HTTP API.
+// [--pause-on-active] of XMRig only watches the input on Windows, this works everywhere.
+
+use crate::disk::state::IdlePolicy;
+use crate::helper::xrig::config::{XmrigTuning, tune_xmrig_config};
+use anyhow::{Result, anyhow};
+use reqwest::header::AUTHORIZATION;
+use reqwest_middleware::ClientWithMiddleware as Client;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "linux")]
+const POWER_SUPPLY_PATH: &str = "/sys/class/power_supply";
+
+//---------------------------------------------------------------------------------------------------- Inputs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdleInputs {
+    // % of the CPU used by the programs other than XMRig
+    pub load: f32,
+    // lowercase names of the running programs
+    pub processes: BTreeSet<String>,
+    // None without a battery
+    pub on_battery: Option<bool>,
+}
+
+impl IdleInputs {
+    pub fn from_sysinfo(sysinfo: &sysinfo::System, max_threads: u16) -> Self {
+        let mut processes = BTreeSet::new();
+        let mut xmrig_usage = 0.0;
+        for process in sysinfo.processes().values() {
+            let name = process.name().to_string_lossy().to_lowercase();
+            // XMRig-Proxy does not use the CPU, only XMRig is taken out of the load.
+            if name.starts_with("xmrig") && !name.starts_with("xmrig-proxy") {
+                xmrig_usage += process.cpu_usage();
+            }
+            processes.insert(name);
+        }
+        let threads = max_threads.max(1) as f32;
+        let total = sysinfo
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage())
+            .sum::<f32>()
+            / threads;
+        #[cfg(target_os = "linux")]
+        let on_battery = Self::on_battery(Path::new(POWER_SUPPLY_PATH));
+        #[cfg(not(target_os = "linux"))]
+        let on_battery = None;
+        Self {
+            load: (total - xmrig_usage / threads).max(0.0),
+            processes,
+            on_battery,
+        }
+    }
+    // Refresh of the processes done by the Helper every second.
+    // The programs that exited are removed, or a closed program would keep XMRig paused.
+    pub fn refresh_processes(sysinfo: &mut sysinfo::System) {
+        sysinfo.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::All,
+            true,
+            sysinfo::ProcessRefreshKind::nothing().with_cpu(),
+        );
+    }
+    // [root] is [/sys/class/power_supply], given for the tests.
+    pub fn on_battery(root: &Path) -> Option<bool> {
+        let read = |path: &Path, file: &str| {
+            fs::read_to_string(path.join(file))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default()
+        };
+        let mut battery = false;
+        let mut discharging = false;
+        let mut mains = None;
+        for entry in fs::read_dir(root).ok()?.flatten() {
+            let path = entry.path();
+            match read(&path, "type").as_str() {
+                "Battery" => {
+                    battery = true;
+                    discharging |= read(&path, "status") == "Discharging";
+                }
+                "Mains" => *mains.get_or_insert(false) |= read(&path, "online") == "1",
+                _ => (),
+            }
+        }
+        if !battery {
+            return None;
+        }
+        Some(match mains {
+            Some(online) => !online,
+            None => discharging,
+        })
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdleReason {
+    Battery,
+    Process(String),
+    Load(f32),
+}
+
+impl std::fmt::Display for IdleReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Battery => write!(f, "running on battery"),
+            Self::Process(name) => write!(f, "{name} is running"),
+            Self::Load(load) => write!(f, "CPU used at {load:.0}% by other programs"),
+        }
+    }
+}
+
+impl IdlePolicy {
+    // Why the computer is in use, None if it is idle.
+    pub fn busy_reason(&self, inputs: &IdleInputs) -> Option<IdleReason> {
+        if self.pause_on_battery && inputs.on_battery == Some(true) {
+            return Some(IdleReason::Battery);
+        }
+        for name in self.processes.split(',') {
+            let name = name.trim().to_lowercase();
+            if name.is_empty() {
+                continue;
+            }
+            // names on Windows end with [.exe].
+            if inputs.processes.contains(&name)
+                || inputs.processes.contains(&[&name, ".exe"].concat())
+            {
+                return Some(IdleReason::Process(name));
+            }
+        }
+        (inputs.load > self.max_load as f32).then_some(IdleReason::Load(inputs.load))
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdleAction {
+    #[default]
+    Mine,
+    // number of threads kept
+    Throttle(u16),
+    Pause,
+}
+
+impl IdleAction {
+    // The action leaving the least to XMRig, when the idle policy and the thermal guard both apply.
+    pub fn strictest(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Pause, _) | (_, Self::Pause) => Self::Pause,
+            (Self::Throttle(a), Self::Throttle(b)) => Self::Throttle(a.min(b)),
+            (Self::Throttle(threads), Self::Mine) | (Self::Mine, Self::Throttle(threads)) => {
+                Self::Throttle(threads)
+            }
+            (Self::Mine, Self::Mine) => Self::Mine,
+        }
+    }
+}
+
+// State of the policy in the XMRig watchdog, shown in the Status tab.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdleGuard {
+    pub enabled: bool,
+    pub action: IdleAction,
+    // why XMRig is paused or throttled
+    pub reason: Option<IdleReason>,
+    // last time the computer was in use
+    busy: Option<Instant>,
+}
+
+impl IdleGuard {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+    // The action to apply to XMRig, None if it does not change.
+    // XMRig mines again only once the computer stayed idle for [resume_after] seconds.
+    // Without live tuning (custom arguments), XMRig is paused instead of throttled.
+    pub fn update(
+        &mut self,
+        policy: &IdlePolicy,
+        reason: Option<IdleReason>,
+        can_throttle: bool,
+        now: Instant,
+    ) -> Option<IdleAction> {
+        let action = match &reason {
+            Some(reason) => {
+                self.busy = Some(now);
+                if policy.busy_threads == 0 || !can_throttle || *reason == IdleReason::Battery {
+                    IdleAction::Pause
+                } else {
+                    IdleAction::Throttle(policy.busy_threads)
+                }
+            }
+            None if self.busy.is_some_and(|busy| {
+                now.duration_since(busy) < Duration::from_secs(policy.resume_after.into())
+            }) =>
+            {
+                self.action
+            }
+            None => IdleAction::Mine,
+        };
+        if reason.is_some() || action == IdleAction::Mine {
+            self.reason = reason;
+        }
+        if action == self.action {
+            return None;
+        }
+        self.action = action;
+        Some(action)
+    }
+}
+
+impl std::fmt::Display for IdleGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let reason = self
+            .reason
+            .as_ref()
+            .map_or(String::new(), |r| format!(": {r}"));
+        match self.action {
+            _ if !self.enabled => write!(f, "Mining, idle policy disabled"),
+            IdleAction::Mine => write!(f, "Mining, the computer is idle"),
+            IdleAction::Throttle(threads) => write!(f, "Throttled to {threads} threads{reason}"),
+            IdleAction::Pause => write!(f, "Paused{reason}"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Apply
+// Send [pause] or [resume] to [/json_rpc] of XMRig, it needs [--http-no-restricted].
+pub async fn xmrig_json_rpc(
+    client: &Client,
+    api_uri: &str,
+    token: &str,
+    method: &str,
+) -> Result<()> {
+    let body = serde_json::json!({"id": 1, "jsonrpc": "2.0", "method": method});
+    client
+        .post(api_uri)
+        .header(AUTHORIZATION, ["Bearer ", token].concat())
+        .header("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(5))
+        .body(body.to_string())
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+// Move XMRig from an action to another. [full] is the tuning of XMRig when it is not throttled.
+// Returns the number of threads XMRig uses after it.
+pub async fn apply_idle_action(
+    client: &Client,
+    api_uri_json_rpc: &str,
+    api_uri_config: &str,
+    token: &str,
+    from: IdleAction,
+    to: IdleAction,
+    full: Option<&XmrigTuning>,
+) -> Result<Option<u16>> {
+    let mut threads = None;
+    if from == IdleAction::Pause {
+        xmrig_json_rpc(client, api_uri_json_rpc, token, "resume").await?;
+    }
+    match (from, to) {
+        (_, IdleAction::Throttle(busy_threads)) => {
+            let full = full.ok_or_else(|| anyhow!("threads of xmrig can not be changed live"))?;
+            let mut tuning = full.clone();
+            tuning.threads = busy_threads.min(full.threads);
+            tuning.affinity.truncate(tuning.threads.into());
+            threads = Some(
+                tune_xmrig_config(client, api_uri_config, token, &tuning)
+                    .await?
+                    .threads,
+            );
+        }
+        (IdleAction::Throttle(_), _) => {
+            if let Some(full) = full {
+                threads = Some(
+                    tune_xmrig_config(client, api_uri_config, token, full)
+                        .await?
+                        .threads,
+                );
+            }
+        }
+        _ => (),
+    }
+    if to == IdleAction::Pause {
+        xmrig_json_rpc(client, api_uri_json_rpc, token, "pause").await?;
+    }
+    Ok(threads)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn idle_policy() {
+        let policy = IdlePolicy {
+            enabled: true,
+            max_load: 30,
+            processes: "Steam, blender ,".to_string(),
+            pause_on_battery: true,
+            busy_threads: 2,
+            resume_after: 60,
+        };
+        let mut inputs = IdleInputs {
+            load: 10.0,
+            processes: BTreeSet::from(["bash".to_string(), "xmrig".to_string()]),
+            on_battery: Some(false),
+        };
+        assert_eq!(policy.busy_reason(&inputs), None);
+        inputs.processes.insert("steam.exe".to_string());
+        assert_eq!(
+            policy.busy_reason(&inputs),
+            Some(IdleReason::Process("steam".to_string()))
+        );
+        inputs.on_battery = Some(true);
+        assert_eq!(policy.busy_reason(&inputs), Some(IdleReason::Battery));
+        inputs.on_battery = None;
+        inputs.processes.clear();
+        inputs.load = 50.0;
+        assert_eq!(policy.busy_reason(&inputs), Some(IdleReason::Load(50.0)));
+
+        let start = Instant::now();
+        let mut guard = IdleGuard::new(true);
+        assert_eq!(guard.update(&policy, None, true, start), None);
+        assert_eq!(
+            guard.update(&policy, Some(IdleReason::Load(50.0)), true, start),
+            Some(IdleAction::Throttle(2))
+        );
+        // no live tuning, paused instead.
+        let mut custom = IdleGuard::new(true);
+        assert_eq!(
+            custom.update(&policy, Some(IdleReason::Load(50.0)), false, start),
+            Some(IdleAction::Pause)
+        );
+        assert_eq!(
+            guard.update(&policy, Some(IdleReason::Battery), true, start),
+            Some(IdleAction::Pause)
+        );
+        assert_eq!(guard.to_string(), "Paused: running on battery");
+        // idle, but not for long enough.
+        let later = start + Duration::from_secs(30);
+        assert_eq!(guard.update(&policy, None, true, later), None);
+        assert_eq!(guard.action, IdleAction::Pause);
+        let later = start + Duration::from_secs(60);
+        assert_eq!(
+            guard.update(&policy, None, true, later),
+            Some(IdleAction::Mine)
+        );
+        assert_eq!(guard.reason, None);
+
+        use IdleAction::*;
+        assert_eq!(Mine.strictest(Throttle(4)), Throttle(4));
+        assert_eq!(Throttle(2).strictest(Throttle(4)), Throttle(2));
+        assert_eq!(Throttle(2).strictest(Pause), Pause);
+        assert_eq!(Mine.strictest(Mine), Mine);
+    }
+
+    #[test]
+    fn power_supply() {
+        let root = std::env::temp_dir().join(format!("gupaxx_power_supply_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let write = |name: &str, files: &[(&str, &str)]| {
+            fs::create_dir_all(root.join(name)).unwrap();
+            for (file, content) in files {
+                fs::write(root.join(name).join(file), content).unwrap();
+            }
+        };
+        // a desktop, without battery.
+        write("AC", &[("type", "Mains\n"), ("online", "1\n")]);
+        assert_eq!(IdleInputs::on_battery(&root), None);
+        write("BAT0", &[("type", "Battery\n"), ("status", "Charging\n")]);
+        assert_eq!(IdleInputs::on_battery(&root), Some(false));
+        write("AC", &[("online", "0\n")]);
+        assert_eq!(IdleInputs::on_battery(&root), Some(true));
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(IdleInputs::on_battery(&root), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn exited_process() {
+        use sysinfo::System;
+        // a copy of [sleep] with a name that no other program has.
+        let dir = std::env::temp_dir().join(format!("gupaxx_idle_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let program = dir.join("gxidletest");
+        fs::copy("/bin/sleep", &program).unwrap();
+        let mut child = std::process::Command::new(&program)
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let mut sysinfo = System::new();
+        IdleInputs::refresh_processes(&mut sysinfo);
+        assert!(
+            IdleInputs::from_sysinfo(&sysinfo, 1)
+                .processes
+                .contains("gxidletest")
+        );
+        child.kill().unwrap();
+        child.wait().unwrap();
+        IdleInputs::refresh_processes(&mut sysinfo);
+        assert!(
+            !IdleInputs::from_sysinfo(&sysinfo, 1)
+                .processes
+                .contains("gxidletest")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -19,16 +19,20 @@ use crate::XMRIG_API_CONFIG_ENDPOINT;
 use crate::XMRIG_API_SUMMARY_ENDPOINT;
 use crate::helper::Pool;
 use anyhow::Result;
-use anyhow::anyhow;
+use config::{get_xrig_config, put_xrig_config};
 use log::info;
-use reqwest::header::AUTHORIZATION;
 use reqwest_middleware::ClientWithMiddleware as Client;
 use serde::Deserialize;
 use serde::Serialize;
-use serde_json::Value;
 use xmrig::ImgXmrig;
 use xmrig_proxy::ImgProxy;
 
+pub mod config;
+pub mod detail;
+pub mod idle;
+pub mod pool_errors;
+pub mod proxy_workers;
+pub mod thermal;
 pub mod xmrig;
 pub mod xmrig_proxy;
 
@@ -42,44 +46,16 @@ pub async fn update_xmrig_config(
     rig: &str,
 ) -> Result<()> {
     // get config
-    let request = client
-        .get(api_uri)
-        .header(AUTHORIZATION, ["Bearer ", token].concat());
-    let mut config = request.send().await?.json::<Value>().await?;
+    let mut config = get_xrig_config(client, api_uri, token).await?;
     // modify node configuration
-    let uri = [node.url(), ":".to_string(), node.port()].concat();
     info!(
-        "replace xmrig from api url {api_uri} config with node {}",
-        uri
+        "replace xmrig from api url {api_uri} config with node {}:{}",
+        node.url(),
+        node.port()
     );
-    *config
-        .pointer_mut("/pools/0/url")
-        .ok_or_else(|| anyhow!("pools/0/url does not exist in xmrig config"))? = uri.into();
-    *config
-        .pointer_mut("/pools/0/user")
-        .ok_or_else(|| anyhow!("pools/0/user does not exist in xmrig config"))? = node
-        .user(&address.chars().take(8).collect::<String>())
-        .into();
-    *config
-        .pointer_mut("/pools/0/rig-id")
-        .ok_or_else(|| anyhow!("pools/0/rig-id does not exist in xmrig config"))? = rig.into();
-    *config
-        .pointer_mut("/pools/0/tls")
-        .ok_or_else(|| anyhow!("pools/0/tls does not exist in xmrig config"))? = node.tls().into();
-    *config
-        .pointer_mut("/pools/0/keepalive")
-        .ok_or_else(|| anyhow!("pools/0/keepalive does not exist in xmrig config"))? =
-        node.keepalive().into();
+    config.set_pool(node, address, rig)?;
     // send new config
-    client
-        .put(api_uri)
-        .header("Authorization", ["Bearer ", token].concat())
-        .header("Content-Type", "application/json")
-        .timeout(std::time::Duration::from_secs(5))
-        .body(config.to_string())
-        .send()
-        .await?;
-    anyhow::Ok(())
+    put_xrig_config(client, api_uri, token, &config).await
 }
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 struct Hashrate {
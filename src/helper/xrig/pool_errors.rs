@@ -0,0 +1,276 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Pool errors found in the output of XMRig and XMRig-Proxy, classified and counted per pool.
+// Shown in the diagnostics of the Status tab and read by the XvB algorithm to avoid a broken pool.
+
+use crate::helper::Pool;
+use crate::utils::constants::{
+    POOL_ERROR_CONN_REFUSED, POOL_ERROR_JOB_TIMEOUT, POOL_ERROR_LOGIN, POOL_ERROR_LOW_DIFF,
+    POOL_ERROR_OTHER, POOL_ERROR_TLS,
+};
+use derive_more::Display;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+use strum::{EnumCount, EnumIter};
+
+// Number of errors kept for the diagnostics.
+pub const POOL_ERRORS_LAST: usize = 50;
+// Blocking errors in a row after which a pool is considered broken.
+pub const POOL_ERRORS_BROKEN: u32 = 3;
+// A broken pool is tried again once it had no blocking error for this long.
+pub const POOL_ERRORS_BROKEN_DURATION: Duration = Duration::from_secs(600);
+
+// [2025-01-12 12:00:00.000]  net      eu.xmrvsbeast.com:4247 login error code: 6
+static LINE_POOL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?P<pool>[a-zA-Z0-9-]+(?:\.[a-zA-Z0-9-]+)+:\d{1,5})").unwrap());
+static LINE_NEW_JOB: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"new job from (?P<pool>[a-zA-Z0-9-]+(?:\.[a-zA-Z0-9-]+)+:\d{1,5})").unwrap()
+});
+static LINE_ERROR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(error|rejected|refused|timeout|timed out|fail)").unwrap());
+// status line of XMRig-Proxy, [error: 1] is the number of upstreams in error.
+static LINE_PROXY_STATUS: Lazy<Regex> = Lazy::new(|| Regex::new(r"upstreams active").unwrap());
+static LOGIN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(login error|unauthenticated|invalid (payment )?address|invalid user)")
+        .unwrap()
+});
+static TLS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(tls|ssl|handshake|certificate|fingerprint)").unwrap());
+static CONN_REFUSED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(connection refused|connect error)").unwrap());
+static LOW_DIFF: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)low difficulty").unwrap());
+static JOB_TIMEOUT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)(timeout|timed out)").unwrap());
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, EnumIter, EnumCount)]
+pub enum PoolErrorKind {
+    #[display("Login rejected")]
+    LoginRejected,
+    #[display("TLS failure")]
+    Tls,
+    #[display("Connection refused")]
+    ConnectionRefused,
+    #[display("Low difficulty share")]
+    LowDifficulty,
+    #[display("Job timeout")]
+    JobTimeout,
+    #[display("Other")]
+    Other,
+}
+
+impl PoolErrorKind {
+    // None if the line is not an error about a pool or a share.
+    pub fn classify(line: &str) -> Option<Self> {
+        if !LINE_ERROR.is_match(line) || LINE_PROXY_STATUS.is_match(line) {
+            return None;
+        }
+        // errors of other modules (msr, randomx...) do not name a pool.
+        if !LINE_POOL.is_match(line) && !line.contains("rejected") {
+            return None;
+        }
+        Some(if LOGIN.is_match(line) {
+            Self::LoginRejected
+        } else if TLS.is_match(line) {
+            Self::Tls
+        } else if CONN_REFUSED.is_match(line) {
+            Self::ConnectionRefused
+        } else if LOW_DIFF.is_match(line) {
+            Self::LowDifficulty
+        } else if JOB_TIMEOUT.is_match(line) {
+            Self::JobTimeout
+        } else {
+            Self::Other
+        })
+    }
+    pub const fn suggestion(&self) -> &'static str {
+        match self {
+            Self::LoginRejected => POOL_ERROR_LOGIN,
+            Self::Tls => POOL_ERROR_TLS,
+            Self::ConnectionRefused => POOL_ERROR_CONN_REFUSED,
+            Self::LowDifficulty => POOL_ERROR_LOW_DIFF,
+            Self::JobTimeout => POOL_ERROR_JOB_TIMEOUT,
+            Self::Other => POOL_ERROR_OTHER,
+        }
+    }
+    // the pool can not be mined on until it sends a job again.
+    pub const fn is_blocking(&self) -> bool {
+        matches!(
+            self,
+            Self::LoginRejected | Self::Tls | Self::ConnectionRefused
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolError {
+    // local time of the error
+    pub time: String,
+    pub pool: String,
+    pub kind: PoolErrorKind,
+    pub line: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolCounters {
+    // indexed by [PoolErrorKind]
+    pub counts: [u32; PoolErrorKind::COUNT],
+    // blocking errors since the last job of the pool
+    pub blocking: u32,
+    pub last_blocking: Option<Instant>,
+}
+
+impl PoolCounters {
+    pub fn count(&self, kind: PoolErrorKind) -> u32 {
+        self.counts[kind as usize]
+    }
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolErrors {
+    // [host:port] -> counters
+    pub pools: BTreeMap<String, PoolCounters>,
+    // newest last
+    pub last: VecDeque<PoolError>,
+}
+
+impl PoolErrors {
+    // Classify the lines of the output. [pool] is the pool in use, for the lines that do not name one.
+    pub fn update_from_output(&mut self, output: &str, pool: Option<&Pool>) {
+        for line in output.lines() {
+            if let Some(c) = LINE_NEW_JOB.captures(line) {
+                if let Some(counters) = self.pools.get_mut(&c["pool"]) {
+                    counters.blocking = 0;
+                }
+                continue;
+            }
+            let Some(kind) = PoolErrorKind::classify(line) else {
+                continue;
+            };
+            let pool = match LINE_POOL.captures(line) {
+                Some(c) => c["pool"].to_string(),
+                None => pool.map_or("???".to_string(), pool_key),
+            };
+            let counters = self.pools.entry(pool.clone()).or_default();
+            counters.counts[kind as usize] += 1;
+            if kind.is_blocking() {
+                counters.blocking += 1;
+                counters.last_blocking = Some(Instant::now());
+            }
+            self.last.push_back(PoolError {
+                time: chrono::Local::now().format("%H:%M:%S").to_string(),
+                pool,
+                kind,
+                line: line.trim().to_string(),
+            });
+            if self.last.len() > POOL_ERRORS_LAST {
+                self.last.pop_front();
+            }
+        }
+    }
+    pub fn counters(&self, pool: &Pool) -> Option<&PoolCounters> {
+        self.pools.get(&pool_key(pool))
+    }
+    // Too many blocking errors recently, the pool should not be used.
+    pub fn is_broken(&self, pool: &Pool) -> bool {
+        self.counters(pool).is_some_and(|c| {
+            c.blocking >= POOL_ERRORS_BROKEN
+                && c.last_blocking
+                    .is_some_and(|t| t.elapsed() < POOL_ERRORS_BROKEN_DURATION)
+        })
+    }
+}
+
+// Address of the pool as written by XMRig.
+fn pool_key(pool: &Pool) -> String {
+    [pool.url(), ":".to_string(), pool.port()].concat()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pool_errors() {
+        let lines = [
+            (
+                "[2025-01-12 12:00:00.000]  net      eu.xmrvsbeast.com:4247 login error code: 6",
+                Some(PoolErrorKind::LoginRejected),
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  net      eu.xmrvsbeast.com:4247 TLS error: \"handshake failed\"",
+                Some(PoolErrorKind::Tls),
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  net      127.0.0.1:3333 connect error: \"connection refused\"",
+                Some(PoolErrorKind::ConnectionRefused),
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  cpu      rejected (0/1) diff 250K \"Low difficulty share\" (48 ms)",
+                Some(PoolErrorKind::LowDifficulty),
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  net      #0 na.xmrvsbeast.com:4247 timeout",
+                Some(PoolErrorKind::JobTimeout),
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  net      127.0.0.1:3333 read error: \"end of file\"",
+                Some(PoolErrorKind::Other),
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  proxy    200.00 kH/s, shares: 1/0 +0, upstreams active: 0 sleep: 0 error: 1 total: 1",
+                None,
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  msr      FAILED TO APPLY MSR MOD, HASHRATE WILL BE LOW",
+                None,
+            ),
+            (
+                "[2025-01-12 12:00:00.000]  net      new job from 127.0.0.1:3333 diff 100K algo rx/0 height 3000000",
+                None,
+            ),
+        ];
+        for (line, kind) in lines {
+            assert_eq!(PoolErrorKind::classify(line), kind, "{line}");
+        }
+        let mut errors = PoolErrors::default();
+        let output = lines.map(|(line, _)| line).join("\n");
+        errors.update_from_output(&output, Some(&Pool::P2pool(3333)));
+        assert_eq!(errors.last.len(), 6);
+        // the rejected share does not name a pool, it is counted for the pool in use.
+        let p2pool = errors.counters(&Pool::P2pool(3333)).unwrap();
+        assert_eq!(p2pool.count(PoolErrorKind::LowDifficulty), 1);
+        assert_eq!(p2pool.total(), 3);
+        // the new job of P2Pool resets its blocking errors.
+        assert_eq!(p2pool.blocking, 0);
+        let eu = errors.counters(&Pool::XvBEurope).unwrap();
+        assert_eq!(eu.blocking, 2);
+        assert!(!errors.is_broken(&Pool::XvBEurope));
+        errors.update_from_output(lines[0].0, None);
+        assert!(errors.is_broken(&Pool::XvBEurope));
+        assert!(!errors.is_broken(&Pool::XvBNorthAmerica));
+        // the error list is bounded.
+        for _ in 0..POOL_ERRORS_LAST {
+            errors.update_from_output(lines[4].0, None);
+        }
+        assert_eq!(errors.last.len(), POOL_ERRORS_LAST);
+    }
+}
@@ -0,0 +1,271 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Workers of XMRig-Proxy given by [/1/workers] and [/1/miners],
+// shown in the Status tab and watched to alert when a worker stops sending shares.
+
+use anyhow::Result;
+use reqwest::header::AUTHORIZATION;
+use reqwest_middleware::ClientWithMiddleware as Client;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- [/1/workers]
+// e.g: [wget -qO- localhost:18089/1/workers].
+// A worker is a row: [name, ip, connections, accepted, rejected, invalid, hashes, last share (ms),
+// 1m, 10m, 1h, 12h, 24h] with the hashrate in kH/s.
+#[derive(Debug, Deserialize, Clone)]
+pub(super) struct PrivProxyWorkers {
+    // what identifies a worker: rig_id, user, password, agent or ip
+    #[serde(default)]
+    pub mode: String,
+    #[serde(default)]
+    pub workers: Vec<Vec<Value>>,
+}
+
+//---------------------------------------------------------------------------------------------------- [/1/miners]
+// A miner is a row ordered as [format], one per connection.
+#[derive(Debug, Deserialize, Clone)]
+pub(super) struct PrivProxyMiners {
+    #[serde(default)]
+    pub format: Vec<String>,
+    #[serde(default)]
+    pub miners: Vec<Vec<Value>>,
+}
+
+impl PrivProxyMiners {
+    fn field<'a>(&self, miner: &'a [Value], name: &str) -> Option<&'a Value> {
+        let index = self.format.iter().position(|f| f == name)?;
+        miner.get(index)
+    }
+    fn text(&self, miner: &[Value], name: &str) -> Option<String> {
+        self.field(miner, name)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    }
+    // Name of the worker the miner is counted in, depends on the mode of XMRig-Proxy.
+    fn worker_name(&self, miner: &[Value], mode: &str) -> Option<String> {
+        match mode {
+            // XMRig-Proxy uses the user if the miner has no rig id.
+            "rig_id" => self
+                .text(miner, "rig_id")
+                .or_else(|| self.text(miner, "user")),
+            "user" | "password" | "agent" | "ip" => self.text(miner, mode),
+            _ => None,
+        }
+    }
+}
+
+// Send an HTTP request to the API of XMRig-Proxy and deserialize the answer.
+pub(super) async fn request_proxy_api<T: DeserializeOwned>(
+    client: &Client,
+    api_uri: &str,
+    token: &str,
+) -> Result<T> {
+    let request = client
+        .get(api_uri)
+        .header(AUTHORIZATION, ["Bearer ", token].concat());
+    Ok(request
+        .timeout(std::time::Duration::from_millis(5000))
+        .send()
+        .await?
+        .json()
+        .await?)
+}
+
+//---------------------------------------------------------------------------------------------------- Public workers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProxyWorker {
+    pub name: String,
+    pub ip: String,
+    // 0 if the worker is not connected anymore
+    pub connections: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub invalid: u64,
+    // unix time in seconds, 0 until the first share
+    pub last_share: u64,
+    // H/s, 1m/10m/1h/12h/24h
+    pub hashrate: [f32; 5],
+    // from [/1/miners]
+    pub agent: String,
+    pub diff: u64,
+    // no share for longer than [worker_silent_minutes], set in the XMRig-Proxy tab of Gupaxx
+    pub silent: bool,
+}
+
+impl ProxyWorker {
+    fn from_row(row: &[Value]) -> Option<Self> {
+        let text = |i: usize| row.get(i).and_then(Value::as_str).map(str::to_string);
+        let int = |i: usize| row.get(i).and_then(Value::as_u64).unwrap_or_default();
+        // every hashrate value of xmrig-proxy is in kH/s, so we convert it into H/s
+        let hashrate =
+            |i: usize| row.get(i).and_then(Value::as_f64).unwrap_or_default() as f32 * 1000.0;
+        Some(Self {
+            name: text(0)?,
+            ip: text(1).unwrap_or_default(),
+            connections: int(2),
+            accepted: int(3),
+            rejected: int(4),
+            invalid: int(5),
+            last_share: int(7) / 1000,
+            hashrate: [8, 9, 10, 11, 12].map(hashrate),
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProxyWorkers {
+    pub mode: String,
+    // sorted by name, a worker seen once is kept until XMRig-Proxy restarts
+    pub workers: Vec<ProxyWorker>,
+}
+
+impl ProxyWorkers {
+    pub(super) fn update_from_priv(
+        &mut self,
+        private: PrivProxyWorkers,
+        miners: Option<PrivProxyMiners>,
+    ) {
+        let mut workers = std::mem::take(&mut self.workers)
+            .into_iter()
+            .map(|mut w| {
+                w.connections = 0;
+                w.hashrate = [0.0; 5];
+                (w.name.clone(), w)
+            })
+            .collect::<BTreeMap<String, ProxyWorker>>();
+        for row in &private.workers {
+            let Some(mut worker) = ProxyWorker::from_row(row) else {
+                continue;
+            };
+            if let Some(previous) = workers.get(&worker.name) {
+                worker.silent = previous.silent;
+                worker.agent = previous.agent.clone();
+                worker.diff = previous.diff;
+            }
+            workers.insert(worker.name.clone(), worker);
+        }
+        if let Some(miners) = miners {
+            for miner in &miners.miners {
+                let Some(name) = miners.worker_name(miner, &private.mode) else {
+                    continue;
+                };
+                if let Some(worker) = workers.get_mut(&name) {
+                    if let Some(agent) = miners.text(miner, "agent") {
+                        worker.agent = agent;
+                    }
+                    if let Some(diff) = miners.field(miner, "diff").and_then(Value::as_u64) {
+                        worker.diff = diff;
+                    }
+                }
+            }
+        }
+        self.mode = private.mode;
+        self.workers = workers.into_values().collect();
+    }
+    // Mark the workers without a share since [silent_after] (never if zero).
+    // Returns a message for every worker that went silent or came back.
+    pub(super) fn check_silent(&mut self, now: u64, silent_after: Duration) -> Vec<String> {
+        let mut alerts = vec![];
+        for worker in &mut self.workers {
+            let silent = !silent_after.is_zero()
+                && worker.last_share != 0
+                && now.saturating_sub(worker.last_share) >= silent_after.as_secs();
+            if silent && !worker.silent {
+                alerts.push(format!(
+                    "Worker [{}] ({}) did not send a share for {} minutes",
+                    worker.name,
+                    worker.ip,
+                    now.saturating_sub(worker.last_share) / 60
+                ));
+            } else if !silent && worker.silent {
+                alerts.push(format!("Worker [{}] is sending shares again", worker.name));
+            }
+            worker.silent = silent;
+        }
+        alerts
+    }
+    pub fn silent(&self) -> usize {
+        self.workers.iter().filter(|w| w.silent).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn proxy_workers() {
+        let workers = r#"{
+            "mode": "rig_id",
+            "workers": [
+                ["rig1", "192.168.1.10", 1, 120, 1, 0, 30000000, 1736683200000, 5.21, 5.1, 5.05, 4.9, 4.8],
+                ["laptop", "192.168.1.11", 1, 30, 0, 0, 5000000, 1736682000000, 1.1, 1.2, 1.0, 1.0, 0.9]
+            ]
+        }"#;
+        let miners = r#"{
+            "format": ["id", "ip", "tx", "rx", "state", "diff", "user", "password", "rig_id", "agent"],
+            "miners": [
+                [1, "192.168.1.10", 5000, 7000, 2, 100000, "x", "", "rig1", "XMRig/6.22.2"],
+                [2, "192.168.1.11", 1000, 2000, 2, 20000, "laptop", "", null, "XMRig/6.21.0"]
+            ]
+        }"#;
+        let workers = serde_json::from_str::<PrivProxyWorkers>(workers).unwrap();
+        let miners = serde_json::from_str::<PrivProxyMiners>(miners).unwrap();
+        let mut public = ProxyWorkers::default();
+        public.update_from_priv(workers.clone(), Some(miners));
+        assert_eq!(public.workers.len(), 2);
+        // sorted by name
+        let laptop = &public.workers[0];
+        assert_eq!(laptop.name, "laptop");
+        assert_eq!(laptop.last_share, 1736682000);
+        assert_eq!(laptop.agent, "XMRig/6.21.0");
+        assert_eq!(laptop.diff, 20000);
+        let rig = &public.workers[1];
+        assert_eq!(rig.ip, "192.168.1.10");
+        assert_eq!((rig.accepted, rig.rejected), (120, 1));
+        assert_eq!(rig.hashrate[0], 5210.0);
+        // laptop last shared 20 minutes ago, rig1 just now.
+        let now = 1736683200;
+        let alerts = public.check_silent(now, Duration::from_secs(600));
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].contains("laptop"));
+        assert_eq!(public.silent(), 1);
+        // alerted only once.
+        assert!(
+            public
+                .check_silent(now, Duration::from_secs(600))
+                .is_empty()
+        );
+        // disabled.
+        assert_eq!(public.check_silent(now, Duration::ZERO).len(), 1);
+        assert_eq!(public.silent(), 0);
+        // a worker leaving the proxy is kept.
+        let mut rig_only = workers;
+        rig_only.workers.remove(1);
+        public.update_from_priv(rig_only, None);
+        assert_eq!(public.workers.len(), 2);
+        assert_eq!(public.workers[0].connections, 0);
+        assert_eq!(public.workers[0].agent, "XMRig/6.21.0");
+    }
+}
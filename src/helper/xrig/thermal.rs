@@ -0,0 +1,180 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Thermal guard: XMRig is throttled, then paused, while the CPU is above the ceiling of the XMRig tab.
+// The Helper reads the temperature of the CPU into [Sys] every second with its history,
+// the XMRig watchdog applies the guard together with the idle policy.
+
+use crate::disk::state::ThermalPolicy;
+use crate::helper::xrig::idle::IdleAction;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Seconds of temperature kept for the Status tab.
+pub const THERMAL_HISTORY: usize = 600;
+// Number of throttle events kept.
+pub const THERMAL_EVENTS: usize = 50;
+// Throttled XMRig is paused if the CPU stays above the ceiling for this long.
+pub const THERMAL_ESCALATE: Duration = Duration::from_secs(60);
+
+// Labels of the sensors of the CPU (coretemp, k10temp, zenpower, thermal zones of ARM).
+const CPU_SENSORS: [&str; 6] = ["package", "tctl", "tdie", "cpu", "core", "soc"];
+
+// Hottest sensor of the CPU, None if there is none.
+pub fn cpu_temperature(components: &sysinfo::Components) -> Option<f32> {
+    components
+        .list()
+        .iter()
+        .filter(|c| {
+            let label = c.label().to_lowercase();
+            CPU_SENSORS.iter().any(|sensor| label.contains(sensor))
+        })
+        .filter_map(|c| c.temperature())
+        .filter(|t| t.is_finite() && *t > 0.0)
+        .reduce(f32::max)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalEvent {
+    // local time of the event
+    pub time: String,
+    pub temperature: f32,
+    pub action: IdleAction,
+}
+
+impl std::fmt::Display for ThermalEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let action = match self.action {
+            IdleAction::Mine => "mining with all threads".to_string(),
+            IdleAction::Throttle(threads) => format!("throttled to {threads} threads"),
+            IdleAction::Pause => "paused".to_string(),
+        };
+        write!(f, "[{}] {:.0}°C, {action}", self.time, self.temperature)
+    }
+}
+
+// State of the guard in the XMRig watchdog, shown in the Status tab.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThermalGuard {
+    pub enabled: bool,
+    pub ceiling: u16,
+    pub action: IdleAction,
+    // newest last
+    pub events: VecDeque<ThermalEvent>,
+    // since when the CPU is above the ceiling
+    over: Option<Instant>,
+}
+
+impl ThermalGuard {
+    pub fn new(policy: &ThermalPolicy) -> Self {
+        Self {
+            enabled: policy.enabled,
+            ceiling: policy.ceiling,
+            ..Default::default()
+        }
+    }
+    // The action to apply to XMRig, None if it does not change.
+    // Above the ceiling XMRig is throttled, and paused if it stays above for [THERMAL_ESCALATE].
+    // It mines again with all its threads once the CPU cooled down by [hysteresis].
+    pub fn update(
+        &mut self,
+        policy: &ThermalPolicy,
+        temperature: Option<f32>,
+        can_throttle: bool,
+        now: Instant,
+    ) -> Option<IdleAction> {
+        // without a sensor, nothing is known.
+        let temperature = temperature?;
+        let ceiling = policy.ceiling as f32;
+        let action = if temperature >= ceiling {
+            let over = *self.over.get_or_insert(now);
+            let escalate = now.duration_since(over) >= THERMAL_ESCALATE;
+            match self.action {
+                _ if policy.hot_threads == 0 || !can_throttle => IdleAction::Pause,
+                IdleAction::Throttle(_) if escalate => IdleAction::Pause,
+                IdleAction::Pause => IdleAction::Pause,
+                _ => IdleAction::Throttle(policy.hot_threads),
+            }
+        } else {
+            self.over = None;
+            if temperature <= ceiling - policy.hysteresis as f32 {
+                IdleAction::Mine
+            } else {
+                self.action
+            }
+        };
+        if action == self.action {
+            return None;
+        }
+        self.action = action;
+        self.events.push_back(ThermalEvent {
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            temperature,
+            action,
+        });
+        if self.events.len() > THERMAL_EVENTS {
+            self.events.pop_front();
+        }
+        Some(action)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn thermal_guard() {
+        let policy = ThermalPolicy {
+            enabled: true,
+            ceiling: 85,
+            hysteresis: 10,
+            hot_threads: 2,
+        };
+        let start = Instant::now();
+        let mut guard = ThermalGuard::new(&policy);
+        assert_eq!(guard.update(&policy, None, true, start), None);
+        assert_eq!(guard.update(&policy, Some(70.0), true, start), None);
+        assert_eq!(
+            guard.update(&policy, Some(86.0), true, start),
+            Some(IdleAction::Throttle(2))
+        );
+        // still too hot, but not for long enough to pause.
+        let later = start + Duration::from_secs(30);
+        assert_eq!(guard.update(&policy, Some(88.0), true, later), None);
+        let later = start + THERMAL_ESCALATE;
+        assert_eq!(
+            guard.update(&policy, Some(87.0), true, later),
+            Some(IdleAction::Pause)
+        );
+        // cooled under the ceiling, but not by the hysteresis.
+        assert_eq!(guard.update(&policy, Some(80.0), true, later), None);
+        assert_eq!(guard.action, IdleAction::Pause);
+        assert_eq!(
+            guard.update(&policy, Some(75.0), true, later),
+            Some(IdleAction::Mine)
+        );
+        assert_eq!(guard.events.len(), 3);
+        assert!(guard.events[1].to_string().ends_with("87°C, paused"));
+        // without live tuning, paused at once.
+        let mut custom = ThermalGuard::new(&policy);
+        assert_eq!(
+            custom.update(&policy, Some(90.0), false, start),
+            Some(IdleAction::Pause)
+        );
+    }
+}
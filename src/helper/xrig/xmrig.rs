@@ -1,15 +1,28 @@
 use crate::constants::*;
-use crate::disk::state::{P2pool, StartOptionsMode, XmrigProxy};
+use crate::disk::state::{
+    FailoverPool, IdlePolicy, P2pool, StartOptionsMode, ThermalPolicy, Xmrig, XmrigPrivilege,
+    XmrigProxy,
+};
 use crate::helper::p2pool::ImgP2pool;
+use crate::helper::xrig::config::{XMRIG_PRIORITY_DEFAULT, XmrigTuning, tune_xmrig_config};
+use crate::helper::xrig::detail::{
+    HugePages, Memory, PrivXmrigBackend, SummaryCpu, SummaryResults, XmrigDetail,
+};
+use crate::helper::xrig::idle::{IdleAction, IdleGuard, apply_idle_action};
+use crate::helper::xrig::pool_errors::PoolErrors;
+use crate::helper::xrig::thermal::ThermalGuard;
 use crate::helper::xrig::update_xmrig_config;
-use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState};
+use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState, Sys};
 use crate::helper::{Pool, PubXvbApi};
 use crate::helper::{Process, arc_mut, check_died, check_user_input, sleep, sleep_end_loop};
 use crate::human::HumanTime;
-use crate::miscs::{client, output_console};
-use crate::regex::{XMRIG_REGEX, contains_error, contains_usepool, detect_pool_xmrig};
+use crate::miscs::{local_client, output_console};
+use crate::regex::{
+    XMRIG_REGEX, contains_error, contains_usepool, detect_pool_entry_xmrig, detect_pool_xmrig,
+};
+use crate::utils::args::Args;
 use crate::utils::human::HumanNumber;
-use crate::utils::sudo::SudoState;
+use crate::utils::sudo::{SudoState, capabilities_set};
 use enclose::{enc, enclose};
 use log::*;
 use portable_pty::Child;
@@ -176,12 +189,37 @@ impl Helper {
             Ok(_) => {}
             Err(_) => return true,
         };
-        let cmd = std::process::Command::new("sudo")
+        !Self::sudo_without_password()
+    }
+    /// sudo can be used right now without a password, the timestamp of sudo is kept.
+    pub fn sudo_without_password() -> bool {
+        std::process::Command::new("sudo")
             .args(["-n", "true"])
-            .status();
-        if cmd.is_ok_and(|s| s.success()) {
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    }
+    /// The password of sudo must be asked before starting XMRig, depending on the privilege mode.
+    pub fn xmrig_password_needed(state: &Xmrig, path: &Path) -> bool {
+        match state.privilege {
+            XmrigPrivilege::Sudo => Self::password_needed(),
+            XmrigPrivilege::SudoNoPassword => !Self::sudo_without_password(),
+            XmrigPrivilege::Capabilities => {
+                Self::xmrig_use_sudo(state, path) && Self::password_needed()
+            }
+        }
+    }
+    /// XMRig is started with sudo, unless its capabilities are still set.
+    /// They are lost when the binary is replaced by an update.
+    pub fn xmrig_use_sudo(state: &Xmrig, path: &Path) -> bool {
+        if state.privilege != XmrigPrivilege::Capabilities {
+            return true;
+        }
+        if capabilities_set(path) {
             return false;
         }
+        warn!("XMRig | Capabilities of the binary are missing, sudo is used");
         true
     }
     #[cold]
@@ -256,6 +294,16 @@ impl Helper {
             StartOptionsMode::Advanced
         };
         let args = Self::build_xmrig_args(state, mode, p2pool_stratum_port);
+        // the failover chain only exists in advanced mode.
+        let pools = if matches!(mode, StartOptionsMode::Advanced) {
+            state.pool_chain()
+        } else {
+            vec![]
+        };
+        let use_sudo = cfg!(unix) && Self::xmrig_use_sudo(state, path);
+        // custom arguments can set anything, the tab does not know what XMRig uses.
+        let tuned =
+            (!matches!(mode, StartOptionsMode::Custom)).then(|| XmrigTuning::from_state(state));
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("XMRig | Launch arguments: {:#?}", args));
         info!("XMRig | Using path: [{}]", path.display());
@@ -274,6 +322,10 @@ impl Helper {
         let proxy_state = proxy_state.clone();
         let proxy_img = Arc::clone(&helper.lock().unwrap().img_proxy);
         let pub_api_xvb = Arc::clone(&helper.lock().unwrap().pub_api_xvb);
+        let img_xmrig = Arc::clone(&helper.lock().unwrap().img_xmrig);
+        let pub_sys = Arc::clone(&helper.lock().unwrap().pub_sys);
+        let idle_policy = state.idle.clone();
+        let thermal_policy = state.thermal.clone();
         thread::spawn(move || {
             Self::spawn_xmrig_watchdog(
                 process,
@@ -282,6 +334,10 @@ impl Helper {
                 args,
                 path,
                 sudo,
+                use_sudo,
+                tuned,
+                pools,
+                &img_xmrig,
                 api_ip_port,
                 &token,
                 process_xvb,
@@ -292,6 +348,9 @@ impl Helper {
                 &p2pool_img,
                 &proxy_state,
                 &proxy_img,
+                &pub_sys,
+                &idle_policy,
+                &thermal_policy,
             );
         });
     }
@@ -300,46 +359,41 @@ impl Helper {
         state: &crate::disk::state::Xmrig,
         stratum_port: u16,
     ) -> String {
-        let mut api_ip = String::with_capacity(15);
-        let mut api_port = String::with_capacity(5);
+        let api_ip;
+        let api_port;
         if state.simple {
             api_ip = "127.0.0.1".to_string();
             api_port = "18088".to_string();
 
             *helper.lock().unwrap().img_xmrig.lock().unwrap() = ImgXmrig {
-                threads: state.current_threads.to_string(),
+                threads: state.threads().to_string(),
                 url: format!("127.0.0.1:{stratum_port} (Local P2Pool)"),
                 api_port: XMRIG_API_PORT_DEFAULT,
                 token: state.token.clone(),
             };
         } else if !state.arguments.is_empty() {
-            // This parses the input and attempts to fill out
-            // the [ImgXmrig]... This is pretty bad code...
-            let mut last = "";
-            let lock = helper.lock().unwrap();
-            let mut xmrig_image = lock.img_xmrig.lock().unwrap();
-            for arg in state.arguments.split_whitespace() {
-                match last {
-                    "--threads" => xmrig_image.threads = arg.to_string(),
-                    "--url" => xmrig_image.url = arg.to_string(),
-                    "--http-host" => {
-                        api_ip = if arg == "localhost" {
-                            "127.0.0.1".to_string()
-                        } else {
-                            arg.to_string()
-                        }
-                    }
-                    "--http-port" => {
-                        api_port = arg.to_string();
-                        xmrig_image.api_port = arg.parse().unwrap_or(XMRIG_API_PORT_DEFAULT)
-                    }
-                    l if l.contains("--http-access-token=") => {
-                        xmrig_image.token = l.split_once("=").unwrap().1.to_string();
-                    }
-                    _ => (),
-                }
-                last = arg;
-            }
+            // Parse the custom arguments to fill out the [ImgXmrig].
+            let args = Args::parse(&state.arguments);
+            api_ip = args
+                .host(&["--http-host"])
+                .unwrap_or("127.0.0.1")
+                .to_string();
+            let port = args
+                .port(&["--http-port"])
+                .unwrap_or(XMRIG_API_PORT_DEFAULT);
+            api_port = port.to_string();
+            *helper.lock().unwrap().img_xmrig.lock().unwrap() = ImgXmrig {
+                threads: args
+                    .value(&["--threads", "-t"])
+                    .unwrap_or("???")
+                    .to_string(),
+                url: args.value(&["--url", "-o"]).unwrap_or("???").to_string(),
+                api_port: port,
+                token: args
+                    .value(&["--http-access-token"])
+                    .unwrap_or_default()
+                    .to_string(),
+            };
         } else {
             let ip = if state.ip == "localhost" || state.ip.is_empty() {
                 "127.0.0.1"
@@ -359,7 +413,7 @@ impl Helper {
             let url = format!("{}:{}", ip, state.port); // Combine IP:Port into one string
             *helper.lock().unwrap().img_xmrig.lock().unwrap() = ImgXmrig {
                 url: url.clone(),
-                threads: state.current_threads.to_string(),
+                threads: state.threads().to_string(),
                 api_port: state.api_port.parse().unwrap_or(XMRIG_API_PORT_DEFAULT),
                 token: state.token.clone(),
             };
@@ -386,11 +440,14 @@ impl Helper {
                 args.push(format!("--http-access-token={}", state.token)); // HTTP API Port
                 args.push("--http-no-restricted".to_string());
                 args.push("--threads".to_string());
-                args.push(state.current_threads.to_string()); // Threads
+                args.push(state.threads().to_string()); // Threads
                 if state.pause != 0 {
                     args.push("--pause-on-active".to_string());
                     args.push(state.pause.to_string());
                 } // Pause on active
+                if state.pages_1gb {
+                    args.push("--randomx-1gb-pages".to_string());
+                }
             }
             _ => (),
         }
@@ -413,11 +470,6 @@ impl Helper {
             }
             StartOptionsMode::Advanced => {
                 // XMRig doesn't understand [localhost]
-                let ip = if state.ip == "localhost" || state.ip.is_empty() {
-                    "127.0.0.1"
-                } else {
-                    &state.ip
-                };
                 let api_ip = if state.api_ip == "localhost" || state.api_ip.is_empty() {
                     "127.0.0.1".to_string()
                 } else {
@@ -428,23 +480,36 @@ impl Helper {
                 } else {
                     state.api_port.to_string()
                 };
-                let url = format!("{}:{}", ip, state.port); // Combine IP:Port into one string
-                args.push("--user".to_string());
-                args.push(state.address.clone()); // Wallet
-                args.push("--rig-id".to_string());
-                args.push(state.rig.to_string()); // Rig ID
-                args.push("--url".to_string());
-                args.push(url.clone()); // IP/Port
+                if state.priority != XMRIG_PRIORITY_DEFAULT {
+                    args.push("--cpu-priority".to_string());
+                    args.push(state.priority.to_string());
+                } // CPU priority
+                if !state.huge_pages {
+                    args.push("--no-huge-pages".to_string());
+                } // Huge pages
+                // one group of pool options per [--url], XMRig uses the next pool when one fails.
+                for pool in state.pool_chain() {
+                    args.push("--url".to_string());
+                    args.push(pool.url()); // IP/Port
+                    args.push("--user".to_string());
+                    args.push(state.address.clone()); // Wallet
+                    args.push("--rig-id".to_string());
+                    args.push(pool.rig.to_string()); // Rig ID
+                    if pool.tls {
+                        args.push("--tls".to_string());
+                    } // TLS
+                    if pool.keepalive {
+                        args.push("--keepalive".to_string());
+                    } // Keepalive
+                    if pool.nicehash {
+                        args.push("--nicehash".to_string());
+                    } // Nicehash
+                    args.extend(Xmrig::proxy_args(&pool.ip)); // SOCKS5 proxy
+                }
                 args.push("--http-host".to_string());
                 args.push(api_ip.to_string()); // HTTP API IP
                 args.push("--http-port".to_string());
                 args.push(api_port.to_string()); // HTTP API Port
-                if state.tls {
-                    args.push("--tls".to_string());
-                } // TLS
-                if state.keepalive {
-                    args.push("--keepalive".to_string());
-                } // Keepalive
             }
             StartOptionsMode::Custom => {
                 // This parses the input and attempts to fill out
@@ -461,9 +526,18 @@ impl Helper {
     }
 
     // We actually spawn [sudo] on Unix, with XMRig being the argument.
+    // XMRig is spawned directly if it has the capabilities it needs.
     #[cfg(target_family = "unix")]
-    fn create_xmrig_cmd_unix(args: Vec<String>, path: PathBuf) -> portable_pty::CommandBuilder {
-        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new("sudo");
+    fn create_xmrig_cmd_unix(
+        args: Vec<String>,
+        path: PathBuf,
+        use_sudo: bool,
+    ) -> portable_pty::CommandBuilder {
+        let mut cmd = if use_sudo {
+            portable_pty::cmdbuilder::CommandBuilder::new("sudo")
+        } else {
+            portable_pty::cmdbuilder::CommandBuilder::new(path.clone())
+        };
         cmd.args(args);
         cmd.cwd(path.as_path().parent().unwrap());
         cmd
@@ -492,6 +566,10 @@ impl Helper {
         mut args: Vec<String>,
         path: std::path::PathBuf,
         sudo: Arc<Mutex<SudoState>>,
+        use_sudo: bool,
+        tuned: Option<XmrigTuning>,
+        pools: Vec<FailoverPool>,
+        img_xmrig: &Arc<Mutex<ImgXmrig>>,
         mut api_ip_port: String,
         token: &str,
         process_xvb: Arc<Mutex<Process>>,
@@ -502,12 +580,15 @@ impl Helper {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_state: &XmrigProxy,
         proxy_img: &Arc<Mutex<ImgProxy>>,
+        pub_sys: &Arc<Mutex<Sys>>,
+        idle_policy: &IdlePolicy,
+        thermal_policy: &ThermalPolicy,
     ) {
         // The actual binary we're executing is [sudo], technically
         // the XMRig path is just an argument to sudo, so add it.
         // Before that though, add the ["--prompt"] flag and set it
         // to emptiness so that it doesn't show up in the output.
-        if cfg!(unix) {
+        if use_sudo {
             args.splice(..0, vec![path.display().to_string()]);
             // do not use prompt when sudo is not needed
             // success is still to false if sudo has not been used to start xmrig
@@ -542,7 +623,7 @@ impl Helper {
         #[cfg(target_os = "windows")]
         let cmd = Self::create_xmrig_cmd_windows(args, path);
         #[cfg(target_family = "unix")]
-        let cmd = Self::create_xmrig_cmd_unix(args, path);
+        let cmd = Self::create_xmrig_cmd_unix(args, path, use_sudo);
         // 1c. Create child
         debug!("XMRig | Creating child...");
         let child_pty = arc_mut!(pair.slave.spawn_command(cmd).unwrap());
@@ -552,9 +633,11 @@ impl Helper {
         // 2. Input [sudo] pass, wipe, then drop.
         if cfg!(unix) && sudo.lock().unwrap().success {
             debug!("XMRig | Inputting [sudo] and wiping...");
-            if let Err(e) = writeln!(stdin, "{}", sudo.lock().unwrap().pass) {
-                error!("XMRig | Sudo STDIN error: {}", e);
-            };
+            if use_sudo {
+                if let Err(e) = writeln!(stdin, "{}", sudo.lock().unwrap().pass) {
+                    error!("XMRig | Sudo STDIN error: {}", e);
+                };
+            }
             SudoState::wipe(&sudo);
             SudoState::reset(&sudo);
 
@@ -575,7 +658,7 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
 
-        let client = client();
+        let client = local_client();
         let start = process.lock().unwrap().start;
         let api_uri_config = {
             if !api_ip_port.ends_with('/') {
@@ -589,6 +672,8 @@ impl Helper {
             }
             "http://".to_owned() + &api_ip_port + XMRIG_API_SUMMARY_ENDPOINT
         };
+        let api_uri_backends = "http://".to_owned() + &api_ip_port + XMRIG_API_BACKENDS_ENDPOINT;
+        let api_uri_json_rpc = "http://".to_owned() + &api_ip_port + XMRIG_API_JSON_RPC_ENDPOINT;
         info!("XMRig | Final API URI: {}", api_uri_config);
 
         // Reset stats before loop
@@ -596,6 +681,16 @@ impl Helper {
         *gui_api.lock().unwrap() = PubXmrigApi::new();
         // pool used for process Status tab
         pub_api.lock().unwrap().pool = None;
+        // the threads are pinned once the HTTP API answers, XMRig has no argument for it.
+        let mut layout = tuned.clone().filter(|tuning| !tuning.affinity.is_empty());
+        pub_api.lock().unwrap().tuned = tuned;
+        pub_api.lock().unwrap().pools = pools;
+        let mut idle = IdleGuard::new(idle_policy.enabled);
+        let mut thermal = ThermalGuard::new(thermal_policy);
+        // action of the idle policy and the thermal guard XMRig is in
+        let mut applied = IdleAction::Mine;
+        pub_api.lock().unwrap().idle = idle.clone();
+        pub_api.lock().unwrap().thermal = thermal.clone();
         // 5. Loop as watchdog
         info!("XMRig | Entering watchdog mode... woof!");
         // needs xmrig to be in belownormal priority or else Gupaxx will be in trouble if it does not have enough cpu time.
@@ -671,17 +766,135 @@ impl Helper {
             }
             // Send an HTTP API request
             debug!("XMRig Watchdog | Attempting HTTP API request...");
-            match PrivXmrigApi::request_xmrig_api(&client, &api_uri_summary, token).await {
+            let api_ok = match PrivXmrigApi::request_xmrig_api(&client, &api_uri_summary, token)
+                .await
+            {
                 Ok(priv_api) => {
                     debug!("XMRig Watchdog | HTTP API request OK, attempting [update_from_priv()]");
                     PubXmrigApi::update_from_priv(&pub_api, priv_api);
+                    match PrivXmrigBackend::request_backends(&client, &api_uri_backends, token)
+                        .await
+                    {
+                        Ok(backends) => pub_api
+                            .lock()
+                            .unwrap()
+                            .detail
+                            .update_from_backends(backends),
+                        Err(err) => warn!(
+                            "XMRig Watchdog | Could not send HTTP API request to: {}\n{}",
+                            api_uri_backends, err
+                        ),
+                    }
+                    true
                 }
                 Err(err) => {
                     warn!(
                         "XMRig Watchdog | Could not send HTTP API request to: {}\n{}",
                         api_uri_summary, err
                     );
+                    false
                 }
+            };
+            // Push the tuning asked by the XMRig tab, without restarting XMRig.
+            // It replaces the layout of the start, which is pushed as soon as possible.
+            let mut tuning = gui_api.lock().unwrap().tuning.take();
+            if tuning.is_some() {
+                layout = None;
+            } else if api_ok {
+                tuning = layout.take();
+            }
+            if let Some(tuning) = tuning {
+                debug!("XMRig Watchdog | Attempting live tuning...");
+                match tune_xmrig_config(&client, &api_uri_config, token, &tuning).await {
+                    Ok(tuned) => {
+                        info!("XMRig Watchdog | Live tuning ... OK");
+                        img_xmrig.lock().unwrap().threads = tuned.threads.to_string();
+                        let mut msg =
+                            format!("Tuning applied without restart: {} threads", tuned.threads);
+                        if !tuned.affinity.is_empty() {
+                            msg.push_str(&format!(" pinned to CPUs {:?}", tuned.affinity));
+                        }
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            &msg,
+                            ProcessName::Xmrig,
+                        );
+                        pub_api.lock().unwrap().tuned = Some(tuned);
+                    }
+                    Err(err) => {
+                        warn!("XMRig Watchdog | Live tuning ... FAIL ... {}", err);
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            &format!("Failure to tune xmrig with HTTP API.\nError: {}", err),
+                            ProcessName::Xmrig,
+                        );
+                    }
+                }
+            }
+            // Pause or throttle XMRig while the computer is in use or too hot.
+            if api_ok && (idle_policy.enabled || thermal_policy.enabled) {
+                let full = pub_api.lock().unwrap().tuned.clone();
+                let (reason, temperature) = {
+                    let sys = pub_sys.lock().unwrap();
+                    (idle_policy.busy_reason(&sys.idle), sys.cpu_temperature)
+                };
+                let now = Instant::now();
+                if idle_policy.enabled
+                    && idle
+                        .update(idle_policy, reason, full.is_some(), now)
+                        .is_some()
+                {
+                    info!("XMRig Watchdog | Idle policy ... {}", idle);
+                    output_console(
+                        &mut gui_api.lock().unwrap().output,
+                        &format!("Idle policy: {}", idle),
+                        ProcessName::Xmrig,
+                    );
+                }
+                if thermal_policy.enabled
+                    && thermal
+                        .update(thermal_policy, temperature, full.is_some(), now)
+                        .is_some()
+                {
+                    if let Some(event) = thermal.events.back() {
+                        info!("XMRig Watchdog | Thermal guard ... {}", event);
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            &format!("Thermal guard: {}", event),
+                            ProcessName::Xmrig,
+                        );
+                    }
+                }
+                let action = idle.action.strictest(thermal.action);
+                if action != applied {
+                    debug!("XMRig Watchdog | Applying {:?}...", action);
+                    match apply_idle_action(
+                        &client,
+                        &api_uri_json_rpc,
+                        &api_uri_config,
+                        token,
+                        applied,
+                        action,
+                        full.as_ref(),
+                    )
+                    .await
+                    {
+                        Ok(threads) => {
+                            applied = action;
+                            if let Some(threads) = threads {
+                                img_xmrig.lock().unwrap().threads = threads.to_string();
+                            }
+                        }
+                        // tried again on the next loop.
+                        Err(err) => warn!(
+                            "XMRig Watchdog | Applying {:?} ... FAIL ... {}",
+                            action, err
+                        ),
+                    }
+                }
+                let mut pub_api = pub_api.lock().unwrap();
+                pub_api.idle = idle.clone();
+                pub_api.thermal = thermal.clone();
             }
             // if mining on proxy and proxy is not alive, switch back to p2pool node
             debug!("update from priv ok");
@@ -850,6 +1063,21 @@ pub struct PubXmrigApi {
     pub hashrate_raw_1m: f32,
     pub hashrate_raw_15m: f32,
     pub pool: Option<Pool>,
+    // tuning asked by the XMRig tab, taken by the watchdog
+    pub tuning: Option<XmrigTuning>,
+    // tuning confirmed by XMRig, None if it can not be changed live (custom arguments)
+    pub tuned: Option<XmrigTuning>,
+    // full summary and backends, for the XMRig submenu of the Status tab
+    pub detail: XmrigDetail,
+    pub pool_errors: PoolErrors,
+    // why XMRig is paused or throttled by the idle policy
+    pub idle: IdleGuard,
+    // throttle events of the thermal guard
+    pub thermal: ThermalGuard,
+    // pool of the advanced mode and its failover pools, as given to XMRig
+    pub pools: Vec<FailoverPool>,
+    // index in [pools] of the pool in use
+    pub pool_entry: Option<usize>,
 }
 
 impl Default for PubXmrigApi {
@@ -873,15 +1101,25 @@ impl PubXmrigApi {
             hashrate_raw_1m: 0.0,
             hashrate_raw_15m: 0.0,
             pool: None,
+            tuning: None,
+            tuned: None,
+            detail: XmrigDetail::default(),
+            pool_errors: PoolErrors::default(),
+            idle: IdleGuard::default(),
+            thermal: ThermalGuard::default(),
+            pools: vec![],
+            pool_entry: None,
         }
     }
 
     #[inline]
     pub fn combine_gui_pub_api(gui_api: &mut Self, pub_api: &mut Self) {
         let output = std::mem::take(&mut gui_api.output);
+        let tuning = gui_api.tuning.take();
         let buf = std::mem::take(&mut pub_api.output);
         *gui_api = Self {
             output,
+            tuning,
             ..pub_api.clone()
         };
         if !buf.is_empty() {
@@ -918,6 +1156,9 @@ impl PubXmrigApi {
         drop(output_pub);
 
         let mut output_parse = output_parse.lock().unwrap();
+        public
+            .pool_errors
+            .update_from_output(&output_parse, public.pool.as_ref());
         // 2. Check for "new job"/"no active...".
         if XMRIG_REGEX.new_job.is_match(&output_parse) {
             process.state = ProcessState::Alive;
@@ -930,10 +1171,17 @@ impl PubXmrigApi {
                 p2pool_state.current_port(process_p2pool.is_alive(), &p2pool_img.lock().unwrap()),
             ) {
                 public.pool = Some(name_pool);
+                // a pool set by XvB is not in the chain.
+                let urls = public.pools.iter().map(|p| p.url()).collect::<Vec<_>>();
+                public.pool_entry = detect_pool_entry_xmrig(&output_parse, &urls);
             }
         } else if XMRIG_REGEX.not_mining.is_match(&output_parse) {
+            if let Some(error) = public.pool_errors.last.back() {
+                warn!("XMRig | Not mining ... {} on {}", error.kind, error.pool);
+            }
             process.state = ProcessState::NotMining;
             public.pool = None;
+            public.pool_entry = None;
         }
 
         // 3. Throw away [output_parse]
@@ -962,6 +1210,31 @@ impl PubXmrigApi {
             .iter()
             .map(|x| x.as_ref().map(|y| *y as u64))
             .collect::<Vec<Option<u64>>>();
+        // the part given by [/2/backends] is kept.
+        let detail = XmrigDetail {
+            version: private.version,
+            algo: private.algo.unwrap_or_default(),
+            paused: private.paused,
+            features: private.features,
+            cpu_brand: private.cpu.brand,
+            cpu_flags: private.cpu.flags,
+            assembly: private.cpu.assembly,
+            msr_preset: private.cpu.msr,
+            hugepages: private.hugepages.or(public.detail.hugepages),
+            memory_rss: private.resources.memory.resident_set_memory,
+            memory_free: private.resources.memory.free,
+            memory_total: private.resources.memory.total,
+            pool: private.connection.pool,
+            tls: private.connection.tls,
+            ping_ms: private.connection.ping,
+            pool_uptime: private.connection.uptime,
+            failures: private.connection.failures,
+            share_time_ms: private.results.avg_time_ms,
+            shares_good: private.results.shares_good,
+            shares_total: private.results.shares_total,
+            best_diff: private.results.best.first().copied().unwrap_or_default(),
+            ..std::mem::take(&mut public.detail)
+        };
         *public = Self {
             worker_id: private.worker_id,
             resources: HumanNumber::from_load(private.resources.load_average).to_string(),
@@ -972,6 +1245,7 @@ impl PubXmrigApi {
             hashrate_raw,
             hashrate_raw_1m,
             hashrate_raw_15m,
+            detail,
             ..std::mem::take(&mut *public)
         }
     }
@@ -988,6 +1262,20 @@ pub struct PrivXmrigApi {
     resources: Resources,
     connection: Connection,
     hashrate: Hashrate,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    algo: Option<String>,
+    #[serde(default)]
+    paused: bool,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    cpu: SummaryCpu,
+    #[serde(default)]
+    hugepages: Option<HugePages>,
+    #[serde(default)]
+    results: SummaryResults,
 }
 
 impl PrivXmrigApi {
@@ -1013,6 +1301,8 @@ impl PrivXmrigApi {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 struct Resources {
     load_average: [Option<f32>; 3],
+    #[serde(default)]
+    memory: Memory,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1020,6 +1310,16 @@ struct Connection {
     diff: u128,
     accepted: u128,
     rejected: u128,
+    #[serde(default)]
+    pool: String,
+    #[serde(default)]
+    tls: Option<String>,
+    #[serde(default)]
+    ping: u64,
+    #[serde(default)]
+    uptime: u64,
+    #[serde(default)]
+    failures: u64,
 }
 
 //  get the API port that would be used if xmrig was started with the current settings
@@ -21,7 +21,7 @@ use reqwest::header::AUTHORIZATION;
 use reqwest_middleware::ClientWithMiddleware as Client;
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{
     path::Path,
     sync::{Arc, Mutex},
@@ -33,8 +33,13 @@ use tokio::spawn;
 use crate::disk::state::{P2pool, StartOptionsMode, XmrigProxy};
 use crate::helper::p2pool::ImgP2pool;
 use crate::helper::xrig::current_api_url_xrig;
+use crate::helper::xrig::pool_errors::PoolErrors;
+use crate::helper::xrig::proxy_workers::{
+    PrivProxyMiners, PrivProxyWorkers, ProxyWorkers, request_proxy_api,
+};
 use crate::human::{HumanNumber, HumanTime};
-use crate::miscs::client;
+use crate::miscs::local_client;
+use crate::utils::args::Args;
 use crate::{
     GUPAX_VERSION_UNDERSCORE,
     helper::{
@@ -47,7 +52,10 @@ use crate::{
     miscs::output_console,
     regex::{XMRIG_REGEX, contains_timeout, contains_usepool, detect_pool_xmrig},
 };
-use crate::{PROXY_API_PORT_DEFAULT, PROXY_PORT_DEFAULT, XMRIG_API_SUMMARY_ENDPOINT};
+use crate::{
+    PROXY_API_PORT_DEFAULT, PROXY_PORT_DEFAULT, XMRIG_API_SUMMARY_ENDPOINT,
+    XMRIG_PROXY_API_MINERS_ENDPOINT, XMRIG_PROXY_API_WORKERS_ENDPOINT,
+};
 
 use super::xmrig::{ImgXmrig, PubXmrigApi};
 impl Helper {
@@ -247,31 +255,18 @@ impl Helper {
         if state.simple {
             *helper.lock().unwrap().img_proxy.lock().unwrap() = ImgProxy::new();
         } else if !state.arguments.is_empty() {
-            // This parses the input and attempts to fill out
-            // the [ImgXmrig]... This is pretty bad code...
-            let mut last = "";
-            let lock = helper.lock().unwrap();
-            let mut proxy_image = lock.img_proxy.lock().unwrap();
-            for arg in state.arguments.split_whitespace() {
-                match last {
-                    "--bind" | "-b" => {
-                        proxy_image.port = last
-                            .split(":")
-                            .last()
-                            .unwrap_or_default()
-                            .parse()
-                            .unwrap_or(PROXY_PORT_DEFAULT);
-                    }
-                    "--http-host" => {
-                        proxy_image.api_port = last.parse().unwrap_or(PROXY_API_PORT_DEFAULT)
-                    }
-                    l if l.contains("--http-access-token=") => {
-                        proxy_image.token = l.split_once("=").unwrap().1.to_string();
-                    }
-                    _ => {}
-                }
-                last = arg;
-            }
+            // Parse the custom arguments to fill out the [ImgProxy].
+            let args = Args::parse(&state.arguments);
+            *helper.lock().unwrap().img_proxy.lock().unwrap() = ImgProxy {
+                api_port: args
+                    .port(&["--http-port"])
+                    .unwrap_or(PROXY_API_PORT_DEFAULT),
+                port: args.port(&["--bind", "-b"]).unwrap_or(PROXY_PORT_DEFAULT),
+                token: args
+                    .value(&["--http-access-token"])
+                    .unwrap_or_default()
+                    .to_string(),
+            };
         } else {
             *helper.lock().unwrap().img_proxy.lock().unwrap() = ImgProxy {
                 api_port: state.api_port.parse().unwrap_or(PROXY_API_PORT_DEFAULT),
@@ -439,9 +434,19 @@ impl Helper {
             state.api_port(),
             XMRIG_API_SUMMARY_ENDPOINT
         );
+        let api_workers_xp = format!(
+            "http://127.0.0.1:{}/{}",
+            state.api_port(),
+            XMRIG_PROXY_API_WORKERS_ENDPOINT
+        );
+        let api_miners_xp = format!(
+            "http://127.0.0.1:{}/{}",
+            state.api_port(),
+            XMRIG_PROXY_API_MINERS_ENDPOINT
+        );
 
         // set state
-        let client = client();
+        let client = local_client();
         process.lock().unwrap().state = ProcessState::NotMining;
         process.lock().unwrap().signal = ProcessSignal::None;
         // reset stats
@@ -538,6 +543,44 @@ impl Helper {
                         );
                     }
                 }
+                // workers, with the agent and difficulty of their miners
+                match request_proxy_api::<PrivProxyWorkers>(&client, &api_workers_xp, &state.token)
+                    .await
+                {
+                    Ok(workers) => {
+                        let miners = request_proxy_api::<PrivProxyMiners>(
+                            &client,
+                            &api_miners_xp,
+                            &state.token,
+                        )
+                        .await
+                        .ok();
+                        let now = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs();
+                        let silent_after =
+                            Duration::from_secs(u64::from(state.worker_silent_minutes) * 60);
+                        let mut pub_api_lock = pub_api.lock().unwrap();
+                        pub_api_lock.workers.update_from_priv(workers, miners);
+                        let alerts = pub_api_lock.workers.check_silent(now, silent_after);
+                        drop(pub_api_lock);
+                        for alert in alerts {
+                            warn!("XMRig-Proxy Watchdog | {}", alert);
+                            output_console(
+                                &mut gui_api.lock().unwrap().output,
+                                &alert,
+                                ProcessName::XmrigProxy,
+                            );
+                        }
+                    }
+                    Err(err) => {
+                        warn!(
+                            "XMRig-Proxy Watchdog | Could not send HTTP API request to: {}\n{}",
+                            api_workers_xp, err
+                        );
+                    }
+                }
                 // update xmrig to use xmrig-proxy if option enabled and local xmrig alive
                 // if the request was just sent, do not repeat it, let xmrig time to apply the change.
                 let pool = Pool::XmrigProxy(state.bind_port()); // get current port of xmrig-proxy
@@ -626,6 +669,8 @@ pub struct PubXmrigProxyApi {
     pub hashrate_24h: f32,
     pub miners: u16,
     pub pool: Option<Pool>,
+    pub pool_errors: PoolErrors,
+    pub workers: ProxyWorkers,
 }
 
 impl Default for PubXmrigProxyApi {
@@ -648,6 +693,8 @@ impl PubXmrigProxyApi {
             hashrate_24h: 0.0,
             miners: 0,
             pool: None,
+            pool_errors: PoolErrors::default(),
+            workers: ProxyWorkers::default(),
         }
     }
     #[allow(clippy::too_many_arguments)]
@@ -675,6 +722,9 @@ impl PubXmrigProxyApi {
 
         drop(output_pub);
         let mut output_parse = output_parse.lock().unwrap();
+        public
+            .pool_errors
+            .update_from_output(&output_parse, public.pool.as_ref());
         // 2. Check for "new job"/"no active...".
         if XMRIG_REGEX.new_job.is_match(&output_parse)
             || XMRIG_REGEX.valid_conn.is_match(&output_parse)
@@ -692,6 +742,12 @@ impl PubXmrigProxyApi {
             || XMRIG_REGEX.invalid_conn.is_match(&output_parse)
             || XMRIG_REGEX.error.is_match(&output_parse)
         {
+            if let Some(error) = public.pool_errors.last.back() {
+                warn!(
+                    "XMRig-Proxy | Not mining ... {} on {}",
+                    error.kind, error.pool
+                );
+            }
             process.state = ProcessState::NotMining;
             public.pool = None;
         }
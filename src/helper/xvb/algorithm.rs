@@ -340,8 +340,36 @@ impl<'a> Algorithm<'a> {
         }
     }
 
-    async fn target_xvb_node(&self) {
+    // The preferred XvB pool, or the other one if the preferred is broken according to the errors of XMRig/XMRig-Proxy.
+    fn usable_xvb_pool(&self) -> Option<Pool> {
+        let errors = if self.xp_alive {
+            self.gui_api_xp.lock().unwrap().pool_errors.clone()
+        } else {
+            self.gui_api_xmrig.lock().unwrap().pool_errors.clone()
+        };
         let pool = self.gui_api_xvb.lock().unwrap().stats_priv.pool.clone();
+        if !errors.is_broken(&pool) {
+            return Some(pool);
+        }
+        let other = match pool {
+            Pool::XvBEurope => Pool::XvBNorthAmerica,
+            Pool::XvBNorthAmerica => Pool::XvBEurope,
+            _ => return None,
+        };
+        (!errors.is_broken(&other)).then_some(other)
+    }
+
+    async fn target_xvb_node(&self) {
+        let Some(pool) = self.usable_xvb_pool() else {
+            warn!("Algorithm | XvB pools are broken, staying on p2pool");
+            output_console(
+                &mut self.gui_api_xvb.lock().unwrap().output,
+                "XvB pools have repeated errors, hashrate stays on P2Pool",
+                crate::helper::ProcessName::Xvb,
+            );
+            self.target_p2pool_node().await;
+            return;
+        };
 
         info!(
             "Algorithm | request {} to mine on XvB",
@@ -130,6 +130,8 @@ pub fn init_auto(app: &mut App) {
     } else {
         info!("Starting init_auto()...");
     }
+    // the proxy must be known before the update, the ping and the processes.
+    crate::miscs::set_proxy(&app.state.gupax.proxy);
     // update the absolute path, or gupaxx will crash if it's not valid and p2pool is enabled since it only verify the relative path.
     // it could be the case if gupaxx was manually installed, the relative path stay the same but absolute path will also still stay on the old path that maybe is deleted. SO the check with the absolute path would be valid but when launched with the other old/wrong path from absolute, it would panic.
     // this change is non breaking and will fix the issue if it was occurring.
@@ -181,6 +183,7 @@ pub fn init_auto(app: &mut App) {
                 &app.helper,
                 &app.state.node,
                 &app.state.gupax.absolute_node_path,
+                app.ping.lock().unwrap().bootstrap_address(),
             );
         }
     } else {
@@ -232,7 +235,12 @@ pub fn init_auto(app: &mut App) {
             warn!(
                 "Gupaxx | Xmrig instance is already running outside of Gupaxx ! Skipping auto-node..."
             );
-        } else if cfg!(windows) || !Helper::password_needed() {
+        } else if cfg!(windows)
+            || !Helper::xmrig_password_needed(
+                &app.state.xmrig,
+                &app.state.gupax.absolute_xmrig_path,
+            )
+        {
             Helper::start_xmrig(
                 &app.helper,
                 &app.state.xmrig,
@@ -147,12 +147,14 @@ pub fn cmp_f64(a: f64, b: f64) -> std::cmp::Ordering {
 // Free functions.
 
 use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
+use crate::disk::state::ProxyOptions;
 use crate::helper::ProcessName;
 use chrono::Local;
 use egui::TextStyle;
 use egui::Ui;
 use log::error;
 use log::warn;
+use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest_middleware::ClientWithMiddleware;
 use std::path::PathBuf;
@@ -192,8 +194,61 @@ fn datetimeonsole() -> String {
     format!("[{}]  ", Local::now().format("%Y-%m-%d %H:%M:%S%.3f"))
 }
 
+//---------------------------------------------------------------------------------------------------- Proxy
+// Proxy of the Gupaxx tab, synced from the state at each frame.
+// It is used by the HTTP clients and the arguments of the processes started afterwards.
+static PROXY: Lazy<Mutex<Option<ProxyOptions>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn set_proxy(options: &ProxyOptions) {
+    let proxy = (options.enabled && options.validate().is_ok()).then(|| options.clone());
+    let mut current = PROXY.lock().unwrap();
+    if *current != proxy {
+        match &proxy {
+            Some(p) => info!("Proxy | Using SOCKS5 proxy [{}]", p.address),
+            None => info!("Proxy | Disabled"),
+        }
+        *current = proxy;
+    }
+}
+
+pub fn proxy() -> Option<ProxyOptions> {
+    PROXY.lock().unwrap().clone()
+}
+
+// Hosts never reached through the proxy: loopback, private and link-local ranges, where the local programs and their APIs are.
+const NO_PROXY: &str = "localhost,127.0.0.0/8,::1,0.0.0.0,10.0.0.0/8,172.16.0.0/12,192.168.0.0/16,169.254.0.0/16,fc00::/7,fe80::/10";
+
+// HTTP client going through the proxy if enabled, except for the local network.
+pub fn reqwest_client() -> reqwest::Client {
+    proxied_client(proxy().as_ref())
+}
+
+// [socks5h] resolves the domains on the proxy, needed for .onion hosts.
+pub fn proxied_client(proxy: Option<&ProxyOptions>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder();
+    if let Some(options) = proxy {
+        match reqwest::Proxy::all(format!("socks5h://{}", options.address)) {
+            Ok(p) => builder = builder.proxy(p.no_proxy(reqwest::NoProxy::from_string(NO_PROXY))),
+            Err(e) => warn!("Proxy | Invalid proxy [{}] ... {}", options.address, e),
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        warn!("Proxy | HTTP client ... FAIL ... {}", e);
+        reqwest::Client::new()
+    })
+}
+
 pub fn client() -> ClientWithMiddleware {
-    reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+    retry_client(reqwest_client())
+}
+
+// Client for the APIs of XMRig and XMRig-Proxy, never proxied since their host can be any name given by the user.
+pub fn local_client() -> ClientWithMiddleware {
+    retry_client(reqwest::Client::new())
+}
+
+fn retry_client(client: reqwest::Client) -> ClientWithMiddleware {
+    reqwest_middleware::ClientBuilder::new(client)
         .with(reqwest_retry::RetryTransientMiddleware::new_with_policy(
             reqwest_retry::policies::ExponentialBackoff::builder()
                 .retry_bounds(Duration::from_secs(1), Duration::from_secs(5))
@@ -201,6 +256,45 @@ pub fn client() -> ClientWithMiddleware {
         ))
         .build()
 }
+/// POST to the RPC of monerod, answering the HTTP digest challenge it sends back when started with [--rpc-login].
+pub async fn post_rpc(
+    client: &reqwest::Client,
+    url: &str,
+    body: String,
+    login: Option<&str>,
+) -> reqwest::Result<reqwest::Response> {
+    let response = client.post(url).body(body.clone()).send().await?;
+    let Some(login) = login.filter(|_| response.status() == reqwest::StatusCode::UNAUTHORIZED)
+    else {
+        return Ok(response);
+    };
+    let Some(challenge) = response
+        .headers()
+        .get(reqwest::header::WWW_AUTHENTICATE)
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string)
+    else {
+        return Ok(response);
+    };
+    let (user, password) = login.split_once(':').unwrap_or((login, ""));
+    let uri =
+        reqwest::Url::parse(url).map_or_else(|_| "/json_rpc".to_string(), |u| u.path().to_string());
+    let context = digest_auth::AuthContext::new_post(user, password, uri, Some(body.as_bytes()));
+    match digest_auth::parse(&challenge).and_then(|mut prompt| prompt.respond(&context)) {
+        Ok(answer) => {
+            client
+                .post(url)
+                .header(reqwest::header::AUTHORIZATION, answer.to_header_string())
+                .body(body)
+                .send()
+                .await
+        }
+        Err(e) => {
+            warn!("RPC | Could not answer the login challenge of [{url}] ... {e}");
+            Ok(response)
+        }
+    }
+}
 /// to get the right height that a text must take before a button to be aligned in the center correctly.
 pub fn height_txt_before_button(ui: &Ui, style: &TextStyle) -> f32 {
     ui.style().spacing.button_padding.y * 2.0 + ui.text_style_height(style)
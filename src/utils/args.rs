@@ -0,0 +1,211 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Parser for the custom arguments given to Node/P2Pool/XMRig/XMRig-Proxy.
+// The binaries all accept [--flag value] and [--flag=value],
+// and addresses are given as [port], [host:port], [[ipv6]:port] or [scheme://host:port].
+
+//---------------------------------------------------------------------------------------------------- [Args]
+#[derive(Debug, Clone, Default)]
+pub struct Args<'a> {
+    tokens: Vec<&'a str>,
+}
+
+impl<'a> Args<'a> {
+    pub fn parse(args: &'a str) -> Self {
+        Self {
+            tokens: args.split_whitespace().collect(),
+        }
+    }
+
+    /// Value of the last occurrence of any of the flags, in the form [--flag value] or [--flag=value].
+    /// The last one is used because this is what the binaries do when a flag is repeated.
+    pub fn value(&self, flags: &[&str]) -> Option<&'a str> {
+        let mut value = None;
+        for (i, token) in self.tokens.iter().enumerate() {
+            if let Some((flag, v)) = token.split_once('=') {
+                if flags.contains(&flag) {
+                    value = Some(v);
+                }
+            } else if flags.contains(token) {
+                // a flag directly followed by another flag has no value.
+                if let Some(next) = self.tokens.get(i + 1).filter(|n| !n.starts_with("--")) {
+                    value = Some(*next);
+                }
+            }
+        }
+        value
+    }
+
+    /// Is any of the flags present, with or without a value.
+    pub fn has(&self, flags: &[&str]) -> bool {
+        self.tokens
+            .iter()
+            .any(|t| flags.contains(&t.split_once('=').map_or(*t, |(f, _)| f)))
+    }
+
+    /// Port given to any of the flags, see [port_of] for the accepted forms.
+    pub fn port(&self, flags: &[&str]) -> Option<u16> {
+        self.value(flags).and_then(port_of)
+    }
+
+    /// Host given to any of the flags, see [host_of] for the accepted forms.
+    pub fn host(&self, flags: &[&str]) -> Option<&'a str> {
+        self.value(flags).and_then(host_of)
+    }
+}
+
+/// Port of an address given as [port], [host:port], [[ipv6]:port] or [scheme://host:port].
+/// For a list of addresses separated by commas (like P2Pool [--stratum]), the first one is used.
+pub fn port_of(value: &str) -> Option<u16> {
+    let value = strip_scheme(value.split(',').next()?);
+    let port = if let Some(ipv6) = value.strip_prefix('[') {
+        ipv6.split_once("]:")?.1
+    } else {
+        value.rsplit_once(':').map_or(value, |(_, port)| port)
+    };
+    port.parse().ok()
+}
+
+/// Host of an address given as [host:port], [[ipv6]:port] or [scheme://host:port], without the port.
+/// [localhost] is returned as [127.0.0.1], since this is what the GUI uses to reach the local processes.
+pub fn host_of(value: &str) -> Option<&str> {
+    let value = strip_scheme(value.split(',').next()?);
+    let host = if let Some(ipv6) = value.strip_prefix('[') {
+        ipv6.split_once(']')?.0
+    } else {
+        value.split_once(':').map_or(value, |(host, _)| host)
+    };
+    match host {
+        "" => None,
+        "localhost" => Some("127.0.0.1"),
+        host => Some(host),
+    }
+}
+
+fn strip_scheme(value: &str) -> &str {
+    value.split_once("://").map_or(value, |(_, rest)| rest)
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn value_forms() {
+        let args = Args::parse("--rpc-bind-port 18089 --zmq-pub=tcp://127.0.0.1:18084 --no-igd");
+        assert_eq!(args.value(&["--rpc-bind-port"]), Some("18089"));
+        assert_eq!(args.value(&["--zmq-pub"]), Some("tcp://127.0.0.1:18084"));
+        assert_eq!(args.value(&["--no-igd"]), None);
+        assert!(args.has(&["--no-igd"]));
+        assert!(args.has(&["--zmq-pub"]));
+        assert!(!args.has(&["--zmq"]));
+        assert_eq!(args.port(&["--zmq-pub"]), Some(18084));
+        assert_eq!(args.host(&["--zmq-pub"]), Some("127.0.0.1"));
+    }
+
+    #[test]
+    fn flag_without_value() {
+        let args = Args::parse("--bind --http-port 4000");
+        assert_eq!(args.value(&["--bind"]), None);
+        assert_eq!(args.port(&["--http-port"]), Some(4000));
+        assert_eq!(Args::parse("--stratum").value(&["--stratum"]), None);
+    }
+
+    #[test]
+    fn last_occurrence_and_aliases() {
+        let args = Args::parse("-b 0.0.0.0:3355 --http-port 1 --bind [::]:3356 --http-port=2");
+        assert_eq!(args.port(&["--bind", "-b"]), Some(3356));
+        assert_eq!(args.port(&["--http-port"]), Some(2));
+    }
+
+    #[test]
+    fn addresses() {
+        assert_eq!(port_of("3333"), Some(3333));
+        assert_eq!(port_of("0.0.0.0:3333"), Some(3333));
+        assert_eq!(port_of("[::]:3334,0.0.0.0:3333"), Some(3334));
+        assert_eq!(port_of("tcp://node.example.com:18083"), Some(18083));
+        assert_eq!(port_of("[::1]"), None);
+        assert_eq!(port_of("node.example.com"), None);
+        assert_eq!(port_of("0.0.0.0:99999"), None);
+        assert_eq!(host_of("[::1]:18081"), Some("::1"));
+        assert_eq!(host_of("localhost:18081"), Some("127.0.0.1"));
+        assert_eq!(host_of("node.example.com"), Some("node.example.com"));
+        assert_eq!(host_of(":18081"), None);
+    }
+
+    fn flag() -> impl Strategy<Value = String> {
+        "--[a-z][a-z-]{0,15}"
+    }
+    fn host() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[a-z][a-z0-9.-]{0,20}",
+            "(25[0-5]|2[0-4][0-9]|1?[0-9]?[0-9])(\\.(25[0-5]|2[0-4][0-9]|1?[0-9]?[0-9])){3}",
+            "\\[[0-9a-f:]{2,20}\\]",
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn value_roundtrip(flag in flag(), value in "[a-zA-Z0-9./:_-]{1,30}", equal in any::<bool>()) {
+            prop_assume!(!value.starts_with("--"));
+            let args = if equal {
+                format!("{flag}={value}")
+            } else {
+                format!("{flag} {value}")
+            };
+            let args = Args::parse(&args);
+            prop_assert_eq!(args.value(&[flag.as_str()]), Some(value.as_str()));
+            prop_assert!(args.has(&[flag.as_str()]));
+        }
+
+        #[test]
+        fn port_roundtrip(flag in flag(), host in host(), port in any::<u16>(), scheme in any::<bool>(), equal in any::<bool>()) {
+            let address = if scheme {
+                format!("tcp://{host}:{port}")
+            } else {
+                format!("{host}:{port}")
+            };
+            let args = if equal {
+                format!("--before 1 {flag}={address} --after")
+            } else {
+                format!("--before 1 {flag} {address} --after")
+            };
+            let args = Args::parse(&args);
+            prop_assert_eq!(args.port(&[flag.as_str()]), Some(port));
+            let expected = host.trim_start_matches('[').trim_end_matches(']');
+            let expected = if expected == "localhost" { "127.0.0.1" } else { expected };
+            prop_assert_eq!(args.host(&[flag.as_str()]), Some(expected));
+        }
+
+        #[test]
+        fn last_value_wins(flag in flag(), first in any::<u16>(), second in any::<u16>()) {
+            let args = format!("{flag} {first} {flag}={second}");
+            prop_assert_eq!(Args::parse(&args).port(&[flag.as_str()]), Some(second));
+        }
+
+        #[test]
+        fn never_panics(args in "\\PC{0,100}", flag in flag()) {
+            let args = Args::parse(&args);
+            let _ = args.value(&[flag.as_str()]);
+            let _ = args.port(&[flag.as_str()]);
+            let _ = args.host(&[flag.as_str()]);
+        }
+    }
+}
@@ -95,6 +95,10 @@ pub const P2POOL_API_PATH_POOL: &str = "pool/stats";
 pub const P2POOL_API_PATH_P2P: &str = "local/p2p";
 pub const XMRIG_API_SUMMARY_ENDPOINT: &str = "1/summary"; // The default relative URI of XMRig's API summary
 pub const XMRIG_API_CONFIG_ENDPOINT: &str = "1/config"; // The default relative URI of XMRig's API config
+pub const XMRIG_API_BACKENDS_ENDPOINT: &str = "2/backends"; // The default relative URI of XMRig's API backends
+pub const XMRIG_API_JSON_RPC_ENDPOINT: &str = "json_rpc"; // The default relative URI of XMRig's API JSON-RPC
+pub const XMRIG_PROXY_API_WORKERS_ENDPOINT: &str = "1/workers"; // The default relative URI of XMRig-Proxy's API workers
+pub const XMRIG_PROXY_API_MINERS_ENDPOINT: &str = "1/miners"; // The default relative URI of XMRig-Proxy's API miners
 
 // Process state tooltips (online, offline, etc)
 pub const P2POOL_ALIVE: &str = "P2Pool is online and fully synchronized";
@@ -123,6 +127,7 @@ pub const XMRIG_PROXY_MIDDLE: &str = "XMRig-Proxy is in the middle of (re)starti
 pub const XMRIG_PROXY_NOT_MINING: &str = "XMRig-Proxy is online, but not mining to any pool";
 pub const XMRIG_PROXY_REDIRECT: &str = "point local xmrig instance on this proxy instead of the p2pool instance (recommended if using XvB)";
 pub const XMRIG_PROXY_INPUT: &str = "Send a command to XMRig-Proxy";
+pub const XMRIG_PROXY_WORKER_SILENT: &str = "Alert in the console when a worker of XMRig-Proxy did not send a share for this many minutes. 0 disables the alert.";
 pub const XMRIG_PROXY_SIMPLE: &str = r#"Use simple XMRig-Proxy settings:
   - Mine to local P2Pool (localhost:3333)
   - redirect Xmrig local instance to the proxy
@@ -189,6 +194,8 @@ pub const IP_NOT_FOUND: &str = "No ip found, try refreshing by clicking on the b
 
 // [Duration] constants
 pub const SECOND: std::time::Duration = std::time::Duration::from_secs(1);
+// ETA of the local node above which P2Pool stays on the remote node.
+pub const P2POOL_LOCAL_NODE_MAX_ETA: std::time::Duration = std::time::Duration::from_secs(60);
 
 // The explanation given to the user on why XMRig needs sudo.
 pub const XMRIG_ADMIN_REASON: &str = r#"The large hashrate difference between XMRig and other miners like Monero and P2Pool's built-in miners is mostly due to XMRig configuring CPU MSRs and setting up hugepages. Other miners like Monero or P2Pool's built-in miner do not do this. It can be done manually but it isn't recommended since XMRig does this for you automatically, but only if it has the proper admin privileges."#;
@@ -241,13 +248,50 @@ pub const STATUS_P2POOL_CONNECTIONS: &str = "The total amount of miner connectio
 pub const STATUS_P2POOL_MONERO_NODE: &str = "The Monero node being used by P2Pool";
 pub const STATUS_P2POOL_POOL: &str = "The P2Pool sidechain you're currently connected to";
 pub const STATUS_P2POOL_ADDRESS: &str = "The Monero address P2Pool will send payouts to";
+pub const STATUS_P2POOL_MERGE_MINING: &str =
+    "The state of merge mining with Tari, as seen in the output of P2Pool";
 //--
 pub const STATUS_XMRIG_UPTIME: &str = "How long XMRig has been online";
 pub const STATUS_XMRIG_HASHRATE: &str = "The average hashrate of XMRig";
 pub const STATUS_XMRIG_DIFFICULTY: &str = "The current difficulty of the job XMRig is working on";
 pub const STATUS_XMRIG_SHARES: &str = "The amount of accepted and rejected shares";
-pub const STATUS_XMRIG_POOL: &str = "The pool XMRig is currently mining to";
+pub const STATUS_XMRIG_POOL: &str =
+    "The pool XMRig is currently mining to, and its place in the failover chain of the XMRig tab";
 pub const STATUS_XMRIG_THREADS: &str = "The amount of threads XMRig is currently using";
+pub const STATUS_XMRIG_IDLE: &str =
+    "Whether XMRig is paused or throttled by the idle policy of the XMRig tab, and why";
+pub const STATUS_XMRIG_DETAIL_HUGEPAGES_WARNING: &str = "Hugepages are not fully available, this costs 10-30% of the hashrate. Give XMRig the privileges to allocate them with sudo or the setup without sudo of the XMRig tab.";
+pub const STATUS_XMRIG_DETAIL_MSR_WARNING: &str = "The MSR mod could not be applied, this costs 10-15% of the hashrate. XMRig needs to be started with sudo or with the setup without sudo of the XMRig tab.";
+pub const STATUS_XMRIG_DETAIL_PAUSED_WARNING: &str = "XMRig is paused, no hashes are computed";
+pub const STATUS_XMRIG_DETAIL_THREADS: &str =
+    "Hashrate of every thread of XMRig (10s/1m/15m) and the CPU it is pinned to";
+pub const STATUS_XMRIG_DETAIL_HUGEPAGES: &str =
+    "Hugepages allocated by XMRig out of the ones it asked for";
+pub const STATUS_XMRIG_DETAIL_MSR: &str =
+    "Model Specific Registers of the CPU tweaked by XMRig for RandomX, needs admin privileges";
+pub const STATUS_XMRIG_DETAIL_MEMORY: &str =
+    "Memory used by XMRig, used by the mining threads and free on the system";
+pub const STATUS_XMRIG_DETAIL_CPU: &str =
+    "CPU detected by XMRig, the assembly code it uses and the instruction sets available";
+pub const STATUS_XMRIG_DETAIL_FEATURES: &str = "Features XMRig was built with";
+pub const STATUS_XMRIG_DETAIL_LATENCY: &str = "Round trip time between XMRig and the pool";
+pub const STATUS_XMRIG_DETAIL_SHARES: &str =
+    "Average time between two accepted shares and the shares accepted out of the ones sent";
+pub const STATUS_XMRIG_DETAIL_BEST: &str =
+    "Highest difficulty of a share found since XMRig started";
+pub const STATUS_POOL_ERRORS: &str = "Errors of the pools found in the console, counted per pool. A pool with repeated login, TLS or connection errors is avoided by the XvB algorithm for 10 minutes.";
+pub const STATUS_POOL_ERRORS_LAST: &str = "The last errors found in the console, the newest first";
+pub const STATUS_PROXY_WORKERS: &str = "Workers connected to XMRig-Proxy since it started, with their hashrate over 1 minute, 10 minutes, 1 hour, 12 hours and 24 hours. A worker without a share for longer than the time set in the XMRig-Proxy tab is shown in red.";
+pub const STATUS_TEMPERATURE: &str = "Temperature of the hottest sensor of the CPU over the last 10 minutes, with the ceiling of the thermal guard of the XMRig tab as a red line";
+pub const STATUS_TEMPERATURE_EVENTS: &str =
+    "XMRig throttled, paused or resumed by the thermal guard, the newest first";
+// Suggestions for the pool errors
+pub const POOL_ERROR_LOGIN: &str = "The pool rejected the login. Check the payout address, the XvB token and the user/password of the pool.";
+pub const POOL_ERROR_TLS: &str = "The TLS connection failed. Check that the pool port uses TLS, or disable TLS for a plain port.";
+pub const POOL_ERROR_CONN_REFUSED: &str = "The pool refused the connection. Check that the pool (or P2Pool/XMRig-Proxy) is running and that the IP and port are correct.";
+pub const POOL_ERROR_LOW_DIFF: &str = "A share was under the difficulty of the pool. It happens after a new job, if it is frequent, lower the fixed difficulty set in the user.";
+pub const POOL_ERROR_JOB_TIMEOUT: &str = "The pool did not answer in time. Check the network connection and the latency to the pool, or choose a closer pool.";
+pub const POOL_ERROR_OTHER: &str = "Read the line in the console for the details.";
 pub const STATUS_PROXY_CONNECTIONS: &str = "The total amount of miner connections on this Proxy";
 //--
 pub const STATUS_XVB_TIME_REMAIN: &str = "Minutes left before end of round";
@@ -270,11 +314,19 @@ pub const STATUS_NODE_OUT: &str = "Current number of active outbound connections
 pub const STATUS_NODE_IN: &str = "Current number of active incoming connections";
 pub const STATUS_NODE_SYNC: &str = "Does the node is synchronized with the network ?";
 pub const STATUS_NODE_STATUS: &str = "General status of the node";
+pub const STATUS_NODE_TX_POOL: &str = "Number of transactions waiting in the pool of the node";
+pub const STATUS_NODE_SYNC_PROGRESS: &str =
+    "Percent of the blockchain synchronized, with the current and target height";
+pub const STATUS_NODE_SYNC_SPEED: &str = "Blocks synchronized per minute, on the last five minutes";
+pub const STATUS_NODE_SYNC_ETA: &str =
+    "Estimated time left before the node is synchronized, from the current sync speed";
+pub const STATUS_NODE_BOOTSTRAP: &str = "Remote node answering the RPC requests while the node is not synchronized.\nThe answers of the bootstrap daemon are not verified by your node.";
 // Status Submenus
 pub const STATUS_SUBMENU_PROCESSES: &str =
     "View the status of process related data for [Gupaxx|P2Pool|XMRig]";
 pub const STATUS_SUBMENU_P2POOL: &str = "View P2Pool specific data";
 pub const STATUS_SUBMENU_HASHRATE: &str = "Compare your CPU hashrate with others";
+pub const STATUS_SUBMENU_XMRIG: &str = "View the hashrate of every thread of XMRig, the state of the hardware it runs on, the temperature of the CPU and the workers of XMRig-Proxy";
 //-- P2Pool
 pub const STATUS_SUBMENU_PAYOUT: &str = "The total amount of payouts received via P2Pool across all time. This includes all payouts you have ever received using Gupaxx and P2Pool.";
 pub const STATUS_SUBMENU_XMR: &str = "The total of XMR mined via P2Pool across all time. This includes all the XMR you have ever mined using Gupaxx and P2Pool.";
@@ -282,6 +334,29 @@ pub const STATUS_SUBMENU_LATEST: &str = "Sort the payouts from latest to oldest"
 pub const STATUS_SUBMENU_OLDEST: &str = "Sort the payouts from oldest to latest";
 pub const STATUS_SUBMENU_BIGGEST: &str = "Sort the payouts from biggest to smallest";
 pub const STATUS_SUBMENU_SMALLEST: &str = "Sort the payouts from smallest to biggest";
+pub const STATUS_SUBMENU_IMPORT_ADDRESS: &str =
+    "The Monero address that received the payouts, defaults to the address used for P2Pool";
+pub const STATUS_SUBMENU_IMPORT_VIEW_KEY: &str = "The private view key of this address. It is only used to check the outputs of the blocks and is never saved to disk.";
+pub const STATUS_SUBMENU_IMPORT_START: &str =
+    "The block height to start the scan from. Leave empty to scan the last ~30 days of blocks.";
+pub const STATUS_SUBMENU_IMPORT_END: &str = "The block height where the scan stops. Leave empty to scan up to the current height of the node.";
+pub const STATUS_SUBMENU_IMPORT_SCAN: &str = "Scan the blocks with the local Node to find the P2Pool payouts received by this address. Payouts already present are skipped, the new ones are added to the payout log and totals.\nOnly the blocks found by P2Pool are imported, a block mined solo or by another pool is skipped. A block the node can not give is skipped and listed at the end.";
+pub const STATUS_SUBMENU_IMPORT_STOP: &str =
+    "Stop the scan, payouts found until now will still be added";
+pub const STATUS_SUBMENU_PEERS: &str =
+    "The peers of the P2Pool sidechain currently connected to your P2Pool";
+pub const STATUS_SUBMENU_PEER_PIN: &str =
+    "Pin this peer, P2Pool will connect to it at every start. A pinned peer is always added";
+pub const STATUS_SUBMENU_PEER_PIN_INCOMING: &str = "This peer connected to your P2Pool, its address has the temporary port of its connection and can not be reached. Only the peers your P2Pool connected to can be pinned";
+pub const STATUS_SUBMENU_PEER_DONT_ADD: &str = "Don't add this peer: it will never be given to P2Pool by Gupaxx and is removed from the bundled peer list.\nThis is not a ban, P2Pool can still find it and connect to it by itself";
+pub const STATUS_SUBMENU_PEER_INPUT: &str =
+    "Add a peer that is not connected; It must be a valid IPv4/domain with a port";
+pub const STATUS_SUBMENU_PEER_REMOVE: &str = "Click to remove this peer from the list";
+pub const STATUS_SUBMENU_PEERS_PINNED: &str =
+    "Peers given to P2Pool with --addpeers at start, changes apply after a save and a restart";
+pub const STATUS_SUBMENU_PEERS_NOT_ADDED: &str = "Peers never given to P2Pool by Gupaxx, P2Pool can still find them by itself. Changes apply after a save and a restart";
+pub const STATUS_SUBMENU_IMPORT_NODE_OFFLINE: &str =
+    "The local Node must be running and synchronized to scan blocks";
 pub const STATUS_SUBMENU_AUTOMATIC: &str =
     "Automatically calculate share/block time with your current P2Pool 1 hour average hashrate";
 pub const STATUS_SUBMENU_MANUAL: &str = "Manually input a hashrate to calculate share/block time with current P2Pool/Monero network stats";
@@ -328,6 +403,20 @@ pub const STATUS_SUBMENU_OTHER_LOW: &str = "Lowest hashrate record";
 pub const STATUS_SUBMENU_OTHER_RANK: &str = "The rank of this CPU out of [1567] (lower is better)";
 pub const STATUS_SUBMENU_OTHER_BENCHMARKS: &str =
     "How many benchmarks this CPU has had posted to [https://xmrig.com/benchmark]";
+pub const STATUS_SUBMENU_BENCH: &str = "Run [xmrig --bench] on this computer and compare the runs with the other CPUs. XMRig is started without sudo, so the result is lower without hugepages and the MSR mod unless the setup without sudo of the XMRig tab was done.";
+pub const STATUS_SUBMENU_BENCH_SIZE: &str =
+    "Number of hashes to compute, 10M gives a more precise result but takes longer";
+pub const STATUS_SUBMENU_BENCH_MODE: &str = "RandomX mode of the benchmark. Fast uses a 2GB dataset like when mining, light only needs 256MB but is much slower";
+pub const STATUS_SUBMENU_BENCH_THREADS: &str = "Number of threads used by the benchmark";
+pub const STATUS_SUBMENU_BENCH_RUN: &str = "Start the benchmark with the selected options";
+pub const STATUS_SUBMENU_BENCH_XMRIG_ALIVE: &str =
+    "XMRig must be stopped to run a benchmark, both would share the CPU";
+pub const STATUS_SUBMENU_BENCH_XMRIG_PATH: &str =
+    "XMRig was not found, select its path in the Gupaxx tab";
+pub const STATUS_SUBMENU_BENCH_STOP: &str = "Stop the benchmark, the run is not recorded";
+pub const STATUS_SUBMENU_BENCH_REMOVE: &str = "Remove this run";
+pub const STATUS_SUBMENU_BENCH_LOCAL: &str =
+    "A benchmark run on this computer, ranked among the recorded CPUs by its hashrate";
 
 // Gupaxx
 pub const GUPAX_UPDATE: &str = "Check for updates on Gupaxx and bundled versions of P2Pool and XMRig via GitHub's API and upgrade automatically";
@@ -379,10 +468,17 @@ pub const GUPAX_ADVANCED: &str = r#"Use advanced Gupaxx settings:
 pub const GUPAX_SELECT: &str = "Open a file explorer to select a file";
 pub const GUPAX_PATH_P2POOL: &str = "The location of the P2Pool binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
 pub const GUPAX_PATH_XMRIG: &str = "The location of the XMRig binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
+pub const GUPAX_PROXY_ENABLED: &str = "Use a SOCKS5 proxy like Tor for the connections of Gupaxx (updates, XvB, pings) and of the processes started: --proxy for the Node, --socks5 for P2Pool and --proxy for XMRig mining on a remote pool. The local processes are always reached directly. Required to use .onion nodes and pools";
+pub const GUPAX_PROXY_ADDRESS: &str = "IP:PORT of the SOCKS5 proxy, 127.0.0.1:9050 for a local Tor. The Node does not accept a domain";
+pub const GUPAX_PROXY_TOR: &str =
+    "The proxy is Tor: the Node also broadcasts its transactions over Tor with --tx-proxy";
+pub const GUPAX_PROXY_ONION: &str =
+    "A .onion host can only be reached with the proxy enabled in the Gupaxx tab";
 pub const GUPAX_PATH_XMRIG_PROXY: &str = "The location of the XMRig-Proxy binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
 
 // P2Pool
 pub const P2POOL_PORT_DEFAULT: u16 = 3333;
+pub const P2POOL_MERGE_MINING_HOST_DEFAULT: &str = "tari://127.0.0.1:18102";
 pub const P2POOL_MAIN: &str = "Use the P2Pool main-chain. This P2Pool finds blocks faster, but has a higher difficulty. Suitable for miners with more than 100kH/s";
 pub const P2POOL_MINI: &str = "Use the P2Pool mini-chain. This P2Pool finds blocks slower, but has a lower difficulty. Suitable for miners with less than 100kH/s";
 pub const P2POOL_NANO: &str = "Use the P2Pool nano-chain. This P2Pool finds blocks slower, but has a lower difficulty. Suitable for miners with less than 50kH/s";
@@ -401,11 +497,16 @@ pub const P2POOL_BACKUP_HOST_ADVANCED: &str =
     "Automatically switch to the other nodes in your list if the current one is down.";
 pub const P2POOL_AUTOSWITCH_LOCAL_NODE: &str =
     "Automatically switch to the local node when it will be ready to be used.";
+pub const P2POOL_AUTOSWITCH_LOCAL_NODE_ETA: &str = "Estimated from the sync speed of the local node, P2Pool will be restarted on the local node once it is synchronized and less than a minute behind.";
 pub const P2POOL_SELECT_FASTEST: &str = "Select the fastest remote Monero node";
 pub const P2POOL_SELECT_RANDOM: &str = "Select a random remote Monero node";
 pub const P2POOL_SELECT_LAST: &str = "Select the previous remote Monero node";
 pub const P2POOL_SELECT_NEXT: &str = "Select the next remote Monero node";
 pub const P2POOL_PING: &str = "Ping the built-in remote Monero nodes";
+pub const P2POOL_DISCOVERY: &str = "Find remote nodes with a public RPC and ZMQ in the peer list of the local node (it must be running) and in the seeds. The verified nodes are remembered and added to the list of remote nodes, then all the remote nodes are pinged";
+pub const P2POOL_DISCOVERY_SEEDS: &str =
+    "Nodes to probe by the discovery, as [host:rpc] or [host:rpc:zmq] separated by commas";
+pub const P2POOL_NODE_SCORE: &str = "Health score of the node at the last ping, out of 100: latency, sync state, height compared to the best known height (the local node's if synced), ZMQ port reachability (not checked behind a proxy, the score is then out of the other checks) and version. Nodes scoring badly at several pings in a row are not used as backup hosts";
 pub const P2POOL_ADDRESS: &str = "You must use a primary Monero address to mine on P2Pool (starts with a 4). It is highly recommended to create a new wallet since addresses are public on P2Pool!";
 pub const P2POOL_COMMUNITY_NODE_WARNING: &str = r#"TL;DR: Run & use your own Monero Node.
 
@@ -435,6 +536,21 @@ pub const P2POOL_NAME: &str = "Add a unique name to identify this node; Only [A-
 pub const P2POOL_NODE_IP: &str = "Specify the Monero Node IP to connect to with P2Pool; It must be a valid IPv4 address or a valid domain name; Max length = 255 characters";
 pub const P2POOL_RPC_PORT: &str = "Specify the RPC port of the Monero node; [1-65535]";
 pub const P2POOL_ZMQ_PORT: &str = "Specify the ZMQ port of the Monero node; [1-65535]";
+pub const P2POOL_LIGHT_MODE: &str = "Don't allocate the RandomX dataset, saves 2GB of RAM. Disable it only if you mine directly on P2Pool without XMRig or XMRig-Proxy";
+pub const P2POOL_NO_RANDOMX: &str = "Disable the internal RandomX hasher of P2Pool, it will ask the Monero Node to check the PoW hashes instead. Saves more RAM and CPU but puts more load on the node";
+pub const P2POOL_NO_UPNP: &str =
+    "Disable the UPnP port forwarding of P2Pool (also known as --no-igd)";
+pub const P2POOL_ONION_ADDRESS: &str = "Tell the other peers to use this .onion address to connect to your P2Pool through Tor; It must be a valid onion v3 address or empty";
+pub const P2POOL_SOCKS5: &str = "IP:PORT of a SOCKS5 proxy used by P2Pool for outgoing connections, like [127.0.0.1:9050] for Tor; It must be a valid IPv4/domain with a port or empty";
+pub const P2POOL_SEED_NANO_PEERS: &str = "Replace the peer list of P2Pool with the one bundled with Gupaxx when using the nano sidechain, without the peers marked as not added";
+pub const P2POOL_MERGE_MINING: &str = "Merge mine Tari with P2Pool, without using more hashrate. A Tari node with mining enabled is needed, running locally by default";
+pub const P2POOL_MERGE_MINING_CHAINS: &str = "Chains merge mined with P2Pool, without using more hashrate. The first one is Tari, the others are only used in advanced mode";
+pub const P2POOL_MERGE_MINING_HOST: &str = "Node of the chain to merge mine, like [tari://127.0.0.1:18102] for Tari; It must be a valid IPv4/domain with a port, prefixed by the chain if P2Pool needs it";
+pub const P2POOL_MERGE_MINING_ADDRESS: &str = "Your wallet address on the merge mined chain, the rewards will be sent to it; A Tari address must be a valid Base58 Tari address";
+pub const P2POOL_MERGE_MINING_DUPLICATE: &str =
+    "The same merge mining node is enabled twice, P2Pool is given each node once";
+pub const P2POOL_MERGE_MINING_ADD: &str = "Add another chain to merge mine";
+pub const P2POOL_MERGE_MINING_REMOVE: &str = "Remove this chain";
 pub const P2POOL_PATH_NOT_FILE: &str = "P2Pool binary not found at the given PATH in the Gupaxx tab! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where P2Pool is located.";
 pub const P2POOL_PATH_NOT_VALID: &str = "P2Pool binary at the given PATH in the Gupaxx tab doesn't look like P2Pool! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where P2Pool is located.";
 pub const P2POOL_PATH_OK: &str = "P2Pool was found at the given PATH";
@@ -485,6 +601,59 @@ pub const NODE_API_PORT: &str = "RPC API listen port";
 pub const NODE_ZMQ_BIND: &str = "bind address of ZMQ API";
 pub const NODE_ZMQ_PORT: &str = "ZMQ API listen port";
 pub const NODE_FULL_MEM: &str = "Use 2GB of RAM insteal of 256MB for faster block verification";
+pub const NODE_RESTRICTED_RPC: &str = "Restrict the RPC to view-only commands and do not return privacy sensitive data.\nNeeded to share the RPC port with people you do not fully trust.";
+pub const NODE_PUBLIC_NODE: &str = "Advertise the RPC of the node to other peers, so that wallets can use it.\nThe RPC must be restricted, with the Restricted RPC option or a restricted RPC port.";
+pub const NODE_RESTRICTED_PORT: &str = "Open a second RPC port, restricted, reachable from other machines.\nThe port needs to be valid and different from the RPC and ZMQ ports. Leave empty to not use it.";
+pub const NODE_CONFIRM_EXTERNAL_BIND: &str = "Confirm that the unrestricted RPC can be bound to an IP other than localhost.\nNeeded if the RPC bind IP is not 127.0.0.1 and the RPC is not restricted.";
+pub const NODE_LIMIT_RATE_UP: &str =
+    "Limit of the upload bandwidth in kB/s. 0 keeps the default of monerod (2048 kB/s).";
+pub const NODE_LIMIT_RATE_DOWN: &str =
+    "Limit of the download bandwidth in kB/s. 0 keeps the default of monerod (8192 kB/s).";
+pub const NODE_RPC_LOGIN: &str = "Login required to use the RPC, in the form user:password. Leave empty to not use it.\nGupaxx gives it to P2Pool when it uses this node, and uses it for the statistics of the Status tab and the payout import.";
+pub const NODE_BOOTSTRAP_SIMPLE: &str = "While the node is synchronizing, answer the RPC requests (wallets, P2Pool) with the fastest remote node of the last ping.\nIf no ping was done in the last 10 minutes, monerod picks a public node itself.\nThe remote node sees the requests made to your node until it is synchronized.";
+pub const NODE_BOOTSTRAP_PRIVACY: &str = "Privacy: the remote node sees the requests of your wallets and P2Pool until your node is synchronized";
+pub const NODE_BOOTSTRAP_ADDRESS: &str = "Remote node answering the RPC requests while the node is synchronizing, in the form host:port.\n\"auto\" lets monerod pick a public node. Leave empty to not use it.";
+pub const NODE_BOOTSTRAP_LOGIN: &str = "Login of the RPC of the bootstrap daemon, in the form user:password. Leave empty if it does not need one.";
+pub const NODE_BOOTSTRAP_PROXY: &str = "SOCKS proxy used to reach the bootstrap daemon, in the form IP:port.\nLeave empty to use the proxy of the Gupaxx tab if enabled.";
+pub const NODE_RECOVERY: &str = "The node stopped on an error of its database, it might be corrupted.\nmonerod can try to recover it with --db-salvage, it can be verified with monero-blockchain-stats, or moved aside to synchronize a new one.";
+pub const NODE_RECOVERY_SALVAGE: &str = "Start the node once with --db-salvage, monerod tries to recover the database from its last valid state";
+pub const NODE_RECOVERY_VERIFY: &str = "Read the whole database with monero-blockchain-stats, found next to monerod.\nIt stops on the first error of the database. The node must be stopped and it can take hours.";
+pub const NODE_RECOVERY_RESYNC: &str = "Rename the database and start the node to synchronize a new one.\nThe old database is kept next to it, remove it once the node is synchronized.";
+pub const NODE_PUBLIC_RPC_LOCAL: &str = "Set a restricted port, or bind the RPC to 0.0.0.0 or to the IP of this computer, to let others use your node.";
+pub const NODE_PUBLIC_RPC: &str = "Address that people can use to reach the RPC of your node.\nFrom outside of the local network, the port must be open in your firewall and/or router.";
+pub const NODE_COMMAND_STATUS: &str = "Show the height, network and connections of the node";
+pub const NODE_COMMAND_SYNC_INFO: &str =
+    "Show the synchronization state and the peers it syncs from";
+pub const NODE_COMMAND_PRINT_CN: &str = "Show the connected peers";
+pub const NODE_COMMAND_BAN: &str =
+    "Ban an IP or a subnet, for the given number of seconds (default: 1 day)";
+pub const NODE_COMMAND_UNBAN: &str = "Remove the ban of an IP or a subnet";
+pub const NODE_COMMAND_SET_LIMIT: &str =
+    "Set the upload and/or download limit, show the current limits if both are empty";
+pub const NODE_COMMAND_FLUSH_TXPOOL: &str =
+    "Remove a transaction, or all of them, from the transaction pool";
+pub const NODE_COMMAND_SAVE: &str = "Save the blockchain to disk";
+pub const P2POOL_COMMAND_STATUS: &str =
+    "Show the state of the sidechain, of the stratum and of the peers";
+pub const P2POOL_COMMAND_PEERS: &str = "Show the connected peers of the sidechain";
+pub const P2POOL_COMMAND_WORKERS: &str = "Show the miners connected to the stratum";
+pub const NODE_STORAGE_DATA_DIR: &str =
+    "Directory of the blockchain used by the node with the current settings";
+pub const NODE_STORAGE_SPACE: &str = "A pruned node needs about 100 GB of disk space and a full node about 260 GB.\nThe space already taken by the database is deducted.";
+pub const NODE_STORAGE_OLD_DB: &str = "monero-blockchain-prune keeps the database before pruning in the lmdb-old directory of the data directory.\nDelete it once the pruned database works to free the space. The node must be stopped.";
+pub const NODE_STORAGE_REFRESH: &str = "Read again the size of the database and the free space";
+pub const NODE_STORAGE_MOVE: &str = "Move the existing database to another directory and use this directory for the node.\nThe node must be stopped. Only available in advanced mode without custom arguments.";
+pub const NODE_STORAGE_PRUNE: &str = "Prune the existing database with monero-blockchain-prune, found next to monerod.\nThe size of the database is reduced to about a third. The node must be stopped and it can take hours.\nThe database before pruning is left on disk, it can be deleted afterwards.";
+pub const NODE_STORAGE_BUSY: &str = "The database of the node is being moved or pruned";
+pub const NODE_BAN_LIST: &str = include_str!("../../monero_ban_list.txt");
+pub const NODE_BAN_LIST_ENABLE: &str = "Ban the IPs of the spy nodes recommended by the Monero Research Lab (https://github.com/monero-project/meta/issues/1124).\nThe list bundled with Gupaxx is used, unless the path of your own list is given.";
+pub const NODE_BAN_LIST_PATH_EMPTY: &str =
+    "If the PATH of the ban list is empty, the list bundled with Gupaxx will be used.";
+pub const NODE_BAN_LIST_FILE: &str = "The ban list needs to be a correct path to a file if not empty.\nOne IP or subnet (like 1.2.3.0/24) by line.";
+pub const NODE_BAN_LIST_COUNT: &str =
+    "Number of IPs and subnets in the ban list that monerod will use at its next start.";
+pub const NODE_BAN_LIST_REFRESH: &str =
+    "Read again the ban list from the file, after it was edited or replaced.";
 // XMRig
 pub const XMRIG_API_PORT_DEFAULT: u16 = 18088;
 pub const XMRIG_SIMPLE: &str = r#"Use simple XMRig settings:
@@ -516,9 +685,49 @@ pub const XMRIG_API_IP: &str =
 pub const XMRIG_API_PORT: &str =
     "Specify which port to bind to for XMRig's HTTP API; If empty: [18088]";
 pub const XMRIG_API_TOKEN: &str = "Specify the token to authenticate on the HTTP API";
+pub const XMRIG_PRIVILEGE: &str = "How XMRig gets the privileges needed for the MSR mod and the hugepages, which improve the hashrate";
+pub const XMRIG_PRIVILEGE_SETUP: &str = "Configure the hugepages, the 1GB pages if the CPU supports them, load the msr module, give the MSR devices to your user with a udev rule and the capability cap_sys_rawio to the XMRig binary.\nThe password of sudo is asked once, XMRig is then started without sudo.\nThe 1GB pages must be set up again after a reboot, and the capability after an update of XMRig.";
+pub const XMRIG_PRIVILEGE_SETUP_WARNING: &str = "The XMRig binary will get the capability cap_sys_rawio and your user will be able to write the MSR registers of the CPU.\nAny program run by your user could change them, and anything replacing this binary keeps raw access to the hardware.\nContinue only if you trust the XMRig binary and the programs running under your user.";
+pub const XMRIG_PRIVILEGE_DETECT: &str = "Check if sudo can be used without a password (configured in sudoers), so it is not asked anymore on start";
+pub const XMRIG_PRIVILEGE_RESET: &str = "Ask the password of sudo on each start of XMRig, as by default.\nIf the setup was done, the password of sudo is asked to remove its files, the udev rule and the capability of the binary, and to put back the hugepages from before the setup.";
 pub const XMRIG_TLS: &str = "Enable SSL/TLS connections (needs pool support)";
 pub const XMRIG_KEEPALIVE: &str = "Send keepalive packets to prevent timeout (needs pool support)";
+pub const XMRIG_NICEHASH: &str =
+    "Use the nonce range given by the pool to each miner (needs pool support, like NiceHash)";
+pub const XMRIG_FAILOVER: &str = "Pools of the list XMRig switches to, in order, when the pool above fails. XMRig goes back to the first pool once it works again";
+pub const XMRIG_FAILOVER_ADD: &str = "Add a pool of the list to the end of the failover chain";
+pub const XMRIG_FAILOVER_TAKEN: &str =
+    "This pool is already in the failover chain, or is the main pool";
+pub const XMRIG_FAILOVER_UP: &str = "Try this pool before the one above";
+pub const XMRIG_FAILOVER_REMOVE: &str = "Remove this pool from the failover chain";
 pub const XMRIG_THREADS: &str = "Number of CPU threads to use for mining";
+pub const XMRIG_PRIORITY: &str = "Priority of XMRig, from 0 (idle) to 5 (highest).\nIf set to [2], XMRig keeps its default priority";
+pub const XMRIG_HUGE_PAGES: &str =
+    "Use the hugepages for the RandomX dataset, which improves the hashrate";
+pub const XMRIG_AFFINITY: &str = "Logical CPUs the threads of XMRig are pinned to, one thread by selected CPU. They are applied through the HTTP API once XMRig is started.\nThe CPUs of the same physical core are grouped, by NUMA node";
+pub const XMRIG_AFFINITY_CORES: &str =
+    "Pin one thread to every physical core, without their sibling threads";
+pub const XMRIG_AFFINITY_SPREAD: &str = "Pin the number of threads selected above, spread evenly across the NUMA nodes so each node gets its own RandomX dataset. Physical cores are used before their siblings";
+pub const XMRIG_AFFINITY_RESET: &str =
+    "Do not pin the threads, XMRig chooses the CPUs with the number of threads selected above";
+pub const XMRIG_TUNING: &str = "Send the threads, affinity, priority, hint, hugepages and pause on active to the running XMRig through its HTTP API, without restarting it";
+pub const XMRIG_TUNING_DISABLED: &str =
+    "XMRig must be running without custom arguments, with settings different from the ones in use";
+pub const XMRIG_IDLE: &str = "Mine only while the computer is not in use: XMRig is paused or throttled while other programs use the CPU, one of the programs below runs or the computer is on battery.\nIt is applied through the HTTP API of XMRig and taken on the start of XMRig";
+pub const XMRIG_IDLE_LOAD: &str =
+    "Percentage of the CPU used by other programs than XMRig above which the computer is in use";
+pub const XMRIG_IDLE_PROCESSES: &str = "Names of the programs that make the computer in use while they run, separated by commas. [.exe] can be left out";
+pub const XMRIG_IDLE_BATTERY: &str = "Pause XMRig while the computer runs on battery";
+pub const XMRIG_IDLE_THREADS: &str =
+    "Threads kept while the computer is in use. [0] pauses XMRig, as custom arguments do";
+pub const XMRIG_IDLE_RESUME: &str =
+    "Seconds the computer must stay idle before XMRig mines again with all its threads";
+pub const XMRIG_THERMAL: &str = "Watch the temperature of the CPU: XMRig is throttled when it reaches the ceiling, and paused if it stays above for a minute.\nIt is applied through the HTTP API of XMRig and taken on the start of XMRig";
+pub const XMRIG_THERMAL_CEILING: &str =
+    "Temperature of the hottest sensor of the CPU from which XMRig is throttled";
+pub const XMRIG_THERMAL_HYSTERESIS: &str = "Degrees the CPU must cool down under the ceiling before XMRig mines again with all its threads";
+pub const XMRIG_THERMAL_THREADS: &str =
+    "Threads kept while the CPU is too hot. [0] pauses XMRig, as custom arguments do";
 pub const XMRIG_PATH_NOT_FILE: &str = "XMRig binary not found at the given PATH in the Gupaxxtab! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where XMRig is located.";
 pub const XMRIG_PATH_NOT_VALID: &str = "XMRig binary at the given PATH in the Gupaxxtab doesn't look like XMRig! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where XMRig is located.";
 pub const XMRIG_PATH_OK: &str = "XMRig was found at the given PATH";
@@ -15,6 +15,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod args;
 pub mod constants;
 pub mod errors;
 pub mod ferris;
@@ -24,4 +25,5 @@ pub mod panic;
 pub mod regex;
 pub mod resets;
 pub mod sudo;
+pub mod topology;
 pub mod xmr;
@@ -17,6 +17,7 @@
 
 // Some regexes used throughout Gupax.
 
+use crate::helper::p2pool::MergeMiningStatus;
 use crate::helper::xvb::nodes::Pool;
 use log::{error, warn};
 use once_cell::sync::Lazy;
@@ -36,6 +37,8 @@ pub struct Regexes {
     pub ipv4: Regex,
     pub domain: Regex,
     pub port: Regex,
+    pub onion: Regex,
+    pub tari_address: Regex,
 }
 
 impl Regexes {
@@ -48,6 +51,8 @@ impl Regexes {
 			ipv4: Regex::new(r#"^((25[0-5]|(2[0-4]|1\d|[1-9]|)\d)\.?\b){4}$"#).unwrap(),
 			domain: Regex::new(r#"^[A-Za-z0-9-.]+[A-Za-z0-9-]+$"#).unwrap(),
 			port: Regex::new(r#"^([1-9][0-9]{0,3}|[1-5][0-9]{4}|6[0-4][0-9]{3}|65[0-4][0-9]{2}|655[0-2][0-9]|6553[0-5])$"#).unwrap(),
+			onion: Regex::new(r#"^[a-z2-7]{56}\.onion$"#).unwrap(),
+			tari_address: Regex::new("^[1-9A-HJ-NP-Za-km-z]+$").unwrap(),
 		}
     }
 
@@ -62,6 +67,25 @@ impl Regexes {
             && !address.contains('O')
             && !address.contains('l')
     }
+
+    #[inline]
+    // Check if a Tari address is correct.
+    // Only checks the Base58 alphabet and the length of a single (~48) or dual (~91) address, not the checksum.
+    pub fn tari_addr_ok(address: &str) -> bool {
+        (40..=100).contains(&address.len()) && REGEXES.tari_address.is_match(address)
+    }
+
+    #[inline]
+    // Check if a [host:port] is correct, the host being an IPv4 or a domain.
+    pub fn host_port_ok(host_port: &str) -> bool {
+        match host_port.rsplit_once(':') {
+            Some((host, port)) => {
+                (REGEXES.ipv4.is_match(host) || REGEXES.domain.is_match(host))
+                    && REGEXES.port.is_match(port)
+            }
+            None => false,
+        }
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- [P2poolRegex]
@@ -156,10 +180,11 @@ pub fn nb_current_shares(s: &str) -> Option<u32> {
     }
     None
 }
+static XMRIG_CURRENT_POOL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(use pool|new job from) (?P<pool>.*:\d{1,5})(| diff)").unwrap());
+// The last pool of the output is the one used now, XMRig may have switched pools in the same output.
 pub fn detect_pool_xmrig(s: &str, proxy_port: u16, p2pool_port: u16) -> Option<Pool> {
-    static CURRENT_SHARE: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(use pool|new job from) (?P<pool>.*:\d{1,5})(| diff)").unwrap());
-    if let Some(c) = CURRENT_SHARE.captures(s) {
+    if let Some(c) = XMRIG_CURRENT_POOL.captures_iter(s).last() {
         if let Some(m) = c.name("pool") {
             match m.as_str() {
                 // if user change address of local p2pool, it could create issue
@@ -195,6 +220,11 @@ pub fn detect_pool_xmrig(s: &str, proxy_port: u16, p2pool_port: u16) -> Option<P
     );
     None
 }
+// Index in [urls] ([ip:port] of every [--url] given to XMRig) of the last pool used in the output.
+pub fn detect_pool_entry_xmrig(s: &str, urls: &[String]) -> Option<usize> {
+    let c = XMRIG_CURRENT_POOL.captures_iter(s).last()?;
+    urls.iter().position(|url| *url == c["pool"])
+}
 pub fn estimated_hr(s: &str) -> Option<f32> {
     static CURRENT_SHARE: Lazy<Regex> = Lazy::new(|| {
         Regex::new(r"(?P<nb>[-+]?[0-9]*\.?[0-9]+([eE][-+]?[0-9]+)?) (?P<unit>.*)H/s").unwrap()
@@ -270,6 +300,27 @@ pub fn contains_zmq_failure(l: &str) -> bool {
     });
     LINE_SHARE.is_match(l)
 }
+/// state of merge mining given by the last line of a merge mining client of P2Pool, if any.
+pub fn merge_mining_status(output: &str) -> Option<MergeMiningStatus> {
+    static LINE_CLIENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"MergeMiningClient").unwrap());
+    static LINE_FAILURE: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)(fail|error|couldn't|can't|timeout|timed out|disconnected)").unwrap()
+    });
+    static LINE_CONNECTING: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)connecting").unwrap());
+    output
+        .lines()
+        .rev()
+        .find(|l| LINE_CLIENT.is_match(l))
+        .map(|l| {
+            if LINE_FAILURE.is_match(l) {
+                MergeMiningStatus::Error
+            } else if LINE_CONNECTING.is_match(l) {
+                MergeMiningStatus::Connecting
+            } else {
+                MergeMiningStatus::Connected
+            }
+        })
+}
 
 //---------------------------------------------------------------------------------------------------- TEST
 #[cfg(test)]
@@ -301,6 +352,31 @@ mod test {
         }
         assert!(!Regex::is_match(&r.port, "0"));
         assert!(!Regex::is_match(&r.port, "65536"));
+        assert!(Regex::is_match(
+            &r.onion,
+            "p2pooltxkkhxkfmzc2xamkmqqdqezlyzx2ab4x3dvotqowxgbyn2ilid.onion"
+        ));
+        assert!(!Regex::is_match(&r.onion, "p2pool.onion"));
+    }
+
+    #[test]
+    fn host_port() {
+        assert!(Regexes::host_port_ok("127.0.0.1:9050"));
+        assert!(Regexes::host_port_ok("my.node.com:18102"));
+        assert!(!Regexes::host_port_ok("127.0.0.1"));
+        assert!(!Regexes::host_port_ok("127.0.0.1:0"));
+        assert!(!Regexes::host_port_ok(":9050"));
+    }
+
+    #[test]
+    fn tari_address() {
+        assert!(Regexes::tari_addr_ok(
+            "12FrDe7ebUeZ5Ppr5xGSCKMEbR5uL3MJvvzh1TDYwXsSgHcXRZxJBcDeKmTeGvznDdALhS6bLaWQQ6JdnsXaj7a6dWs"
+        ));
+        assert!(!Regexes::tari_addr_ok("12FrDe7ebUeZ"));
+        assert!(!Regexes::tari_addr_ok(
+            "44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW0"
+        ));
     }
 
     #[test]
@@ -336,4 +412,58 @@ mod test {
         );
         assert_eq!(r.new_job.find(text2).unwrap().as_str(), "new job");
     }
+
+    #[test]
+    fn detect_pool_entry() {
+        let urls = [
+            "127.0.0.1:3333".to_string(),
+            "pool.example.com:443".to_string(),
+        ];
+        let output = "[2025-01-12 12:00:00.000]  net      use pool 127.0.0.1:3333  127.0.0.1\n[2025-01-12 12:00:30.000]  net      127.0.0.1:3333 connect error: \"connection refused\"\n[2025-01-12 12:00:31.000]  net      use pool pool.example.com:443 TLSv1.3 203.0.113.7";
+        assert_eq!(detect_pool_entry_xmrig(output, &urls), Some(1));
+        assert_eq!(
+            detect_pool_entry_xmrig(
+                "[2025-01-12 12:00:00.000]  net      new job from 127.0.0.1:3333 diff 100K algo rx/0 height 3000000",
+                &urls
+            ),
+            Some(0)
+        );
+        // a pool set by XvB
+        assert_eq!(
+            detect_pool_entry_xmrig(
+                "[2025-01-12 12:00:00.000]  net      use pool eu.xmrvsbeast.com:4247 TLSv1.3 1.2.3.4",
+                &urls
+            ),
+            None
+        );
+        // the pool used now is the last one, as for the entry.
+        assert_eq!(
+            detect_pool_xmrig(output, 3355, 3333),
+            Some(Pool::Custom("pool.example.com".to_string(), 443))
+        );
+    }
+
+    #[test]
+    fn merge_mining_status_from_output() {
+        use super::merge_mining_status;
+        use crate::helper::p2pool::MergeMiningStatus;
+        assert_eq!(merge_mining_status("NOTICE  P2Pool new block found"), None);
+        assert_eq!(
+            merge_mining_status(
+                "NOTICE  2025-01-01 10:00:00.0000 MergeMiningClientTari Connecting to tari://127.0.0.1:18102"
+            ),
+            Some(MergeMiningStatus::Connecting)
+        );
+        let output = "NOTICE  2025-01-01 10:00:00.0000 MergeMiningClientTari Connecting to tari://127.0.0.1:18102\nNOTICE  2025-01-01 10:00:01.0000 MergeMiningClientTari Tari tip changed: height 12345";
+        assert_eq!(
+            merge_mining_status(output),
+            Some(MergeMiningStatus::Connected)
+        );
+        assert_eq!(
+            merge_mining_status(
+                "WARNING 2025-01-01 10:00:02.0000 MergeMiningClientTari GetNewBlockTemplateWithCoinbases failed"
+            ),
+            Some(MergeMiningStatus::Error)
+        );
+    }
 }
@@ -18,23 +18,158 @@
 // Handling of [sudo] for XMRig.
 // [zeroize] is used to wipe the memory after use.
 // Only gets imported in [main.rs] for Unix.
+// On Linux, a one-time setup with [sudo] gives XMRig what it needs to be started without it:
+// hugepages, 1GB pages, the msr module, the MSR devices owned by the user and the capability of the binary.
 
 use crate::{
     constants::*,
-    disk::state::{P2pool, Xmrig, XmrigProxy},
+    disk::state::{P2pool, Xmrig, XmrigPrivilege, XmrigProxy},
     helper::{Helper, ProcessSignal},
 };
 use enclose::enc;
 use log::*;
 use std::{
-    io::Write,
-    path::Path,
+    io::{Read, Write},
+    path::{Path, PathBuf},
     process::*,
     sync::{Arc, Mutex},
     thread,
 };
 use zeroize::Zeroize;
 
+// Hugepages of 2MB for the RandomX dataset, one more is needed by thread.
+pub const XMRIG_HUGEPAGES_DATASET: u32 = 1168;
+// Pages of 1GB for the RandomX dataset, by NUMA node.
+pub const XMRIG_PAGES_1GB: u32 = 3;
+// Capability to write the MSR registers without root, the access to the devices is given by a udev rule.
+pub const XMRIG_CAPABILITIES: &str = "cap_sys_rawio=ep";
+// Files written by the setup, removed by the reset.
+pub const XMRIG_SYSCTL_CONF: &str = "/etc/sysctl.d/99-gupaxx-xmrig.conf";
+pub const XMRIG_MODULES_LOAD_CONF: &str = "/etc/modules-load.d/gupaxx-msr.conf";
+pub const XMRIG_MODPROBE_CONF: &str = "/etc/modprobe.d/gupaxx-msr.conf";
+pub const XMRIG_UDEV_RULE: &str = "/etc/udev/rules.d/99-gupaxx-msr.rules";
+// Pools of 1GB pages, one by NUMA node.
+const XMRIG_PAGES_1GB_POOLS: &str =
+    "/sys/devices/system/node/node*/hugepages/hugepages-1048576kB/nr_hugepages";
+// Comment of the sysctl file keeping a value from before the first setup, restored by the reset.
+const XMRIG_SYSCTL_BEFORE: &str = "# before ";
+
+// Privileged setup asked from the XMRig tab, done with the password of [sudo].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrivilegeSetup {
+    pub xmrig_path: PathBuf,
+    pub hugepages: u32,
+    pub pages_1gb: bool,
+    // undo the setup instead, asked by the reset
+    pub remove: bool,
+}
+
+impl PrivilegeSetup {
+    pub fn new(xmrig_path: &Path, max_threads: u16) -> Self {
+        Self {
+            xmrig_path: xmrig_path.to_path_buf(),
+            hugepages: XMRIG_HUGEPAGES_DATASET + max_threads as u32,
+            pages_1gb: cpu_has_1gb_pages(),
+            remove: false,
+        }
+    }
+    pub fn removal(xmrig_path: &Path) -> Self {
+        Self {
+            xmrig_path: xmrig_path.to_path_buf(),
+            hugepages: 0,
+            pages_1gb: false,
+            remove: true,
+        }
+    }
+    fn quoted_path(&self) -> String {
+        self.xmrig_path.display().to_string().replace('\'', r"'\''")
+    }
+    // Shell script run as root, the settings are kept across reboots except the 1GB pages.
+    // The MSR devices are given to the user calling sudo, the capability alone does not open them.
+    // The hugepages from before the first setup are kept in the sysctl file, a new setup keeps them.
+    pub fn script(&self) -> String {
+        if self.remove {
+            return self.removal_script();
+        }
+        let path = self.quoted_path();
+        let mut script = format!(
+            "set -e
+if [ -e {XMRIG_SYSCTL_CONF} ]; then
+before=$(grep '^{XMRIG_SYSCTL_BEFORE}' {XMRIG_SYSCTL_CONF} || true)
+else
+before=\"{XMRIG_SYSCTL_BEFORE}vm.nr_hugepages=$(sysctl -n vm.nr_hugepages)\"
+"
+        );
+        if self.pages_1gb {
+            script.push_str(&format!(
+                "for f in {XMRIG_PAGES_1GB_POOLS}; do if [ -e \"$f\" ]; then before=\"$before
+{XMRIG_SYSCTL_BEFORE}$f=$(cat \"$f\")\"; fi; done
+"
+            ));
+        }
+        script.push_str(&format!(
+            "fi
+sysctl -w vm.nr_hugepages={pages}
+printf '%s\\nvm.nr_hugepages={pages}\\n' \"$before\" > {XMRIG_SYSCTL_CONF}
+modprobe msr allow_writes=on
+echo msr > {XMRIG_MODULES_LOAD_CONF}
+echo 'options msr allow_writes=on' > {XMRIG_MODPROBE_CONF}
+echo \"SUBSYSTEM==\\\"msr\\\", OWNER=\\\"$SUDO_USER\\\", MODE=\\\"0600\\\"\" > {XMRIG_UDEV_RULE}
+udevadm control --reload-rules
+udevadm trigger --subsystem-match=msr
+",
+            pages = self.hugepages
+        ));
+        if self.pages_1gb {
+            script.push_str(&format!(
+                "for f in {XMRIG_PAGES_1GB_POOLS}; do echo {XMRIG_PAGES_1GB} > \"$f\"; done\n"
+            ));
+        }
+        script.push_str(&format!("setcap {XMRIG_CAPABILITIES} '{path}'\n"));
+        script
+    }
+    // Puts back the hugepages kept by the setup, or gives them back without a kept value, then removes every file it wrote.
+    // The binary may have been replaced or removed since, so its capability is removed if present.
+    fn removal_script(&self) -> String {
+        let path = self.quoted_path();
+        format!(
+            "if [ -e {XMRIG_SYSCTL_CONF} ]; then
+pages=$(sed -n 's/^{XMRIG_SYSCTL_BEFORE}vm.nr_hugepages=//p' {XMRIG_SYSCTL_CONF})
+sysctl -w vm.nr_hugepages=\"${{pages:-0}}\"
+sed -n 's|^{XMRIG_SYSCTL_BEFORE}\\(/sys/.*\\)=\\([0-9]*\\)$|\\2 \\1|p' {XMRIG_SYSCTL_CONF} | while read -r n f; do echo \"$n\" > \"$f\"; done
+fi
+rm -f {XMRIG_SYSCTL_CONF} {XMRIG_MODULES_LOAD_CONF} {XMRIG_MODPROBE_CONF} {XMRIG_UDEV_RULE}
+udevadm control --reload-rules || true
+udevadm trigger --subsystem-match=msr || true
+[ -e '{path}' ] && setcap -r '{path}' || true
+"
+        )
+    }
+}
+
+/// The CPU supports 1GB pages, flag [pdpe1gb] of /proc/cpuinfo.
+pub fn cpu_has_1gb_pages() -> bool {
+    std::fs::read_to_string("/proc/cpuinfo").is_ok_and(|info| {
+        info.lines()
+            .filter(|l| l.starts_with("flags"))
+            .any(|l| l.split_whitespace().any(|flag| flag == "pdpe1gb"))
+    })
+}
+
+/// The capabilities given by the setup are still on the binary.
+/// If [getcap] can not be found, they are supposed to be.
+pub fn capabilities_set(path: &Path) -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    for getcap in ["getcap", "/usr/sbin/getcap", "/sbin/getcap"] {
+        if let Ok(output) = Command::new(getcap).arg(path).output() {
+            return String::from_utf8_lossy(&output.stdout).contains("cap_sys_rawio");
+        }
+    }
+    true
+}
+
 #[allow(dead_code)] // for dummy value windows
 #[derive(Debug, Clone)]
 pub struct SudoState {
@@ -45,6 +180,9 @@ pub struct SudoState {
     pub msg: String,   // The message shown to the user if unsuccessful
     pub pass: String,  // The actual password wrapped in a [SecretVec]
     pub signal: ProcessSignal, // Main GUI will set this depending on if we want [Start] or [Restart]
+    pub setup: Option<PrivilegeSetup>, // Setup to run instead of starting XMRig once the password is tested
+    pub setup_done: Option<XmrigPrivilege>, // Mode to keep in the state, taken by the XMRig tab
+    pub setup_msg: String,             // Result of the last setup or detection
 }
 
 impl Default for SudoState {
@@ -66,6 +204,9 @@ impl SudoState {
             msg: String::new(),
             pass: String::new(),
             signal: ProcessSignal::None,
+            setup: None,
+            setup_done: None,
+            setup_msg: String::new(),
         }
     }
     #[cold]
@@ -80,6 +221,9 @@ impl SudoState {
             msg: "".to_string(),
             pass: String::with_capacity(256),
             signal: ProcessSignal::None,
+            setup: None,
+            setup_done: None,
+            setup_msg: String::new(),
         }
     }
 
@@ -91,6 +235,7 @@ impl SudoState {
         let mut state = state.lock().unwrap();
         state.testing = false;
         state.success = false;
+        state.setup = None;
         //		state.signal = ProcessSignal::None;
     }
 
@@ -188,7 +333,16 @@ impl SudoState {
             if let Err(e) = sudo.kill() {
                 warn!("Sudo | Kill error (it probably already exited): {}", e);
             }
-            if state.lock().unwrap().success {
+            // the setup is kept if the password is wrong, for the next try.
+            let success = state.lock().unwrap().success;
+            let setup = if success {
+                state.lock().unwrap().setup.take()
+            } else {
+                None
+            };
+            if let Some(setup) = setup {
+                Self::run_setup(&state, &setup);
+            } else if state.lock().unwrap().success {
                 match state.lock().unwrap().signal {
                     ProcessSignal::Restart => crate::helper::Helper::restart_xmrig(
                         &helper,
@@ -216,4 +370,143 @@ impl SudoState {
             state.lock().unwrap().testing = false;
         }));
     }
+
+    #[cold]
+    #[inline(never)]
+    // Run the privileged setup as root, the password is given through STDIN then wiped.
+    fn run_setup(state: &Arc<Mutex<Self>>, setup: &PrivilegeSetup) {
+        info!(
+            "Sudo | Running the privileged {} of XMRig...",
+            if setup.remove { "removal" } else { "setup" }
+        );
+        let result = Command::new("sudo")
+            .args(["--stdin", "--prompt=", "--", "sh", "-c", &setup.script()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    writeln!(stdin, "{}", state.lock().unwrap().pass)?;
+                }
+                let mut stderr = String::new();
+                if let Some(mut err) = child.stderr.take() {
+                    err.read_to_string(&mut stderr)?;
+                }
+                Ok((child.wait()?, stderr))
+            });
+        Self::wipe(state);
+        let mut state = state.lock().unwrap();
+        match result {
+            Ok((status, _)) if status.success() && setup.remove => {
+                info!("Sudo | Privileged removal ... OK");
+                state.setup_done = Some(XmrigPrivilege::Sudo);
+                state.setup_msg =
+                    "Setup removed, the password of sudo is asked on each start".to_string();
+            }
+            Ok((status, _)) if status.success() => {
+                info!("Sudo | Privileged setup ... OK");
+                state.setup_done = Some(XmrigPrivilege::Capabilities);
+                state.setup_msg = "Setup done, XMRig is now started without sudo".to_string();
+            }
+            Ok((status, stderr)) => {
+                error!(
+                    "Sudo | Privileged setup ... FAIL ... {}: {}",
+                    status, stderr
+                );
+                state.setup_msg = format!("Setup failed: {}", stderr.trim());
+            }
+            Err(e) => {
+                error!("Sudo | Privileged setup ... FAIL ... {}", e);
+                state.setup_msg = format!("Setup failed: {}", e);
+            }
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Check if sudo can be used without a password, configured in sudoers.
+    pub fn detect_no_password(state: &Arc<Mutex<Self>>) {
+        let state = Arc::clone(state);
+        state.lock().unwrap().testing = true;
+        thread::spawn(move || {
+            // the timestamp is reset first, a password typed recently must not count.
+            let no_password = !Helper::password_needed();
+            let mut state = state.lock().unwrap();
+            if no_password {
+                info!("Sudo | Without password ... OK");
+                state.setup_done = Some(XmrigPrivilege::SudoNoPassword);
+                state.setup_msg =
+                    "sudo does not need a password, it is not asked anymore".to_string();
+            } else {
+                info!("Sudo | Without password ... FAIL");
+                state.setup_msg = "sudo needs a password, it is still asked on start".to_string();
+            }
+            state.testing = false;
+        });
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn privilege_setup_script() {
+        let setup = PrivilegeSetup {
+            xmrig_path: PathBuf::from("/home/user/it's gupaxx/xmrig/xmrig"),
+            hugepages: XMRIG_HUGEPAGES_DATASET + 8,
+            pages_1gb: false,
+            remove: false,
+        };
+        let script = setup.script();
+        assert!(script.starts_with("set -e\n"));
+        assert!(script.contains("sysctl -w vm.nr_hugepages=1176\n"));
+        // the value from before the first setup is kept for the reset.
+        assert!(
+            script.contains("before=\"# before vm.nr_hugepages=$(sysctl -n vm.nr_hugepages)\"\n")
+        );
+        assert!(script.contains(
+            "printf '%s\\nvm.nr_hugepages=1176\\n' \"$before\" > /etc/sysctl.d/99-gupaxx-xmrig.conf\n"
+        ));
+        assert!(script.contains("modprobe msr allow_writes=on\n"));
+        assert!(!script.contains("hugepages-1048576kB"));
+        assert!(script.contains(
+            "echo \"SUBSYSTEM==\\\"msr\\\", OWNER=\\\"$SUDO_USER\\\", MODE=\\\"0600\\\"\" > /etc/udev/rules.d/99-gupaxx-msr.rules\n"
+        ));
+        assert!(!script.contains("cap_dac_override"));
+        assert!(
+            script.ends_with("setcap cap_sys_rawio=ep '/home/user/it'\\''s gupaxx/xmrig/xmrig'\n")
+        );
+        let setup = PrivilegeSetup {
+            pages_1gb: true,
+            ..setup
+        };
+        let script = setup.script();
+        assert!(script.contains("hugepages-1048576kB/nr_hugepages; do echo 3 > \"$f\"; done"));
+        assert!(script.contains("# before $f=$(cat \"$f\")"));
+    }
+
+    #[test]
+    fn privilege_removal_script() {
+        let script =
+            PrivilegeSetup::removal(Path::new("/home/user/it's gupaxx/xmrig/xmrig")).script();
+        // every file written by the setup is removed.
+        for file in [
+            XMRIG_SYSCTL_CONF,
+            XMRIG_MODULES_LOAD_CONF,
+            XMRIG_MODPROBE_CONF,
+            XMRIG_UDEV_RULE,
+        ] {
+            assert!(script.contains(file));
+        }
+        assert!(script.contains("\nrm -f /etc/sysctl.d/99-gupaxx-xmrig.conf "));
+        // the hugepages kept by the setup are put back before its file is removed.
+        assert!(script.starts_with("if [ -e /etc/sysctl.d/99-gupaxx-xmrig.conf ]; then\n"));
+        assert!(script.contains("sysctl -w vm.nr_hugepages=\"${pages:-0}\"\n"));
+        assert!(!script.contains("vm.nr_hugepages=0"));
+        assert!(!script.contains("set -e"));
+        assert!(script.ends_with("setcap -r '/home/user/it'\\''s gupaxx/xmrig/xmrig' || true\n"));
+    }
 }
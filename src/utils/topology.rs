@@ -0,0 +1,233 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// CPU topology used to pin the threads of XMRig: NUMA nodes, physical cores and their logical CPUs.
+// The logical CPUs are the ones listed by [sysinfo], with the same index.
+// NUMA nodes and physical cores are only known on Linux, from [/sys/devices/system].
+// Elsewhere every logical CPU is its own core on a single node.
+
+use log::*;
+use std::{collections::BTreeMap, fs, path::Path};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuTopology {
+    pub nodes: Vec<NumaNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaNode {
+    pub id: u16,
+    pub cores: Vec<PhysicalCore>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalCore {
+    // logical CPUs sharing this core, the first one is used by [one_per_core]
+    pub cpus: Vec<u16>,
+}
+
+impl CpuTopology {
+    pub fn detect(cpus: usize) -> Self {
+        #[cfg(target_os = "linux")]
+        if let Some(topology) = Self::from_sysfs(Path::new("/sys/devices/system"), cpus) {
+            info!(
+                "CPU topology | {} NUMA node(s) ... OK",
+                topology.nodes.len()
+            );
+            return topology;
+        }
+        Self::flat(cpus)
+    }
+    pub fn flat(cpus: usize) -> Self {
+        Self {
+            nodes: vec![NumaNode {
+                id: 0,
+                cores: (0..cpus as u16)
+                    .map(|cpu| PhysicalCore { cpus: vec![cpu] })
+                    .collect(),
+            }],
+        }
+    }
+    // [root] is [/sys/devices/system], given for the tests.
+    pub fn from_sysfs(root: &Path, cpus: usize) -> Option<Self> {
+        // node id -> logical CPUs, a system without NUMA has no [node] directory.
+        let mut nodes = BTreeMap::new();
+        if let Ok(dir) = fs::read_dir(root.join("node")) {
+            for entry in dir.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(id) = name
+                    .strip_prefix("node")
+                    .and_then(|id| id.parse::<u16>().ok())
+                else {
+                    continue;
+                };
+                if let Ok(list) = fs::read_to_string(entry.path().join("cpulist")) {
+                    nodes.insert(id, parse_cpulist(&list));
+                }
+            }
+        }
+        if nodes.is_empty() {
+            nodes.insert(0, (0..cpus as u16).collect());
+        }
+        let mut topology = Self::default();
+        for (id, node_cpus) in nodes {
+            // (package, core id) -> logical CPUs
+            let mut cores: BTreeMap<(u32, u32), Vec<u16>> = BTreeMap::new();
+            for cpu in node_cpus.into_iter().filter(|cpu| (*cpu as usize) < cpus) {
+                let path = root.join(format!("cpu/cpu{cpu}/topology"));
+                let read = |file: &str| {
+                    fs::read_to_string(path.join(file))
+                        .ok()
+                        .and_then(|s| s.trim().parse::<u32>().ok())
+                };
+                let (Some(package), Some(core)) = (read("physical_package_id"), read("core_id"))
+                else {
+                    warn!("CPU topology | cpu{} has no topology", cpu);
+                    return None;
+                };
+                cores.entry((package, core)).or_default().push(cpu);
+            }
+            let mut cores: Vec<PhysicalCore> = cores
+                .into_values()
+                .map(|cpus| PhysicalCore { cpus })
+                .collect();
+            cores.sort_by_key(|core| core.cpus[0]);
+            if !cores.is_empty() {
+                topology.nodes.push(NumaNode { id, cores });
+            }
+        }
+        (!topology.nodes.is_empty()).then_some(topology)
+    }
+    pub fn cpus(&self) -> usize {
+        self.nodes
+            .iter()
+            .flat_map(|node| &node.cores)
+            .map(|core| core.cpus.len())
+            .sum()
+    }
+    pub fn node_of(&self, cpu: u16) -> Option<u16> {
+        self.nodes
+            .iter()
+            .find(|node| node.cores.iter().any(|core| core.cpus.contains(&cpu)))
+            .map(|node| node.id)
+    }
+    // Threads ordered node by node, so each NUMA node gets its threads together.
+    pub fn sort(&self, cpus: &mut [u16]) {
+        cpus.sort_by_key(|cpu| (self.node_of(*cpu), *cpu));
+    }
+    // The first logical CPU of every physical core.
+    pub fn one_per_core(&self) -> Vec<u16> {
+        self.nodes
+            .iter()
+            .flat_map(|node| &node.cores)
+            .map(|core| core.cpus[0])
+            .collect()
+    }
+    // [threads] spread evenly across the NUMA nodes, using the physical cores before their siblings.
+    pub fn spread(&self, threads: u16) -> Vec<u16> {
+        let mut queues: Vec<Vec<u16>> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let siblings = node.cores.iter().map(|c| c.cpus.len()).max().unwrap_or(0);
+                let mut queue: Vec<u16> = (0..siblings)
+                    .flat_map(|i| node.cores.iter().filter_map(move |c| c.cpus.get(i)))
+                    .copied()
+                    .collect();
+                queue.reverse();
+                queue
+            })
+            .collect();
+        let mut cpus = Vec::with_capacity(threads as usize);
+        while cpus.len() < threads as usize && queues.iter().any(|q| !q.is_empty()) {
+            for queue in queues.iter_mut() {
+                if cpus.len() < threads as usize {
+                    if let Some(cpu) = queue.pop() {
+                        cpus.push(cpu);
+                    }
+                }
+            }
+        }
+        self.sort(&mut cpus);
+        cpus
+    }
+}
+
+// Parse a list of CPUs as written by Linux, like [0-3,8,10-11].
+pub fn parse_cpulist(list: &str) -> Vec<u16> {
+    let mut cpus = vec![];
+    for range in list.trim().split(',').filter(|r| !r.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<u16>(), end.parse::<u16>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = range.parse() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cpulist() {
+        assert_eq!(parse_cpulist("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpulist("5"), vec![5]);
+        assert!(parse_cpulist("").is_empty());
+    }
+
+    #[test]
+    fn sysfs_topology() {
+        // 2 NUMA nodes of 2 cores with 2 threads each, siblings are N and N+4.
+        let root = std::env::temp_dir().join(format!("gupaxx_topology_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (node, list) in [(0, "0-1,4-5"), (1, "2-3,6-7")] {
+            let path = root.join(format!("node/node{node}"));
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("cpulist"), list).unwrap();
+        }
+        for cpu in 0..8 {
+            let path = root.join(format!("cpu/cpu{cpu}/topology"));
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("physical_package_id"), "0\n").unwrap();
+            fs::write(path.join("core_id"), format!("{}\n", cpu % 4)).unwrap();
+        }
+        let topology = CpuTopology::from_sysfs(&root, 8).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(topology.nodes.len(), 2);
+        assert_eq!(topology.nodes[1].cores[0].cpus, vec![2, 6]);
+        assert_eq!(topology.cpus(), 8);
+        assert_eq!(topology.one_per_core(), vec![0, 1, 2, 3]);
+        assert_eq!(topology.node_of(7), Some(1));
+        // physical cores first, the same number of threads on each node.
+        assert_eq!(topology.spread(4), vec![0, 1, 2, 3]);
+        assert_eq!(topology.spread(6), vec![0, 1, 4, 2, 3, 6]);
+        assert_eq!(topology.spread(20).len(), 8);
+        // without NUMA, a single node.
+        let flat = CpuTopology::flat(3);
+        assert_eq!(flat.one_per_core(), vec![0, 1, 2]);
+        assert_eq!(flat.spread(2), vec![0, 1]);
+    }
+}